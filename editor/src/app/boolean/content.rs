@@ -0,0 +1,120 @@
+use i_triangle::i_overlay::i_shape::int::path::IntPaths;
+use iced::widget::Row;
+use qurvy::convert::grid::Grid;
+use qurvy::convert::to_int::ToInt;
+use qurvy::int::bool::core::fill_rule::FillRule;
+use qurvy::int::bool::core::overlay::{Overlay, ShapeType};
+use qurvy::int::bool::core::overlay_rule::OverlayRule;
+use crate::app::intersect::control::ModeOption;
+use crate::app::main::{EditorApp, AppMessage};
+use crate::app::screen::ScreenState;
+use crate::compat::convert::Convert;
+use crate::data::boolean::BooleanResource;
+
+pub(crate) struct BooleanState {
+    pub(crate) screen: ScreenState,
+    pub(crate) mode: ModeOption,
+    pub(crate) overlay_rule: OverlayRule,
+    pub(crate) fill_rule: FillRule,
+    pub(crate) subj_count: usize,
+    pub(crate) solution: IntPaths,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum BooleanMessage {
+    TestSelected(usize),
+    ModeSelected(ModeOption),
+    OverlayRuleSelected(OverlayRule),
+    FillRuleSelected(FillRule),
+}
+
+impl EditorApp {
+    pub(crate) fn boolean_content(&self) -> Row<AppMessage> {
+        self.screen_content(
+            self.app_resource.boolean.count,
+            self.state.boolean.screen.test,
+            |index| AppMessage::Boolean(BooleanMessage::TestSelected(index)),
+            self.boolean_workspace(),
+        )
+    }
+
+    pub(crate) fn boolean_update(&mut self, message: BooleanMessage) {
+        match message {
+            BooleanMessage::TestSelected(index) => self.boolean_set_test(index),
+            BooleanMessage::ModeSelected(mode) => self.boolean_update_mode(mode),
+            BooleanMessage::OverlayRuleSelected(rule) => self.boolean_update_overlay_rule(rule),
+            BooleanMessage::FillRuleSelected(rule) => self.boolean_update_fill_rule(rule),
+        }
+    }
+
+    pub(crate) fn boolean_set_test(&mut self, index: usize) {
+        self.state.boolean.load_test(index, &mut self.app_resource.boolean);
+        self.state.boolean.update_solution();
+    }
+
+    fn boolean_update_mode(&mut self, mode: ModeOption) {
+        self.state.boolean.mode = mode;
+        self.state.boolean.update_solution();
+    }
+
+    fn boolean_update_overlay_rule(&mut self, rule: OverlayRule) {
+        self.state.boolean.overlay_rule = rule;
+        self.state.boolean.update_solution();
+    }
+
+    fn boolean_update_fill_rule(&mut self, rule: FillRule) {
+        self.state.boolean.fill_rule = rule;
+        self.state.boolean.update_solution();
+    }
+}
+
+impl BooleanState {
+    pub(crate) fn new(resource: &mut BooleanResource) -> Self {
+        let mut state = BooleanState {
+            screen: ScreenState::new(resource.count),
+            mode: ModeOption::Edit,
+            overlay_rule: OverlayRule::Union,
+            fill_rule: FillRule::NonZero,
+            subj_count: 0,
+            solution: Vec::new(),
+        };
+
+        state.load_test(0, resource);
+        state.update_solution();
+        state
+    }
+
+    fn load_test(&mut self, index: usize, resource: &mut BooleanResource) {
+        if let Some(test) = resource.load(index) {
+            let grid = Grid::debug();
+            let mut curves = Vec::with_capacity(test.subj.len() + test.clip.len());
+            curves.extend(test.subj.iter().map(|c| c.to_int(&grid)));
+            self.subj_count = test.subj.len();
+            curves.extend(test.clip.iter().map(|c| c.to_int(&grid)));
+            self.fill_rule = test.fill_rule;
+            self.screen.load_test(index, curves);
+        }
+    }
+
+    pub(crate) fn update_solution(&mut self) {
+        let subj_count = self.subj_count;
+        let mut overlay = Overlay::new();
+        for (index, curve) in self.screen.curves.iter().enumerate() {
+            let shape_type = if index < subj_count { ShapeType::Subject } else { ShapeType::Clip };
+            overlay.add_bezier_path(curve, shape_type);
+        }
+
+        let contours = overlay.overlay(self.overlay_rule, self.fill_rule);
+        self.solution = contours
+            .iter()
+            .map(|contour| {
+                let mut path: Vec<_> = contour.iter().map(|p| p.convert()).collect();
+                // the stroke builder draws open paths, repeat the first point to close it
+                if let Some(&first) = path.first() {
+                    path.push(first);
+                }
+                path
+            })
+            .collect::<IntPaths>();
+    }
+}
@@ -0,0 +1,130 @@
+use crate::app::boolean::content::BooleanMessage;
+use crate::app::intersect::control::ModeOption;
+use crate::app::main::{EditorApp, AppMessage};
+use iced::{Alignment, Length};
+use iced::widget::{Column, Container, pick_list, Row, Text};
+use qurvy::int::bool::core::fill_rule::FillRule;
+use qurvy::int::bool::core::overlay_rule::OverlayRule;
+
+impl EditorApp {
+    pub(crate) fn boolean_control(&self) -> Column<AppMessage> {
+        let mode_pick_list = Self::boolean_pick_list(
+            "Mode:",
+            &ModeOption::ALL[..],
+            self.state.boolean.mode,
+            on_select_mode,
+        );
+
+        let overlay_pick_list = Self::boolean_pick_list(
+            "Operation:",
+            &OverlayRuleOption::ALL[..],
+            OverlayRuleOption(self.state.boolean.overlay_rule),
+            on_select_overlay_rule,
+        );
+
+        let fill_pick_list = Self::boolean_pick_list(
+            "Fill Rule:",
+            &FillRuleOption::ALL[..],
+            FillRuleOption(self.state.boolean.fill_rule),
+            on_select_fill_rule,
+        );
+
+        Column::new()
+            .push(mode_pick_list)
+            .push(overlay_pick_list)
+            .push(fill_pick_list)
+    }
+
+    fn boolean_pick_list<'a, T>(
+        title: &'a str,
+        options: &'a [T],
+        selected: T,
+        on_select: fn(T) -> AppMessage,
+    ) -> Row<'a, AppMessage>
+    where
+        T: ToString + PartialEq + Clone + 'a,
+    {
+        Row::new()
+            .push(Text::new(title)
+                .width(Length::Fixed(90.0))
+                .height(Length::Fill)
+                .align_y(Alignment::Center))
+            .push(
+                Container::new(
+                    pick_list(
+                        options,
+                        Some(selected),
+                        on_select,
+                    ).width(Length::Fixed(160.0))
+                )
+                    .height(Length::Fill)
+                    .align_y(Alignment::Center)
+            ).height(Length::Fixed(40.0))
+    }
+}
+
+fn on_select_mode(option: ModeOption) -> AppMessage {
+    AppMessage::Boolean(BooleanMessage::ModeSelected(option))
+}
+
+fn on_select_overlay_rule(option: OverlayRuleOption) -> AppMessage {
+    AppMessage::Boolean(BooleanMessage::OverlayRuleSelected(option.0))
+}
+
+fn on_select_fill_rule(option: FillRuleOption) -> AppMessage {
+    AppMessage::Boolean(BooleanMessage::FillRuleSelected(option.0))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct OverlayRuleOption(OverlayRule);
+
+impl OverlayRuleOption {
+    const ALL: [OverlayRuleOption; 4] = [
+        OverlayRuleOption(OverlayRule::Union),
+        OverlayRuleOption(OverlayRule::Intersect),
+        OverlayRuleOption(OverlayRule::Difference),
+        OverlayRuleOption(OverlayRule::Xor),
+    ];
+}
+
+impl std::fmt::Display for OverlayRuleOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self.0 {
+                OverlayRule::Union => "Union",
+                OverlayRule::Intersect => "Intersect",
+                OverlayRule::Difference => "Difference",
+                OverlayRule::Xor => "Xor",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FillRuleOption(FillRule);
+
+impl FillRuleOption {
+    const ALL: [FillRuleOption; 4] = [
+        FillRuleOption(FillRule::EvenOdd),
+        FillRuleOption(FillRule::NonZero),
+        FillRuleOption(FillRule::Positive),
+        FillRuleOption(FillRule::Negative),
+    ];
+}
+
+impl std::fmt::Display for FillRuleOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self.0 {
+                FillRule::EvenOdd => "Even-Odd",
+                FillRule::NonZero => "Non-Zero",
+                FillRule::Positive => "Positive",
+                FillRule::Negative => "Negative",
+            }
+        )
+    }
+}
@@ -0,0 +1,3 @@
+pub(crate) mod content;
+mod workspace;
+mod control;
@@ -0,0 +1,39 @@
+use crate::app::design::{style_sheet_background, Design};
+use crate::app::intersect::control::ModeOption;
+use crate::app::main::{EditorApp, AppMessage};
+use crate::draw::path::PathWidget;
+use iced::widget::Stack;
+use iced::widget::Container;
+use iced::{Length, Padding};
+
+impl EditorApp {
+    pub(crate) fn boolean_workspace(&self) -> Container<AppMessage> {
+        let state = &self.state.boolean;
+        Container::new({
+            let mut stack = Stack::new().push(state.screen.sheet());
+            if !state.solution.is_empty() {
+                // direction arrows show how the result contours are oriented
+                let arrows = state.mode == ModeOption::Debug;
+                stack = stack.push(
+                    Container::new(PathWidget::with_paths(
+                        &state.solution,
+                        state.screen.camera,
+                        Design::accent_color(),
+                        4.0,
+                        arrows,
+                    ))
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                );
+            }
+
+            state.screen.push_editors(stack).push(
+                Container::new(self.boolean_control())
+                    .width(Length::Shrink)
+                    .height(Length::Shrink)
+                    .padding(Padding::new(8.0))
+            )
+        })
+            .style(style_sheet_background)
+    }
+}
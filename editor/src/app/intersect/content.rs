@@ -1,125 +1,98 @@
-use std::collections::HashMap;
 use i_triangle::i_overlay::i_float::int::point::IntPoint;
 use i_triangle::i_overlay::i_float::int::rect::IntRect;
-use iced::widget::scrollable;
-use iced::{Alignment, Length, Padding, Size, Vector};
-use iced::widget::{Button, Column, Container, Row, Space, Text};
+use iced::widget::Row;
+use iced::Size;
+use qurvy::convert::grid::Grid;
+use qurvy::convert::to_float::ToFloat;
 use qurvy::convert::to_int::ToInt;
+use qurvy::int::bezier::approximation::IntShort;
 use qurvy::int::bezier::path::IntBezierPath;
-use crate::app::design;
 use crate::app::intersect::control::ModeOption;
-use crate::app::intersect::workspace::WorkspaceState;
 use crate::app::main::{EditorApp, AppMessage};
-use crate::bezier_editor::widget::BezierEditorUpdateEvent;
+use crate::app::screen::ScreenState;
 use crate::compat::convert::Convert;
-use crate::data::intersect::IntersectResource;
+use crate::data::intersect::{CurveTest, IntersectResource};
 use crate::geom::camera::Camera;
 
 pub(crate) struct IntersectState {
-    pub(crate) test: usize,
+    pub(crate) screen: ScreenState,
     pub(crate) mode: ModeOption,
-    pub(crate) workspace: WorkspaceState,
-    pub(crate) size: Size,
-    pub(crate) cameras: HashMap<usize, Camera>,
+    pub(crate) shorts: Vec<IntShort>,
+    pub(crate) min_cos: u32,
+    pub(crate) min_len: u32,
+    /// The result of the last file action, shown under the buttons.
+    pub(crate) file_status: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub(crate) enum IntersectMessage {
     TestSelected(usize),
     ModeSelected(ModeOption),
-    BezierEdited(BezierEditorUpdateEvent),
-    WorkspaceSized(Size),
-    WorkspaceZoomed(Camera),
-    WorkspaceDragged(Vector<f32>),
+    Saved,
+    SavedAsNew,
+    Duplicated,
+    MinCosChanged(u32),
+    MinLenChanged(u32),
 }
 
 impl EditorApp {
-    fn intersect_sidebar(&self) -> Column<AppMessage> {
-        let count = self.app_resource.intersect.count;
-        let mut column = Column::new().push(Space::new(Length::Fill, Length::Fixed(2.0)));
-        for index in 0..count {
-            let is_selected = self.state.intersect.test == index;
-
-            column = column.push(
-                Container::new(
-                    Button::new(
-                        Text::new(format!("test_{}", index))
-                            .style(if is_selected { design::style_sidebar_text_selected } else { design::style_sidebar_text })
-                            .size(14)
-                    )
-                        .width(Length::Fill)
-                        .on_press(AppMessage::Intersect(IntersectMessage::TestSelected(index)))
-                        .style(if is_selected { design::style_sidebar_button_selected } else { design::style_sidebar_button })
-                ).padding(self.design.action_padding())
-            );
-        }
-
-        column
-    }
-
     pub(crate) fn intersect_content(&self) -> Row<AppMessage> {
-        Row::new()
-            .push(
-                scrollable(
-                    Container::new(self.intersect_sidebar())
-                        .width(Length::Fixed(160.0))
-                        .height(Length::Shrink)
-                        .align_x(Alignment::Start)
-                        .padding(Padding::new(0.0).right(8))
-                        .style(design::style_sidebar_background)
-                ).direction(scrollable::Direction::Vertical(
-                    scrollable::Scrollbar::new()
-                        .width(4)
-                        .margin(0)
-                        .scroller_width(4)
-                        .anchor(scrollable::Anchor::Start),
-                ))
-            )
-            .push(self.intersect_workspace())
+        self.screen_content(
+            self.app_resource.intersect.count,
+            self.state.intersect.screen.test,
+            |index| AppMessage::Intersect(IntersectMessage::TestSelected(index)),
+            self.intersect_workspace(),
+        )
     }
 
     pub(crate) fn intersect_update(&mut self, message: IntersectMessage) {
         match message {
             IntersectMessage::TestSelected(index) => self.intersect_set_test(index),
             IntersectMessage::ModeSelected(mode) => self.intersect_update_mode(mode),
-            IntersectMessage::BezierEdited(update) => self.intersect_update_anchor(update),
-            IntersectMessage::WorkspaceSized(size) => self.intersect_update_size(size),
-            IntersectMessage::WorkspaceZoomed(zoom) => self.intersect_update_zoom(zoom),
-            IntersectMessage::WorkspaceDragged(drag) => self.intersect_update_drag(drag),
+            IntersectMessage::Saved => self.intersect_save(),
+            IntersectMessage::SavedAsNew => self.intersect_save_as_new(),
+            IntersectMessage::Duplicated => self.intersect_duplicate(),
+            IntersectMessage::MinCosChanged(min_cos) => self.intersect_update_min_cos(min_cos),
+            IntersectMessage::MinLenChanged(min_len) => self.intersect_update_min_len(min_len),
         }
     }
 
-    fn intersect_set_test(&mut self, index: usize) {
+    pub(crate) fn intersect_set_test(&mut self, index: usize) {
         self.state.intersect.load_test(index, &mut self.app_resource.intersect);
         self.state.intersect.update_solution();
+        self.state.intersect.file_status = None;
     }
 
-    pub(crate) fn intersect_init(&mut self) {
-        self.intersect_set_test(self.state.intersect.test);
+    /// Writes the edited curves over the current test file.
+    fn intersect_save(&mut self) {
+        let index = self.state.intersect.screen.test;
+        let test = self.state.intersect.curve_test();
+        let status = if self.app_resource.intersect.save(index, test) {
+            format!("Saved test_{}", index)
+        } else {
+            format!("Could not save test_{}", index)
+        };
+        self.state.intersect.file_status = Some(status);
     }
 
-    pub(crate) fn intersect_next_test(&mut self) {
-        let next_test = self.state.intersect.test + 1;
-        if next_test < self.app_resource.intersect.count {
-            self.intersect_set_test(next_test);
-        }
+    /// Writes the edited curves as a new test and switches to it.
+    fn intersect_save_as_new(&mut self) {
+        let test = self.state.intersect.curve_test();
+        self.intersect_append(test);
     }
 
-    pub(crate) fn intersect_prev_test(&mut self) {
-        let test = self.state.intersect.test;
-        if test >= 1 {
-            self.intersect_set_test(test - 1);
-        }
+    /// Copies the current test as it is on disk to a new test and switches to it.
+    fn intersect_duplicate(&mut self) {
+        let test = if let Some(test) = self.app_resource.intersect.load(self.state.intersect.screen.test) { test } else { return; };
+        self.intersect_append(test);
     }
 
-    fn intersect_update_size(&mut self, size: Size) {
-        self.state.intersect.size = size;
-        let curves = &self.state.intersect.workspace.curves;
-        if self.state.intersect.workspace.camera.is_empty() && !curves.is_empty() {
-            let camera = Camera::with_size_and_curves(size, curves);
-            self.state.intersect.workspace.camera = camera;
+    fn intersect_append(&mut self, test: CurveTest) {
+        if let Some(index) = self.app_resource.intersect.append(test) {
+            self.intersect_set_test(index);
+            self.state.intersect.file_status = Some(format!("Saved test_{}", index));
         } else {
-            self.state.intersect.workspace.camera.size = size;
+            self.state.intersect.file_status = Some("Could not save a new test".to_string());
         }
     }
 
@@ -127,16 +100,27 @@ impl EditorApp {
         self.state.intersect.mode = mode;
         self.state.intersect.update_solution();
     }
+
+    fn intersect_update_min_cos(&mut self, min_cos: u32) {
+        self.state.intersect.min_cos = min_cos;
+        self.state.intersect.update_solution();
+    }
+
+    fn intersect_update_min_len(&mut self, min_len: u32) {
+        self.state.intersect.min_len = min_len;
+        self.state.intersect.update_solution();
+    }
 }
 
 impl IntersectState {
     pub(crate) fn new(resource: &mut IntersectResource) -> Self {
         let mut state = IntersectState {
-            test: usize::MAX,
+            screen: ScreenState::new(resource.count),
             mode: ModeOption::Edit,
-            workspace: Default::default(),
-            cameras: HashMap::with_capacity(resource.count),
-            size: Size::ZERO,
+            shorts: Vec::new(),
+            min_cos: 900,
+            min_len: 256,
+            file_status: None,
         };
 
         state.load_test(0, resource);
@@ -146,40 +130,32 @@ impl IntersectState {
 
     fn load_test(&mut self, index: usize, resource: &mut IntersectResource) {
         if let Some(test) = resource.load(index) {
-            self.workspace.curves.clear();
-            self.workspace.curves = test.curves.iter().map(|c|c.to_int(2.0)).collect();
-
-            self.cameras.insert(self.test, self.workspace.camera);
-            let mut camera = *self.cameras.get(&index).unwrap_or(&Camera::empty());
-            if camera.is_empty() && self.size.width > 0.001 {
-                camera = Camera::with_size_and_curves(self.size, &self.workspace.curves);
-            }
-
-            self.workspace.camera = camera;
-
-            self.test = index;
+            let grid = Grid::debug();
+            self.screen.load_test(index, test.curves.iter().map(|c| c.to_int(&grid)).collect());
         }
     }
 
-    fn update_solution(&mut self) {
-        // let subj = &self.workspace.subj;
-        // let clip = &self.workspace.clip;
+    /// The edited curves in the float coordinates of the test files.
+    fn curve_test(&self) -> CurveTest {
+        let grid = Grid::debug();
+        CurveTest { curves: self.screen.curves.iter().map(|c| c.to_float(&grid)).collect() }
+    }
+
+    pub(crate) fn update_solution(&mut self) {
         match self.mode {
-            ModeOption::Edit => {}
+            ModeOption::Edit => self.shorts.clear(),
             ModeOption::Debug => {
-                // self.workspace.vectors = Overlay::with_contours(subj, clip).into_separate_vectors(FillRule::NonZero, Default::default());
+                self.shorts = self.screen.curves.iter()
+                    // a curve dragged into a zero handle has no tangent, it is skipped until it is fixed
+                    .flat_map(|curve| curve.try_approximate(self.min_cos, self.min_len).unwrap_or_default())
+                    .collect();
             }
         }
     }
-
-    pub(super) fn intersect_update_point(&mut self, update: BezierEditorUpdateEvent) {
-        self.workspace.curves[update.curve_index].anchors[update.anchor_index] = update.anchor;
-        self.update_solution();
-    }
 }
 
 impl Camera {
-    fn with_size_and_curves(size: Size, curves: &Vec<IntBezierPath>) -> Self {
+    pub(crate) fn with_size_and_curves(size: Size, curves: &[IntBezierPath]) -> Self {
         let rect = if curves.is_empty() {
             IntRect::new(-10_000, 10_000, -10_000, 10_000)
         } else {
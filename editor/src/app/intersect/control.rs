@@ -1,7 +1,7 @@
 use crate::app::intersect::content::IntersectMessage;
 use crate::app::main::{EditorApp, AppMessage};
 use iced::{Alignment, Length};
-use iced::widget::{Column, Container, pick_list, Row, Text};
+use iced::widget::{Button, Column, Container, pick_list, Row, slider, Text};
 
 impl EditorApp {
     pub(crate) fn intersect_control(&self) -> Column<AppMessage> {
@@ -23,8 +23,54 @@ impl EditorApp {
                         .align_y(Alignment::Center)
                 ).height(Length::Fixed(40.0));
 
-        Column::new()
+        let file_actions = Row::new()
+            .push(Button::new(Text::new("Save")).on_press(AppMessage::Intersect(IntersectMessage::Saved)))
+            .push(Button::new(Text::new("Save as new test")).on_press(AppMessage::Intersect(IntersectMessage::SavedAsNew)))
+            .push(Button::new(Text::new("Duplicate test")).on_press(AppMessage::Intersect(IntersectMessage::Duplicated)))
+            .spacing(8)
+            .height(Length::Fixed(40.0))
+            .align_y(Alignment::Center);
+
+        let mut column = Column::new()
             .push(mode_pick_list)
+            .push(file_actions);
+
+        if let Some(status) = &self.state.intersect.file_status {
+            column = column.push(Text::new(status.as_str()).size(14));
+        }
+
+        if self.state.intersect.mode == ModeOption::Debug {
+            let min_cos = self.state.intersect.min_cos;
+            let min_len = self.state.intersect.min_len;
+            column = column
+                .push(Self::intersect_slider_row(
+                    "Min cos:",
+                    Row::new()
+                        .push(slider(512..=1023, min_cos, on_change_min_cos).width(Length::Fixed(120.0)))
+                        .push(Text::new(format!("{}", min_cos)).width(Length::Fixed(40.0)).align_x(Alignment::End)),
+                ))
+                .push(Self::intersect_slider_row(
+                    "Min len:",
+                    Row::new()
+                        .push(slider(1..=16, min_len.ilog2(), on_change_min_len_power).width(Length::Fixed(120.0)))
+                        .push(Text::new(format!("{}", min_len)).width(Length::Fixed(40.0)).align_x(Alignment::End)),
+                ));
+        }
+
+        column
+    }
+
+    fn intersect_slider_row<'a>(title: &'a str, control: Row<'a, AppMessage>) -> Row<'a, AppMessage> {
+        Row::new()
+            .push(Text::new(title)
+                .width(Length::Fixed(90.0))
+                .height(Length::Fill)
+                .align_y(Alignment::Center))
+            .push(
+                Container::new(control)
+                    .height(Length::Fill)
+                    .align_y(Alignment::Center)
+            ).height(Length::Fixed(40.0))
     }
 }
 
@@ -32,6 +78,15 @@ fn on_select_mode(option: ModeOption) -> AppMessage {
     AppMessage::Intersect(IntersectMessage::ModeSelected(option))
 }
 
+fn on_change_min_cos(min_cos: u32) -> AppMessage {
+    AppMessage::Intersect(IntersectMessage::MinCosChanged(min_cos))
+}
+
+/// `min_len` is a power of two, the slider picks the exponent.
+fn on_change_min_len_power(power: u32) -> AppMessage {
+    AppMessage::Intersect(IntersectMessage::MinLenChanged(1 << power))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub(crate) enum ModeOption {
     #[default]
@@ -40,7 +95,7 @@ pub(crate) enum ModeOption {
 }
 
 impl ModeOption {
-    const ALL: [ModeOption; 2] = [
+    pub(crate) const ALL: [ModeOption; 2] = [
         ModeOption::Edit,
         ModeOption::Debug,
     ];
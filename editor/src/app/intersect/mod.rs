@@ -1,3 +1,3 @@
 pub(crate) mod content;
 mod workspace;
-mod control;
\ No newline at end of file
+pub(crate) mod control;
\ No newline at end of file
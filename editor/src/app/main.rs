@@ -1,13 +1,20 @@
-use iced::Subscription;
+use iced::{window, Subscription};
+use iced::time::Instant;
 use iced::keyboard::Key::Named as NamedBox;
+use iced::keyboard::Key::Character;
 use iced::event::{self, Event as MainEvent};
 use iced::widget::{Space, vertical_rule};
 use iced::{Alignment, Element, Length};
 use iced::keyboard::Event as KeyboardEvent;
 use iced::keyboard::key::Named;
 use iced::widget::{Button, Column, Container, Row, Text};
+use crate::app::boolean::content::{BooleanMessage, BooleanState};
 use crate::app::intersect::content::IntersectMessage;
 use crate::app::intersect::content::IntersectState;
+use crate::app::outline::content::{OutlineMessage, OutlineState};
+use crate::app::screen::{ScreenMessage, ScreenState};
+use crate::app::string::content::{StringMessage, StringState};
+use crate::app::stroke::content::{StrokeMessage, StrokeState};
 
 
 use crate::app::design::style_separator;
@@ -24,17 +31,29 @@ pub struct EditorApp {
 pub(super) struct MainState {
     selected_action: MainAction,
     pub(super) intersect: IntersectState,
+    pub(super) boolean: BooleanState,
+    pub(super) string: StringState,
+    pub(super) stroke: StrokeState,
+    pub(super) outline: OutlineState,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum MainAction {
-    Intersect
+    Intersect,
+    Boolean,
+    String,
+    Stroke,
+    Outline,
 }
 
 impl MainAction {
     fn title(&self) -> &str {
         match self {
             MainAction::Intersect => "Intersection",
+            MainAction::Boolean => "Boolean",
+            MainAction::String => "String",
+            MainAction::Stroke => "Stroke",
+            MainAction::Outline => "Outline",
         }
     }
 }
@@ -48,17 +67,66 @@ pub(crate) enum MainMessage {
 pub(crate) enum AppMessage {
     Main(MainMessage),
     Intersect(IntersectMessage),
+    Boolean(BooleanMessage),
+    String(StringMessage),
+    Stroke(StrokeMessage),
+    Outline(OutlineMessage),
+    Screen(ScreenMessage),
     EventOccurred(MainEvent),
+    AnimationFrame(Instant),
+}
+
+impl MainState {
+    /// The shared state of the selected screen.
+    fn screen(&self) -> &ScreenState {
+        match self.selected_action {
+            MainAction::Intersect => &self.intersect.screen,
+            MainAction::Boolean => &self.boolean.screen,
+            MainAction::String => &self.string.screen,
+            MainAction::Stroke => &self.stroke.screen,
+            MainAction::Outline => &self.outline.screen,
+        }
+    }
+
+    fn screen_mut(&mut self) -> &mut ScreenState {
+        match self.selected_action {
+            MainAction::Intersect => &mut self.intersect.screen,
+            MainAction::Boolean => &mut self.boolean.screen,
+            MainAction::String => &mut self.string.screen,
+            MainAction::Stroke => &mut self.stroke.screen,
+            MainAction::Outline => &mut self.outline.screen,
+        }
+    }
+
+    /// Updates the result of the selected screen after its curves changed.
+    fn update_solution(&mut self) {
+        match self.selected_action {
+            MainAction::Intersect => self.intersect.update_solution(),
+            MainAction::Boolean => self.boolean.update_solution(),
+            MainAction::Stroke => self.stroke.update_outline(),
+            MainAction::String | MainAction::Outline => {}
+        }
+    }
 }
 
 impl EditorApp {
 
     pub fn new(mut app_resource: AppResource) -> Self {
         Self {
-            main_actions: vec![MainAction::Intersect],
+            main_actions: vec![
+                MainAction::Intersect,
+                MainAction::Boolean,
+                MainAction::String,
+                MainAction::Stroke,
+                MainAction::Outline,
+            ],
             state: MainState {
                 selected_action: MainAction::Intersect,
                 intersect: IntersectState::new(&mut app_resource.intersect),
+                boolean: BooleanState::new(&mut app_resource.boolean),
+                string: StringState::new(&mut app_resource.string),
+                stroke: StrokeState::new(&mut app_resource.stroke),
+                outline: OutlineState::new(&mut app_resource.outline),
             },
             app_resource,
             design: Design::new(),
@@ -69,28 +137,149 @@ impl EditorApp {
         match message {
             AppMessage::Main(msg) => self.update_main(msg),
             AppMessage::Intersect(msg) => self.intersect_update(msg),
-            AppMessage::EventOccurred(MainEvent::Keyboard(KeyboardEvent::KeyPressed { key: NamedBox(named @ (Named::ArrowDown | Named::ArrowUp)), .. })) => {
-                match (named, self.state.selected_action.clone()) {
-                    (Named::ArrowDown, MainAction::Intersect) => self.intersect_next_test(),
-                    (Named::ArrowUp, MainAction::Intersect) => self.intersect_prev_test(),
-                    _ => {}
+            AppMessage::Boolean(msg) => self.boolean_update(msg),
+            AppMessage::String(msg) => self.string_update(msg),
+            AppMessage::Stroke(msg) => self.stroke_update(msg),
+            AppMessage::Outline(msg) => self.outline_update(msg),
+            AppMessage::Screen(msg) => self.screen_update(msg),
+            AppMessage::AnimationFrame(now) => self.state.screen_mut().animate(now),
+            AppMessage::EventOccurred(MainEvent::Keyboard(KeyboardEvent::KeyPressed { key: NamedBox(Named::ArrowDown), .. })) => {
+                self.next_test();
+            }
+            AppMessage::EventOccurred(MainEvent::Keyboard(KeyboardEvent::KeyPressed { key: NamedBox(Named::ArrowUp), .. })) => {
+                self.prev_test();
+            }
+            AppMessage::EventOccurred(MainEvent::Keyboard(KeyboardEvent::KeyPressed { key: Character(c), modifiers, .. })) if modifiers.command() && c.eq_ignore_ascii_case("z") => {
+                if modifiers.shift() {
+                    self.redo();
+                } else {
+                    self.undo();
+                }
+            }
+            AppMessage::EventOccurred(MainEvent::Keyboard(KeyboardEvent::KeyPressed { key: NamedBox(Named::Delete | Named::Backspace), .. })) => {
+                self.delete_selection();
+            }
+            AppMessage::EventOccurred(MainEvent::Keyboard(KeyboardEvent::KeyPressed { key: NamedBox(Named::Escape), .. })) => {
+                self.clear_selection();
+            }
+            AppMessage::EventOccurred(MainEvent::Keyboard(KeyboardEvent::KeyPressed { key: Character(c), modifiers, .. })) if !modifiers.command() && c.eq_ignore_ascii_case("c") => {
+                self.toggle_closed();
+            }
+            AppMessage::EventOccurred(MainEvent::Keyboard(KeyboardEvent::KeyPressed { key: Character(c), modifiers, .. })) if !modifiers.command() && c.eq_ignore_ascii_case("f") => {
+                if modifiers.shift() {
+                    self.frame_selection();
+                } else {
+                    self.frame_all();
                 }
             }
             _ => {}
         }
     }
 
+    fn screen_update(&mut self, message: ScreenMessage) {
+        let screen = self.state.screen_mut();
+        match message {
+            ScreenMessage::BezierEdited(update) => {
+                screen.apply(update);
+                self.state.update_solution();
+            }
+            ScreenMessage::SelectionChanged(event) => {
+                if screen.update_selection(event) {
+                    self.state.update_solution();
+                }
+            }
+            ScreenMessage::WorkspaceSized(size) => screen.update_size(size),
+            ScreenMessage::WorkspaceZoomed(camera) => screen.update_zoom(camera),
+            ScreenMessage::WorkspaceDragged(drag) => screen.update_drag(drag),
+        }
+    }
+
+    /// Loads the test of the selected screen, each screen reads its own test format.
+    fn set_test(&mut self, index: usize) {
+        match self.state.selected_action {
+            MainAction::Intersect => self.intersect_set_test(index),
+            MainAction::Boolean => self.boolean_set_test(index),
+            MainAction::String => self.string_set_test(index),
+            MainAction::Stroke => self.stroke_set_test(index),
+            MainAction::Outline => self.outline_set_test(index),
+        }
+    }
+
+    fn test_count(&self) -> usize {
+        match self.state.selected_action {
+            MainAction::Intersect => self.app_resource.intersect.count,
+            MainAction::Boolean => self.app_resource.boolean.count,
+            MainAction::String => self.app_resource.string.count,
+            MainAction::Stroke => self.app_resource.stroke.count,
+            MainAction::Outline => self.app_resource.outline.count,
+        }
+    }
+
+    fn next_test(&mut self) {
+        let next_test = self.state.screen().test + 1;
+        if next_test < self.test_count() {
+            self.set_test(next_test);
+        }
+    }
+
+    fn prev_test(&mut self) {
+        let test = self.state.screen().test;
+        if test >= 1 {
+            self.set_test(test - 1);
+        }
+    }
+
+    fn undo(&mut self) {
+        if self.state.screen_mut().undo() {
+            self.state.update_solution();
+        }
+    }
+
+    fn redo(&mut self) {
+        if self.state.screen_mut().redo() {
+            self.state.update_solution();
+        }
+    }
+
+    fn delete_selection(&mut self) {
+        if self.state.screen_mut().delete_selection() {
+            self.state.update_solution();
+        }
+    }
+
+    fn toggle_closed(&mut self) {
+        if self.state.screen_mut().toggle_closed() {
+            self.state.update_solution();
+        }
+    }
+
+    fn clear_selection(&mut self) {
+        self.state.screen_mut().clear_selection();
+    }
+
+    fn frame_all(&mut self) {
+        self.state.screen_mut().frame_all();
+    }
+
+    fn frame_selection(&mut self) {
+        self.state.screen_mut().frame_selection();
+    }
+
     pub fn subscription(&self) -> Subscription<AppMessage> {
-        event::listen().map(AppMessage::EventOccurred)
+        let events = event::listen().map(AppMessage::EventOccurred);
+        if self.state.screen().is_animating() {
+            // frames are only requested while a camera moves
+            Subscription::batch([events, window::frames().map(AppMessage::AnimationFrame)])
+        } else {
+            events
+        }
     }
 
     fn update_main(&mut self, message: MainMessage) {
         match message {
             MainMessage::ActionSelected(action) => {
                 self.state.selected_action = action;
-                match self.state.selected_action {
-                    MainAction::Intersect => self.intersect_init(),
-                }
+                self.set_test(self.state.screen().test);
             }
         }
     }
@@ -110,6 +299,34 @@ impl EditorApp {
                     )
                     .push(self.intersect_content())
             }
+            MainAction::Boolean => {
+                content
+                    .push(
+                        vertical_rule(1).style(style_separator)
+                    )
+                    .push(self.boolean_content())
+            }
+            MainAction::String => {
+                content
+                    .push(
+                        vertical_rule(1).style(style_separator)
+                    )
+                    .push(self.string_content())
+            }
+            MainAction::Stroke => {
+                content
+                    .push(
+                        vertical_rule(1).style(style_separator)
+                    )
+                    .push(self.stroke_content())
+            }
+            MainAction::Outline => {
+                content
+                    .push(
+                        vertical_rule(1).style(style_separator)
+                    )
+                    .push(self.outline_content())
+            }
         };
 
         content.height(Length::Fill).into()
@@ -1,3 +1,8 @@
 pub mod main;
 mod design;
-mod intersect;
\ No newline at end of file
+mod intersect;
+mod boolean;
+mod string;
+mod stroke;
+mod outline;
+mod screen;
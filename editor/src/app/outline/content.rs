@@ -0,0 +1,57 @@
+use iced::widget::Row;
+use qurvy::convert::grid::Grid;
+use qurvy::convert::to_int::ToInt;
+use crate::app::main::{EditorApp, AppMessage};
+use crate::app::screen::ScreenState;
+use crate::data::outline::OutlineResource;
+
+pub(crate) struct OutlineState {
+    pub(crate) screen: ScreenState,
+    pub(crate) offset: f64,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum OutlineMessage {
+    TestSelected(usize),
+}
+
+impl EditorApp {
+    pub(crate) fn outline_content(&self) -> Row<AppMessage> {
+        self.screen_content(
+            self.app_resource.outline.count,
+            self.state.outline.screen.test,
+            |index| AppMessage::Outline(OutlineMessage::TestSelected(index)),
+            self.outline_workspace(),
+        )
+    }
+
+    pub(crate) fn outline_update(&mut self, message: OutlineMessage) {
+        match message {
+            OutlineMessage::TestSelected(index) => self.outline_set_test(index),
+        }
+    }
+
+    pub(crate) fn outline_set_test(&mut self, index: usize) {
+        self.state.outline.load_test(index, &mut self.app_resource.outline);
+    }
+}
+
+impl OutlineState {
+    pub(crate) fn new(resource: &mut OutlineResource) -> Self {
+        let mut state = OutlineState {
+            screen: ScreenState::new(resource.count),
+            offset: 0.0,
+        };
+
+        state.load_test(0, resource);
+        state
+    }
+
+    fn load_test(&mut self, index: usize, resource: &mut OutlineResource) {
+        if let Some(test) = resource.load(index) {
+            let grid = Grid::debug();
+            self.offset = test.offset;
+            self.screen.load_test(index, test.curves.iter().map(|c| c.to_int(&grid)).collect());
+        }
+    }
+}
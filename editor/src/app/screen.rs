@@ -0,0 +1,331 @@
+use std::collections::HashMap;
+use iced::time::Instant;
+use iced::widget::scrollable;
+use iced::widget::{Button, Column, Container, Row, Space, Stack, Text};
+use iced::{Alignment, Length, Padding, Size, Vector};
+use qurvy::convert::grid::Grid;
+use qurvy::int::bezier::path::IntBezierPath;
+use crate::app::design::{self, Design};
+use crate::app::main::{AppMessage, EditorApp};
+use crate::bezier_editor::snap::Snapper;
+use crate::bezier_editor::widget::{BezierEditorEvent, BezierEditorWidget};
+use crate::geom::animation::CameraAnimation;
+use crate::geom::camera::Camera;
+use crate::history::edit::TestHistories;
+use crate::selection::set::SelectionSet;
+use crate::selection::widget::{SelectionEvent, SelectionWidget};
+use crate::sheet::widget::SheetWidget;
+
+/// The state every test screen shares: the curves of the current test with their selection and camera,
+/// the views and the edit histories of the visited tests and the running camera animation.
+pub(crate) struct ScreenState {
+    pub(crate) test: usize,
+    pub(crate) camera: Camera,
+    pub(crate) curves: Vec<IntBezierPath>,
+    pub(crate) selection: SelectionSet,
+    pub(crate) size: Size,
+    pub(crate) cameras: HashMap<usize, Camera>,
+    pub(crate) histories: TestHistories,
+    pub(crate) animation: Option<CameraAnimation>,
+}
+
+/// The workspace events, they always belong to the selected screen.
+#[derive(Debug, Clone)]
+pub(crate) enum ScreenMessage {
+    BezierEdited(BezierEditorEvent),
+    SelectionChanged(SelectionEvent),
+    WorkspaceSized(Size),
+    WorkspaceZoomed(Camera),
+    WorkspaceDragged(Vector<f32>),
+}
+
+impl EditorApp {
+    fn test_sidebar(&self, count: usize, test: usize, on_select: fn(usize) -> AppMessage) -> Column<AppMessage> {
+        let mut column = Column::new().push(Space::new(Length::Fill, Length::Fixed(2.0)));
+        for index in 0..count {
+            let is_selected = test == index;
+
+            column = column.push(
+                Container::new(
+                    Button::new(
+                        Text::new(format!("test_{}", index))
+                            .style(if is_selected { design::style_sidebar_text_selected } else { design::style_sidebar_text })
+                            .size(14)
+                    )
+                        .width(Length::Fill)
+                        .on_press(on_select(index))
+                        .style(if is_selected { design::style_sidebar_button_selected } else { design::style_sidebar_button })
+                ).padding(self.design.action_padding())
+            );
+        }
+
+        column
+    }
+
+    /// The list of the tests of a screen next to its workspace.
+    pub(crate) fn screen_content<'a>(
+        &'a self,
+        count: usize,
+        test: usize,
+        on_select: fn(usize) -> AppMessage,
+        workspace: Container<'a, AppMessage>,
+    ) -> Row<'a, AppMessage> {
+        Row::new()
+            .push(
+                scrollable(
+                    Container::new(self.test_sidebar(count, test, on_select))
+                        .width(Length::Fixed(160.0))
+                        .height(Length::Shrink)
+                        .align_x(Alignment::Start)
+                        .padding(Padding::new(0.0).right(8))
+                        .style(design::style_sidebar_background)
+                ).direction(scrollable::Direction::Vertical(
+                    scrollable::Scrollbar::new()
+                        .width(4)
+                        .margin(0)
+                        .scroller_width(4)
+                        .anchor(scrollable::Anchor::Start),
+                ))
+            )
+            .push(workspace)
+    }
+}
+
+impl ScreenState {
+    pub(crate) fn new(count: usize) -> Self {
+        Self {
+            test: usize::MAX,
+            camera: Camera::empty(),
+            curves: Vec::new(),
+            selection: SelectionSet::default(),
+            size: Size::ZERO,
+            cameras: HashMap::with_capacity(count),
+            histories: Default::default(),
+            animation: None,
+        }
+    }
+
+    /// Shows the curves of the test `index`, the `loaded` ones unless the test was edited before.
+    /// The view and the edited curves of the current test are remembered.
+    pub(crate) fn load_test(&mut self, index: usize, loaded: Vec<IntBezierPath>) {
+        self.histories.leave(self.test, &self.curves);
+        self.curves = self.histories.enter(index, loaded);
+
+        // a running animation is remembered at its end
+        let camera = self.animation.take().map_or(self.camera, |a| a.target());
+        self.cameras.insert(self.test, camera);
+        let mut camera = *self.cameras.get(&index).unwrap_or(&Camera::empty());
+        if camera.is_empty() && self.size.width > 0.001 {
+            camera = Camera::with_size_and_curves(self.size, &self.curves);
+        }
+
+        self.camera = camera;
+        self.selection.clear();
+
+        self.test = index;
+    }
+
+    /// Applies the editor event to the curves and records it in the history of the test.
+    pub(crate) fn apply(&mut self, update: BezierEditorEvent) {
+        self.selection.track(&update);
+        self.histories.get(self.test).apply(&mut self.curves, update);
+    }
+
+    /// Returns true if the curves changed.
+    pub(crate) fn update_selection(&mut self, event: SelectionEvent) -> bool {
+        self.selection.update(event, &mut self.curves, self.histories.get(self.test))
+    }
+
+    /// Returns true if the curves changed.
+    pub(crate) fn undo(&mut self) -> bool {
+        if !self.histories.get(self.test).undo(&mut self.curves) {
+            return false;
+        }
+        self.selection.retain_valid(&self.curves);
+        true
+    }
+
+    /// Returns true if the curves changed.
+    pub(crate) fn redo(&mut self) -> bool {
+        if !self.histories.get(self.test).redo(&mut self.curves) {
+            return false;
+        }
+        self.selection.retain_valid(&self.curves);
+        true
+    }
+
+    /// Returns true if the curves changed.
+    pub(crate) fn delete_selection(&mut self) -> bool {
+        self.selection.delete(&mut self.curves, self.histories.get(self.test))
+    }
+
+    /// Returns true if the curves changed.
+    pub(crate) fn toggle_closed(&mut self) -> bool {
+        self.selection.toggle_closed(&mut self.curves, self.histories.get(self.test))
+    }
+
+    pub(crate) fn clear_selection(&mut self) {
+        self.selection.clear();
+    }
+
+    pub(crate) fn frame_all(&mut self) {
+        if let Some(camera) = self.camera.framed_curves(&self.curves) {
+            self.animate_camera(camera);
+        }
+    }
+
+    /// Frames the selected anchors, or all curves if nothing is selected.
+    pub(crate) fn frame_selection(&mut self) {
+        if let Some((min, max)) = self.selection.bounds(&self.curves) {
+            if let Some(camera) = self.camera.framed(min, max) {
+                self.animate_camera(camera);
+            }
+        } else {
+            self.frame_all();
+        }
+    }
+
+    /// Moves the camera to the view smoothly and remembers the view for the test.
+    fn animate_camera(&mut self, camera: Camera) {
+        self.cameras.insert(self.test, camera);
+        self.animation = Some(CameraAnimation::new(self.camera, camera));
+    }
+
+    pub(crate) fn animate(&mut self, now: Instant) {
+        if let Some(animation) = self.animation {
+            let (camera, is_finished) = animation.camera_at(now);
+            self.camera = camera;
+            if is_finished {
+                self.animation = None;
+            }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_animating(&self) -> bool {
+        self.animation.is_some()
+    }
+
+    pub(crate) fn update_size(&mut self, size: Size) {
+        self.size = size;
+        self.animation = None;
+        if self.camera.is_empty() && !self.curves.is_empty() {
+            self.camera = Camera::with_size_and_curves(size, &self.curves);
+        } else {
+            self.camera.size = size;
+        }
+    }
+
+    pub(crate) fn update_zoom(&mut self, camera: Camera) {
+        self.animation = None;
+        self.camera = camera;
+    }
+
+    pub(crate) fn update_drag(&mut self, new_pos: Vector<f32>) {
+        self.animation = None;
+        self.camera.pos = new_pos;
+    }
+
+    /// The sheet at the bottom of a workspace, it reports the size, zoom, drag and marquee of the workspace.
+    pub(crate) fn sheet(&self) -> Container<AppMessage> {
+        Container::new(SheetWidget::new(
+            self.camera,
+            Design::negative_color().scale_alpha(0.5),
+            on_update_size,
+            on_update_zoom,
+            on_update_drag,
+        ).on_marquee(on_update_selection))
+            .width(Length::Fill)
+            .height(Length::Fill)
+    }
+
+    /// Pushes the selection frame and an editor for every curve over the layers of a workspace.
+    pub(crate) fn push_editors<'a>(&'a self, mut stack: Stack<'a, AppMessage>) -> Stack<'a, AppMessage> {
+        stack = stack.push(
+            Container::new(SelectionWidget::new(
+                self.selection.bounds(&self.curves),
+                self.camera,
+                Design::accent_color(),
+                on_update_selection,
+            ))
+                .width(Length::Fill)
+                .height(Length::Fill)
+        );
+
+        for (id, curve) in self.curves.iter().enumerate() {
+            stack = stack.push(
+                Container::new(BezierEditorWidget::new(
+                    id,
+                    curve,
+                    self.camera,
+                    on_update_anchor
+                ).with_selection(self.selection.curve_anchors(id), on_update_selection)
+                    .with_snapper(Snapper::new(&self.curves, Grid::debug())))
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+            );
+        }
+
+        stack
+    }
+}
+
+fn on_update_anchor(event: BezierEditorEvent) -> AppMessage {
+    AppMessage::Screen(ScreenMessage::BezierEdited(event))
+}
+
+fn on_update_selection(event: SelectionEvent) -> AppMessage {
+    AppMessage::Screen(ScreenMessage::SelectionChanged(event))
+}
+
+fn on_update_size(size: Size) -> AppMessage {
+    AppMessage::Screen(ScreenMessage::WorkspaceSized(size))
+}
+
+fn on_update_zoom(zoom: Camera) -> AppMessage {
+    AppMessage::Screen(ScreenMessage::WorkspaceZoomed(zoom))
+}
+
+fn on_update_drag(drag: Vector<f32>) -> AppMessage {
+    AppMessage::Screen(ScreenMessage::WorkspaceDragged(drag))
+}
+
+#[cfg(test)]
+mod tests {
+    use qurvy::int::bezier::anchor::{AnchorKind, IntBezierAnchor};
+    use qurvy::int::bezier::path::IntBezierPath;
+    use qurvy::int::math::point::IntPoint;
+    use crate::app::screen::ScreenState;
+
+    fn curves(x: i64) -> Vec<IntBezierPath> {
+        let corner = |x: i64, y: i64| IntBezierAnchor {
+            point: IntPoint::new(x, y),
+            handle_in: None,
+            handle_out: None,
+            kind: AnchorKind::Corner,
+        };
+        vec![IntBezierPath { anchors: vec![corner(x, 0), corner(x + 10, 0)], closed: false }]
+    }
+
+    fn first_x(screen: &ScreenState) -> i64 {
+        screen.curves[0].anchors[0].point.x
+    }
+
+    #[test]
+    fn test_00() {
+        let mut screen = ScreenState::new(2);
+        screen.load_test(0, curves(0));
+        screen.histories.get(0).edit(&mut screen.curves, None, true, |curves| curves[0].anchors[0].point.x = 5);
+
+        // the tests are loaded from disk again on every switch
+        screen.load_test(1, curves(100));
+        assert_eq!(first_x(&screen), 100);
+        screen.load_test(0, curves(0));
+        assert_eq!(first_x(&screen), 5);
+
+        assert!(screen.undo());
+        assert_eq!(first_x(&screen), 0);
+        assert!(screen.redo());
+        assert_eq!(first_x(&screen), 5);
+    }
+}
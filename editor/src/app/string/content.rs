@@ -0,0 +1,60 @@
+use iced::widget::Row;
+use qurvy::convert::grid::Grid;
+use qurvy::convert::to_int::ToInt;
+use crate::app::main::{EditorApp, AppMessage};
+use crate::app::screen::ScreenState;
+use crate::data::string::StringResource;
+
+pub(crate) struct StringState {
+    pub(crate) screen: ScreenState,
+    pub(crate) body_count: usize,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum StringMessage {
+    TestSelected(usize),
+}
+
+impl EditorApp {
+    pub(crate) fn string_content(&self) -> Row<AppMessage> {
+        self.screen_content(
+            self.app_resource.string.count,
+            self.state.string.screen.test,
+            |index| AppMessage::String(StringMessage::TestSelected(index)),
+            self.string_workspace(),
+        )
+    }
+
+    pub(crate) fn string_update(&mut self, message: StringMessage) {
+        match message {
+            StringMessage::TestSelected(index) => self.string_set_test(index),
+        }
+    }
+
+    pub(crate) fn string_set_test(&mut self, index: usize) {
+        self.state.string.load_test(index, &mut self.app_resource.string);
+    }
+}
+
+impl StringState {
+    pub(crate) fn new(resource: &mut StringResource) -> Self {
+        let mut state = StringState {
+            screen: ScreenState::new(resource.count),
+            body_count: 0,
+        };
+
+        state.load_test(0, resource);
+        state
+    }
+
+    fn load_test(&mut self, index: usize, resource: &mut StringResource) {
+        if let Some(test) = resource.load(index) {
+            let grid = Grid::debug();
+            let mut curves = Vec::with_capacity(test.body.len() + test.string.len());
+            curves.extend(test.body.iter().map(|c| c.to_int(&grid)));
+            self.body_count = test.body.len();
+            curves.extend(test.string.iter().map(|c| c.to_int(&grid)));
+            self.screen.load_test(index, curves);
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub(crate) mod content;
+mod workspace;
@@ -0,0 +1,12 @@
+use crate::app::design::style_sheet_background;
+use crate::app::main::{EditorApp, AppMessage};
+use iced::widget::Stack;
+use iced::widget::Container;
+
+impl EditorApp {
+    pub(crate) fn string_workspace(&self) -> Container<AppMessage> {
+        let screen = &self.state.string.screen;
+        Container::new(screen.push_editors(Stack::new().push(screen.sheet())))
+            .style(style_sheet_background)
+    }
+}
@@ -0,0 +1,120 @@
+use iced::widget::Row;
+use i_triangle::i_overlay::i_shape::int::path::IntPaths;
+use qurvy::convert::grid::Grid;
+use qurvy::convert::to_float::ToFloat;
+use qurvy::convert::to_int::ToInt;
+use qurvy::float::bezier::path::BezierPath;
+use qurvy::float::stroke::style::{LineCap, LineJoin, StrokeStyle};
+use qurvy::int::bezier::path::IntBezierPath;
+use qurvy::int::math::point::IntPoint;
+use crate::app::intersect::control::ModeOption;
+use crate::app::main::{EditorApp, AppMessage};
+use crate::app::screen::ScreenState;
+use crate::compat::convert::Convert;
+use crate::data::stroke::StrokeResource;
+
+// max deviation of the fitted outline in world units
+const OUTLINE_TOLERANCE: f64 = 0.01;
+
+pub(crate) struct StrokeState {
+    pub(crate) screen: ScreenState,
+    pub(crate) mode: ModeOption,
+    pub(crate) style: StrokeStyle,
+    pub(crate) outline: IntPaths,
+    pub(crate) outline_anchors: Vec<IntPoint>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum StrokeMessage {
+    TestSelected(usize),
+    ModeSelected(ModeOption),
+    WidthChanged(f64),
+    CapSelected(LineCap),
+    JoinSelected(LineJoin),
+    MiterLimitChanged(f64),
+}
+
+impl EditorApp {
+    pub(crate) fn stroke_content(&self) -> Row<AppMessage> {
+        self.screen_content(
+            self.app_resource.stroke.count,
+            self.state.stroke.screen.test,
+            |index| AppMessage::Stroke(StrokeMessage::TestSelected(index)),
+            self.stroke_workspace(),
+        )
+    }
+
+    pub(crate) fn stroke_update(&mut self, message: StrokeMessage) {
+        match message {
+            StrokeMessage::TestSelected(index) => self.stroke_set_test(index),
+            StrokeMessage::ModeSelected(mode) => self.state.stroke.mode = mode,
+            StrokeMessage::WidthChanged(width) => self.stroke_update_style(StrokeStyle { width, ..self.state.stroke.style }),
+            StrokeMessage::CapSelected(cap) => self.stroke_update_style(self.state.stroke.style.with_cap(cap)),
+            StrokeMessage::JoinSelected(join) => self.stroke_update_style(self.state.stroke.style.with_join(join)),
+            StrokeMessage::MiterLimitChanged(limit) => self.stroke_update_style(self.state.stroke.style.with_miter_limit(limit)),
+        }
+    }
+
+    pub(crate) fn stroke_set_test(&mut self, index: usize) {
+        self.state.stroke.load_test(index, &mut self.app_resource.stroke);
+        self.state.stroke.update_outline();
+    }
+
+    fn stroke_update_style(&mut self, style: StrokeStyle) {
+        self.state.stroke.style = style;
+        self.state.stroke.update_outline();
+    }
+}
+
+impl StrokeState {
+    pub(crate) fn new(resource: &mut StrokeResource) -> Self {
+        let mut state = StrokeState {
+            screen: ScreenState::new(resource.count),
+            mode: ModeOption::Edit,
+            style: StrokeStyle::default(),
+            outline: Vec::new(),
+            outline_anchors: Vec::new(),
+        };
+
+        state.load_test(0, resource);
+        state.update_outline();
+        state
+    }
+
+    fn load_test(&mut self, index: usize, resource: &mut StrokeResource) {
+        if let Some(test) = resource.load(index) {
+            let grid = Grid::debug();
+            self.style = StrokeStyle { width: test.width, cap: test.cap, join: test.join, ..self.style };
+            self.screen.load_test(index, test.curves.iter().map(|c| c.to_int(&grid)).collect());
+        }
+    }
+
+    pub(crate) fn update_outline(&mut self) {
+        let grid = Grid::debug();
+        let outline: Vec<IntBezierPath> = self.screen.curves
+            .iter()
+            .flat_map(|curve| {
+                let path: BezierPath = curve.to_float(&grid);
+                path.stroke(&self.style, OUTLINE_TOLERANCE)
+            })
+            .map(|path| path.to_int(&grid))
+            .collect();
+
+        self.outline = outline
+            .iter()
+            .map(|curve| {
+                let mut path: Vec<_> = curve.regular_points(4).iter().map(|p| p.convert()).collect();
+                // PathWidget draws open polylines, repeat the first point to close it
+                if let Some(&first) = path.first() {
+                    path.push(first);
+                }
+                path
+            })
+            .collect::<IntPaths>();
+
+        self.outline_anchors = outline
+            .iter()
+            .flat_map(|curve| curve.anchors.iter().map(|anchor| anchor.point))
+            .collect();
+    }
+}
@@ -0,0 +1,154 @@
+use crate::app::stroke::content::StrokeMessage;
+use crate::app::intersect::control::ModeOption;
+use crate::app::main::{EditorApp, AppMessage};
+use iced::{Alignment, Element, Length};
+use iced::widget::{Column, Container, pick_list, Row, slider, Text};
+use qurvy::float::stroke::style::{LineCap, LineJoin};
+
+impl EditorApp {
+    pub(crate) fn stroke_control(&self) -> Column<AppMessage> {
+        let style = &self.state.stroke.style;
+
+        let mode_pick_list = Self::stroke_row(
+            "Mode:",
+            pick_list(
+                &ModeOption::ALL[..],
+                Some(self.state.stroke.mode),
+                on_select_mode,
+            ).width(Length::Fixed(160.0)).into(),
+        );
+
+        let width_slider = Self::stroke_row(
+            "Width:",
+            Row::new()
+                .push(slider(0.0..=10.0, style.width, on_change_width).step(0.1).width(Length::Fixed(120.0)))
+                .push(Text::new(format!("{:.1}", style.width)).width(Length::Fixed(40.0)).align_x(Alignment::End))
+                .into(),
+        );
+
+        let cap_pick_list = Self::stroke_row(
+            "Cap:",
+            pick_list(
+                &CapOption::ALL[..],
+                Some(CapOption(style.cap)),
+                on_select_cap,
+            ).width(Length::Fixed(160.0)).into(),
+        );
+
+        let join_pick_list = Self::stroke_row(
+            "Join:",
+            pick_list(
+                &JoinOption::ALL[..],
+                Some(JoinOption(style.join)),
+                on_select_join,
+            ).width(Length::Fixed(160.0)).into(),
+        );
+
+        let miter_slider = Self::stroke_row(
+            "Miter Limit:",
+            Row::new()
+                .push(slider(1.0..=20.0, style.miter_limit, on_change_miter_limit).step(0.5).width(Length::Fixed(120.0)))
+                .push(Text::new(format!("{:.1}", style.miter_limit)).width(Length::Fixed(40.0)).align_x(Alignment::End))
+                .into(),
+        );
+
+        let mut column = Column::new()
+            .push(mode_pick_list)
+            .push(width_slider)
+            .push(cap_pick_list)
+            .push(join_pick_list);
+
+        if style.join == LineJoin::Miter {
+            column = column.push(miter_slider);
+        }
+
+        column.push(
+            Text::new(format!("Outline anchors: {}", self.state.stroke.anchors_count()))
+                .height(Length::Fixed(40.0))
+                .align_y(Alignment::Center)
+        )
+    }
+
+    fn stroke_row<'a>(title: &'a str, control: Element<'a, AppMessage>) -> Row<'a, AppMessage> {
+        Row::new()
+            .push(Text::new(title)
+                .width(Length::Fixed(90.0))
+                .height(Length::Fill)
+                .align_y(Alignment::Center))
+            .push(
+                Container::new(control)
+                    .height(Length::Fill)
+                    .align_y(Alignment::Center)
+            ).height(Length::Fixed(40.0))
+    }
+}
+
+fn on_select_mode(option: ModeOption) -> AppMessage {
+    AppMessage::Stroke(StrokeMessage::ModeSelected(option))
+}
+
+fn on_change_width(width: f64) -> AppMessage {
+    AppMessage::Stroke(StrokeMessage::WidthChanged(width))
+}
+
+fn on_select_cap(option: CapOption) -> AppMessage {
+    AppMessage::Stroke(StrokeMessage::CapSelected(option.0))
+}
+
+fn on_select_join(option: JoinOption) -> AppMessage {
+    AppMessage::Stroke(StrokeMessage::JoinSelected(option.0))
+}
+
+fn on_change_miter_limit(limit: f64) -> AppMessage {
+    AppMessage::Stroke(StrokeMessage::MiterLimitChanged(limit))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CapOption(LineCap);
+
+impl CapOption {
+    const ALL: [CapOption; 3] = [
+        CapOption(LineCap::Butt),
+        CapOption(LineCap::Round),
+        CapOption(LineCap::Square),
+    ];
+}
+
+impl std::fmt::Display for CapOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self.0 {
+                LineCap::Butt => "Butt",
+                LineCap::Round => "Round",
+                LineCap::Square => "Square",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct JoinOption(LineJoin);
+
+impl JoinOption {
+    const ALL: [JoinOption; 3] = [
+        JoinOption(LineJoin::Miter),
+        JoinOption(LineJoin::Round),
+        JoinOption(LineJoin::Bevel),
+    ];
+}
+
+impl std::fmt::Display for JoinOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self.0 {
+                LineJoin::Miter => "Miter",
+                LineJoin::Round => "Round",
+                LineJoin::Bevel => "Bevel",
+            }
+        )
+    }
+}
@@ -0,0 +1,79 @@
+use i_triangle::i_overlay::i_shape::int::path::IntPaths;
+use crate::app::design::{style_sheet_background, Design};
+use crate::app::intersect::control::ModeOption;
+use crate::app::main::{EditorApp, AppMessage};
+use crate::app::stroke::content::StrokeState;
+use crate::compat::convert::Convert;
+use crate::draw::path::PathWidget;
+use iced::widget::Stack;
+use iced::widget::Container;
+use iced::{Length, Padding};
+use qurvy::int::math::point::IntPoint;
+
+impl EditorApp {
+    pub(crate) fn stroke_workspace(&self) -> Container<AppMessage> {
+        let state = &self.state.stroke;
+        Container::new({
+            let mut stack = Stack::new().push(state.screen.sheet());
+            if !state.outline.is_empty() {
+                let is_debug = state.mode == ModeOption::Debug;
+                stack = stack.push(
+                    Container::new(PathWidget::with_paths(
+                        &state.outline,
+                        state.screen.camera,
+                        Design::accent_color(),
+                        2.0,
+                        is_debug,
+                    ))
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                );
+
+                if is_debug {
+                    stack = stack.push(
+                        Container::new(PathWidget::with_paths(
+                            &state.anchor_markers(),
+                            state.screen.camera,
+                            Design::both_color(),
+                            2.0,
+                            false,
+                        ))
+                            .width(Length::Fill)
+                            .height(Length::Fill)
+                    );
+                }
+            }
+
+            state.screen.push_editors(stack).push(
+                Container::new(self.stroke_control())
+                    .width(Length::Shrink)
+                    .height(Length::Shrink)
+                    .padding(Padding::new(8.0))
+            )
+        })
+            .style(style_sheet_background)
+    }
+}
+
+impl StrokeState {
+    pub(crate) fn anchors_count(&self) -> usize {
+        self.outline_anchors.len()
+    }
+
+    /// Small squares around the outline anchors, sized in screen pixels.
+    fn anchor_markers(&self) -> IntPaths {
+        let r = (4.0 * self.screen.camera.i_scale).max(1.0) as i64;
+        self.outline_anchors
+            .iter()
+            .map(|p| {
+                [
+                    IntPoint::new(p.x - r, p.y - r),
+                    IntPoint::new(p.x + r, p.y - r),
+                    IntPoint::new(p.x + r, p.y + r),
+                    IntPoint::new(p.x - r, p.y + r),
+                    IntPoint::new(p.x - r, p.y - r),
+                ].iter().map(|p| p.convert()).collect()
+            })
+            .collect()
+    }
+}
@@ -1,3 +1,4 @@
 pub(crate) mod state;
 pub(crate) mod widget;
-pub(crate) mod color;
\ No newline at end of file
+pub(crate) mod color;
+pub(crate) mod snap;
\ No newline at end of file
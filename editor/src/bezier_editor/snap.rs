@@ -0,0 +1,140 @@
+use std::f64::consts::PI;
+use qurvy::convert::grid::Grid;
+use qurvy::int::bezier::path::IntBezierPath;
+use qurvy::int::math::point::IntPoint;
+use crate::geom::camera::Camera;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SnapKind {
+    Grid,
+    Anchor,
+    Curve,
+    /// The handle direction is a multiple of 15 degrees around `origin`.
+    Angle { origin: IntPoint },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SnapHit {
+    pub(crate) point: IntPoint,
+    pub(crate) kind: SnapKind,
+}
+
+/// Snap targets of a dragged point: anchors and curves of the other paths, handle angles and the grid.
+pub(crate) struct Snapper<'a> {
+    curves: &'a [IntBezierPath],
+    grid: Grid,
+    split_factor: u32,
+}
+
+impl<'a> Snapper<'a> {
+    const ANGLE_STEP: f64 = PI / 12.0;
+    /// The grid step grows in powers of two until it is at least that many pixels on screen.
+    const MIN_GRID_STEP: f32 = 8.0;
+
+    pub(crate) fn new(curves: &'a [IntBezierPath], grid: Grid) -> Self {
+        Self { curves, grid, split_factor: 4 }
+    }
+
+    /// Snaps the dragged anchor point, or with `origin` one of its handles.
+    /// `radius` is the snap distance in view pixels.
+    pub(crate) fn snap(
+        &self,
+        camera: Camera,
+        radius: f32,
+        curve_index: usize,
+        anchor_index: usize,
+        origin: Option<IntPoint>,
+        point: IntPoint,
+    ) -> SnapHit {
+        let max_dist = (radius * camera.i_scale) as f64;
+
+        let hit = if let Some(origin) = origin {
+            Self::snap_angle(origin, point, max_dist)
+        } else {
+            self.snap_anchor(curve_index, anchor_index, point, max_dist)
+                .or_else(|| self.snap_curve(curve_index, point, max_dist))
+        };
+
+        hit.unwrap_or_else(|| self.snap_grid(camera, point))
+    }
+
+    fn snap_anchor(&self, curve_index: usize, anchor_index: usize, point: IntPoint, max_dist: f64) -> Option<SnapHit> {
+        let mut min_dist = max_dist;
+        let mut result = None;
+        for (i, curve) in self.curves.iter().enumerate() {
+            for (j, anchor) in curve.anchors.iter().enumerate() {
+                if i == curve_index && j == anchor_index {
+                    continue;
+                }
+                let dist = Self::distance(anchor.point, point);
+                if dist <= min_dist {
+                    min_dist = dist;
+                    result = Some(SnapHit { point: anchor.point, kind: SnapKind::Anchor });
+                }
+            }
+        }
+        result
+    }
+
+    fn snap_curve(&self, curve_index: usize, point: IntPoint, max_dist: f64) -> Option<SnapHit> {
+        let mut min_dist = max_dist;
+        let mut result = None;
+        for (i, curve) in self.curves.iter().enumerate() {
+            if i == curve_index {
+                continue;
+            }
+            if let Some(position) = curve.project(point, self.split_factor) {
+                let dist = Self::distance(position.point, point);
+                if dist <= min_dist {
+                    min_dist = dist;
+                    result = Some(SnapHit { point: position.point, kind: SnapKind::Curve });
+                }
+            }
+        }
+        result
+    }
+
+    fn snap_angle(origin: IntPoint, point: IntPoint, max_dist: f64) -> Option<SnapHit> {
+        let dx = (point.x - origin.x) as f64;
+        let dy = (point.y - origin.y) as f64;
+        let length = (dx * dx + dy * dy).sqrt();
+        if length < max_dist {
+            return None;
+        }
+
+        let angle = (dy.atan2(dx) / Self::ANGLE_STEP).round() * Self::ANGLE_STEP;
+        let (sin, cos) = angle.sin_cos();
+        let snapped = IntPoint::new(
+            origin.x + (length * cos).round() as i64,
+            origin.y + (length * sin).round() as i64,
+        );
+
+        if Self::distance(snapped, point) <= max_dist {
+            Some(SnapHit { point: snapped, kind: SnapKind::Angle { origin } })
+        } else {
+            None
+        }
+    }
+
+    fn snap_grid(&self, camera: Camera, point: IntPoint) -> SnapHit {
+        // an empty camera has no scale, no step would be wide enough on the screen
+        if camera.scale.is_nan() || camera.scale <= 0.0 {
+            return SnapHit { point, kind: SnapKind::Grid };
+        }
+
+        let mut step = self.grid.cell_size();
+        while (step as f32) * camera.scale < Self::MIN_GRID_STEP && step < 1 << 62 {
+            step <<= 1;
+        }
+
+        let round = |a: i64| (a as f64 / step as f64).round() as i64 * step;
+        SnapHit { point: IntPoint::new(round(point.x), round(point.y)), kind: SnapKind::Grid }
+    }
+
+    #[inline]
+    fn distance(a: IntPoint, b: IntPoint) -> f64 {
+        let dx = (a.x - b.x) as f64;
+        let dy = (a.y - b.y) as f64;
+        (dx * dx + dy * dy).sqrt()
+    }
+}
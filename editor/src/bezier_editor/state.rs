@@ -1,8 +1,9 @@
 use iced::advanced::graphics::color::pack;
-use iced::{Rectangle, Transformation, Vector};
+use iced::{keyboard, Rectangle, Transformation, Vector};
 
 use crate::bezier_editor::color::BezierEditorColorSchema;
-use crate::bezier_editor::widget::{BezierEditorUpdateEvent, BezierEditorWidget};
+use crate::bezier_editor::snap::{SnapHit, Snapper};
+use crate::bezier_editor::widget::{BezierEditorEvent, BezierEditorUpdateEvent, BezierEditorWidget};
 use crate::geom::camera::Camera;
 use crate::geom::vector::VectorExt;
 use iced::advanced::graphics::Mesh;
@@ -40,6 +41,7 @@ pub(super) struct DragData {
     start_cursor: Vector<f32>,
     start_world: IntPoint,
     part: AnchorPart,
+    is_moved: bool,
 }
 
 #[derive(Clone, Copy)]
@@ -51,6 +53,9 @@ pub(super) enum SelectState {
 pub(crate) struct BezierEditorState {
     pub(super) mesh_cache: Option<MeshCache>,
     pub(super) active_anchor: Option<ActiveAnchor>,
+    pub(super) modifiers: keyboard::Modifiers,
+    /// The snap target of the running drag.
+    pub(super) snap: Option<SnapHit>,
 }
 
 impl BezierEditorState {
@@ -178,7 +183,8 @@ impl BezierEditorState {
         let drag = DragData {
             start_cursor: cursor,
             start_world: closet_point.point,
-            part: closet_point.part
+            part: closet_point.part,
+            is_moved: false,
         };
 
         self.active_anchor = Some(ActiveAnchor {
@@ -186,10 +192,46 @@ impl BezierEditorState {
             part: closet_point.part,
             select_state: SelectState::Drag(drag),
         });
-
         true
     }
 
+    /// The index of the anchor point under the cursor.
+    pub(super) fn anchor_press<M>(
+        &self,
+        widget: &BezierEditorWidget<M>,
+        cursor: Vector<f32>,
+    ) -> Option<usize> {
+        let closest_point = Self::find_closest_point(widget.camera, widget.hover_radius, widget.path, cursor)?;
+        if closest_point.part != AnchorPart::Point {
+            return None;
+        }
+        Some(closest_point.index)
+    }
+
+    /// A press on the curve away from the anchors inserts a new anchor there.
+    pub(super) fn curve_press<M>(
+        &self,
+        widget: &BezierEditorWidget<M>,
+        cursor: Vector<f32>,
+    ) -> Option<BezierEditorEvent> {
+        let world: IntPoint = widget.camera.view_to_world(cursor).round().convert();
+        let position = widget.path.nearest_position(world, widget.split_factor)?;
+        if position.t <= 0.0 || position.t >= 1.0 {
+            return None;
+        }
+
+        let view_pos = widget.camera.int_world_to_view(position.point);
+        if Self::sqr_length(&cursor, &view_pos) > widget.hover_radius.powi(2) {
+            return None;
+        }
+
+        Some(BezierEditorEvent::Inserted {
+            curve_index: widget.id,
+            spline_index: position.spline_index,
+            t: position.t,
+        })
+    }
+
     pub(super) fn mouse_release<M>(
         &mut self,
         widget: &BezierEditorWidget<M>,
@@ -203,6 +245,7 @@ impl BezierEditorState {
 
         if let SelectState::Drag(_) = &active_anchor.select_state {
             self.active_anchor = None;
+            self.snap = None;
             self.mouse_hover(widget.camera, widget.hover_radius, widget.path, cursor);
             true
         } else {
@@ -215,41 +258,52 @@ impl BezierEditorState {
         widget: &BezierEditorWidget<M>,
         cursor: Vector<f32>,
     ) -> Option<BezierEditorUpdateEvent> {
-        let active_state = &self.active_anchor?;
-        if let SelectState::Drag(drag) = &active_state.select_state {
-            Self::mouse_drag(
-                widget.id,
-                active_state.index, drag, widget.camera, widget.path, cursor)
+        // the command modifier disables snapping
+        let snapper = widget.snapper.as_ref().filter(|_| !self.modifiers.command());
+        let active_state = self.active_anchor.as_mut()?;
+        if let SelectState::Drag(drag) = &mut active_state.select_state {
+            let (update, snap) = Self::mouse_drag(widget, active_state.index, drag, snapper, cursor);
+            self.snap = snap;
+            drag.is_moved |= update.is_some();
+            update
         } else {
             self.mouse_hover(widget.camera, widget.hover_radius, widget.path, cursor);
             None
         }
     }
 
-    fn mouse_drag(
-        curve_index: usize,
+    fn mouse_drag<M>(
+        widget: &BezierEditorWidget<M>,
         anchor_index: usize,
         drag: &DragData,
-        camera: Camera,
-        path: &IntBezierPath,
+        snapper: Option<&Snapper>,
         cursor: Vector<f32>,
-    ) -> Option<BezierEditorUpdateEvent> {
+    ) -> (Option<BezierEditorUpdateEvent>, Option<SnapHit>) {
         let translate = cursor - drag.start_cursor;
-        let world_dist = camera.view_distance_to_world(translate).round();
-        let world_point = world_dist + drag.start_world.convert();
-        let mut anchor = path.anchors[anchor_index];
+        let world_dist = widget.camera.view_distance_to_world(translate).round();
+        let free_point: IntPoint = (world_dist + drag.start_world.convert()).convert();
+        let mut anchor = widget.path.anchors[anchor_index];
+
+        let snap = snapper.map(|snapper| {
+            let origin = if drag.part == AnchorPart::Point { None } else { Some(anchor.point) };
+            snapper.snap(widget.camera, widget.hover_radius, widget.id, anchor_index, origin, free_point)
+        });
+        let world_point = snap.map_or(free_point, |hit| hit.point);
+
         let real_point = drag.part.point(&anchor);
-        if world_point != real_point.convert() {
-            drag.part.update_point(world_point.convert(), &mut anchor);
+        if world_point != real_point {
+            drag.part.update_point(world_point, &mut anchor);
             // return Some(AnchorEd { index: anchor_index, anchor });
-            return Some(BezierEditorUpdateEvent {
-                curve_index,
+            let update = BezierEditorUpdateEvent {
+                curve_index: widget.id,
                 anchor_index,
                 anchor,
-            });
+                is_drag_start: !drag.is_moved,
+            };
+            return (Some(update), snap);
         }
 
-        None
+        (None, snap)
     }
 
     fn mouse_hover(&mut self, camera: Camera, radius: f32, path: &IntBezierPath, cursor: Vector<f32>) {
@@ -331,8 +385,8 @@ impl AnchorPart {
     fn update_point(&self, point: IntPoint, anchor: &mut IntBezierAnchor) {
         match self {
             AnchorPart::Point => anchor.point = point,
-            AnchorPart::HandleIn => anchor.handle_in = Some((point - anchor.point).into()),
-            AnchorPart::HandleOut => anchor.handle_out = Some((point - anchor.point).into()),
+            AnchorPart::HandleIn => anchor.set_handle_in((point - anchor.point).into()),
+            AnchorPart::HandleOut => anchor.set_handle_out((point - anchor.point).into()),
         }
     }
 }
@@ -347,7 +401,9 @@ impl Default for BezierEditorState {
     fn default() -> Self {
         Self {
             mesh_cache: None,
-            active_anchor: Default::default()
+            active_anchor: Default::default(),
+            modifiers: Default::default(),
+            snap: None,
         }
     }
 }
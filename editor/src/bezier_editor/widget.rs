@@ -1,5 +1,5 @@
 use i_triangle::i_overlay::i_float::float::point::FloatPoint;
-use iced::{event, mouse, Color, Element, Event, Length, Point, Rectangle, Renderer, Size, Theme, Vector};
+use iced::{event, keyboard, mouse, Color, Element, Event, Length, Point, Rectangle, Renderer, Size, Theme, Vector};
 use iced::advanced::{layout, renderer, Clipboard, Layout, Shell, Widget};
 use iced::advanced::graphics::Mesh;
 use iced::advanced::widget::{tree, Tree};
@@ -7,10 +7,13 @@ use iced::advanced::widget::tree::State;
 use qurvy::int::bezier::anchor::IntBezierAnchor;
 use qurvy::int::bezier::path::IntBezierPath;
 use crate::bezier_editor::color::BezierEditorColorSchema;
+use crate::bezier_editor::snap::{SnapHit, SnapKind, Snapper};
 use crate::bezier_editor::state::{ActiveAnchor, AnchorPart, BezierEditorState, MeshCache, SelectState};
 use crate::compat::convert::Convert;
 use crate::geom::camera::Camera;
 use crate::mesh::path_builder::PathBuilder;
+use crate::selection::set::AnchorRef;
+use crate::selection::widget::SelectionEvent;
 
 pub(crate) struct BezierEditorWidget<'a, Message> {
     pub(super) id: usize,
@@ -19,8 +22,11 @@ pub(crate) struct BezierEditorWidget<'a, Message> {
     pub(super) schema: BezierEditorColorSchema,
     pub(super) mesh_radius: f32,
     pub(super) hover_radius: f32,
-    pub(super) split_factor: usize,
-    on_update: Box<dyn Fn(BezierEditorUpdateEvent) -> Message + 'a>,
+    pub(super) split_factor: u32,
+    selected: Vec<usize>,
+    pub(super) snapper: Option<Snapper<'a>>,
+    on_update: Box<dyn Fn(BezierEditorEvent) -> Message + 'a>,
+    on_select: Option<Box<dyn Fn(SelectionEvent) -> Message + 'a>>,
 }
 
 #[derive(Debug, Clone)]
@@ -28,10 +34,44 @@ pub(crate) struct BezierEditorUpdateEvent {
     pub(crate) curve_index: usize,
     pub(crate) anchor_index: usize,
     pub(crate) anchor: IntBezierAnchor,
+    /// The first update of a drag gesture, the following ones continue it.
+    pub(crate) is_drag_start: bool,
+}
+
+/// A change requested by the editor. The app applies it to the curve, so it can be recorded.
+#[derive(Debug, Clone)]
+pub(crate) enum BezierEditorEvent {
+    /// An anchor point or one of its handles was dragged.
+    Moved(BezierEditorUpdateEvent),
+    /// A click on the curve inserts an anchor at `t` of the spline that starts at `spline_index`.
+    Inserted { curve_index: usize, spline_index: usize, t: f64 },
+    /// Alt-click on an anchor removes or adds its handles.
+    HandlesToggled { curve_index: usize, anchor_index: usize },
+}
+
+impl BezierEditorEvent {
+    pub(crate) fn curve_index(&self) -> usize {
+        match self {
+            BezierEditorEvent::Moved(update) => update.curve_index,
+            BezierEditorEvent::Inserted { curve_index, .. }
+            | BezierEditorEvent::HandlesToggled { curve_index, .. } => *curve_index,
+        }
+    }
+
+    pub(crate) fn apply(&self, path: &mut IntBezierPath) {
+        match self {
+            BezierEditorEvent::Moved(update) => path.anchors[update.anchor_index] = update.anchor,
+            BezierEditorEvent::Inserted { spline_index, t, .. } => {
+                path.insert_anchor(*spline_index, *t);
+            }
+            BezierEditorEvent::HandlesToggled { anchor_index, .. } => path.toggle_handles(*anchor_index),
+        }
+        path.update_auto_handles();
+    }
 }
 
 impl<'a, Message> BezierEditorWidget<'a, Message> {
-    pub(crate) fn new(id: usize, path: &'a IntBezierPath, camera: Camera, on_update: impl Fn(BezierEditorUpdateEvent) -> Message + 'a) -> Self {
+    pub(crate) fn new(id: usize, path: &'a IntBezierPath, camera: Camera, on_update: impl Fn(BezierEditorEvent) -> Message + 'a) -> Self {
         Self {
             id,
             path,
@@ -40,14 +80,62 @@ impl<'a, Message> BezierEditorWidget<'a, Message> {
             hover_radius: 12.0,
             split_factor: 5,
             schema: BezierEditorColorSchema::with_theme(Theme::default()),
+            selected: Vec::new(),
+            snapper: None,
             on_update: Box::new(on_update),
+            on_select: None,
         }
     }
 
+    /// Highlights the selected anchors, a click on an anchor reports it with `on_select`.
+    pub(crate) fn with_selection(mut self, selected: Vec<usize>, on_select: impl Fn(SelectionEvent) -> Message + 'a) -> Self {
+        self.selected = selected;
+        self.on_select = Some(Box::new(on_select));
+        self
+    }
+
     pub(crate) fn set_schema(mut self, schema: BezierEditorColorSchema) -> Self {
         self.schema = schema;
         self
     }
+
+    /// Dragged points and handles snap to the targets of `snapper`.
+    pub(crate) fn with_snapper(mut self, snapper: Snapper<'a>) -> Self {
+        self.snapper = Some(snapper);
+        self
+    }
+
+    fn draw_snap(&self, renderer: &mut Renderer, offset: Vector<f32>, hit: SnapHit) {
+        use iced::advanced::graphics::mesh::Renderer as _;
+        use iced::advanced::Renderer as _;
+
+        let center = self.camera.world_to_screen(offset, hit.point.convert());
+        let mut builder = PathBuilder::new(self.camera, offset.convert());
+        match hit.kind {
+            // a grid node needs no hint, the point just lands on it
+            SnapKind::Grid => return,
+            SnapKind::Anchor | SnapKind::Curve => {
+                let r = 1.5 * self.mesh_radius;
+                let ring = if hit.kind == SnapKind::Anchor {
+                    [Vector::new(-r, -r), Vector::new(r, -r), Vector::new(r, r), Vector::new(-r, r)]
+                } else {
+                    [Vector::new(0.0, -r), Vector::new(r, 0.0), Vector::new(0.0, r), Vector::new(-r, 0.0)]
+                };
+                for i in 0..4 {
+                    builder.add_segment((center + ring[i]).convert(), (center + ring[(i + 1) % 4]).convert(), 1.5);
+                }
+            }
+            SnapKind::Angle { origin } => {
+                let a = self.camera.world_to_screen(offset, origin.convert());
+                let b = a + (center - a) * 1.5;
+                builder.add_segment(a.convert(), b.convert(), 1.0);
+            }
+        }
+
+        if let Some(mesh) = builder.into_mesh(self.schema.hover) {
+            renderer.with_translation(Vector::new(0.0, 0.0), |renderer| renderer.draw_mesh(mesh));
+        }
+    }
 }
 
 impl<Message> Widget<Message, Theme, Renderer> for BezierEditorWidget<'_, Message> {
@@ -108,7 +196,7 @@ impl<Message> Widget<Message, Theme, Renderer> for BezierEditorWidget<'_, Messag
                             &*self,
                             view_cursor,
                         ) {
-                            shell.publish((self.on_update)(updated_point));
+                            shell.publish((self.on_update)(BezierEditorEvent::Moved(updated_point)));
                             return event::Status::Captured;
                         }
                     }
@@ -117,12 +205,33 @@ impl<Message> Widget<Message, Theme, Renderer> for BezierEditorWidget<'_, Messag
                     let position = cursor.position().unwrap_or(Point::ORIGIN);
                     if bounds.contains(position) {
                         let view_cursor = position - bounds.position();
+                        if state.modifiers.alt() {
+                            if let Some(anchor_index) = state.anchor_press(&*self, view_cursor) {
+                                let event = BezierEditorEvent::HandlesToggled { curve_index: self.id, anchor_index };
+                                shell.publish((self.on_update)(event));
+                                return event::Status::Captured;
+                            }
+                        }
+                        if let Some(on_select) = &self.on_select {
+                            if let Some(anchor_index) = state.anchor_press(&*self, view_cursor) {
+                                let extend = state.modifiers.shift();
+                                let anchor = AnchorRef { curve_index: self.id, anchor_index };
+                                shell.publish(on_select(SelectionEvent::Clicked { anchor, extend }));
+                                if extend {
+                                    return event::Status::Captured;
+                                }
+                            }
+                        }
                         if state.mouse_press(
                             &*self,
                             view_cursor,
                         ) {
                             return event::Status::Captured;
                         }
+                        if let Some(event) = state.curve_press(&*self, view_cursor) {
+                            shell.publish((self.on_update)(event));
+                            return event::Status::Captured;
+                        }
                     }
                 }
                 mouse::Event::ButtonReleased(mouse::Button::Left) => {
@@ -139,6 +248,10 @@ impl<Message> Widget<Message, Theme, Renderer> for BezierEditorWidget<'_, Messag
             }
         }
 
+        if let Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) = event {
+            state.modifiers = modifiers;
+        }
+
         event::Status::Ignored
     }
 
@@ -164,7 +277,7 @@ impl<Message> Widget<Message, Theme, Renderer> for BezierEditorWidget<'_, Messag
         let radius_offset = offset - Point::new(self.mesh_radius, self.mesh_radius);
 
         let mut contour_builder = PathBuilder::new(self.camera, offset_vec.convert());
-        contour_builder.add_paths(&self.path.points(self.split_factor), self.path.closed, 1.0);
+        contour_builder.add_paths(&self.path.regular_points(self.split_factor), self.path.closed, 1.0);
         if let Some(mesh) = contour_builder.into_mesh( Color::new(1.0, 0.6, 0.6, 1.0)) {
             renderer.with_translation(Vector::new(0.0, 0.0), |renderer| renderer.draw_mesh(mesh));
         }
@@ -188,7 +301,11 @@ impl<Message> Widget<Message, Theme, Renderer> for BezierEditorWidget<'_, Messag
                 renderer.with_translation(Vector::new(0.0, 0.0), |renderer| renderer.draw_mesh(mesh));
             }
             {
-                let mesh = mesh_cache.point_mesh(index, state.active_anchor);
+                let mesh = if self.selected.contains(&index) {
+                    mesh_cache.drag.clone()
+                } else {
+                    mesh_cache.point_mesh(index, state.active_anchor)
+                };
                 renderer.with_translation(main_screen, |renderer| renderer.draw_mesh(mesh));
             }
 
@@ -204,6 +321,10 @@ impl<Message> Widget<Message, Theme, Renderer> for BezierEditorWidget<'_, Messag
                 renderer.with_translation(screen, |renderer| renderer.draw_mesh(mesh));
             }
         }
+
+        if let Some(hit) = state.snap {
+            self.draw_snap(renderer, offset_vec, hit);
+        }
     }
 }
 
@@ -1,68 +1,13 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
 use qurvy::float::bezier::path::BezierPath;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use crate::data::loader;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct CurveTest {
     pub(crate) curves: Vec<BezierPath>,
 }
 
-impl CurveTest {
-    fn load(index: usize, folder: &str) -> Option<Self> {
-        let file_name = format!("test_{}.json", index);
-        let mut path_buf = PathBuf::from(folder);
-        path_buf.push(file_name);
-
-        match path_buf.canonicalize() {
-            Ok(full_path) => println!("Full path: {}", full_path.display()),
-            Err(e) => eprintln!("Error: {}", e),
-        }
-        let data = match std::fs::read_to_string(path_buf.as_path()) {
-            Ok(data) => {
-                data
-            }
-            Err(e) => {
-                eprintln!("{:?}", e);
-                return None;
-            }
-        };
-
-        let result: Result<CurveTest, _> = serde_json::from_str(&data);
-        match result {
-            Ok(test) => Some(test),
-            Err(e) => {
-                eprintln!("Failed to parse JSON: {}", e);
-                None
-            }
-        }
-    }
-
-    fn tests_count(folder: &str) -> usize {
-        let folder_path = PathBuf::from(folder);
-        match std::fs::read_dir(folder_path) {
-            Ok(entries) => {
-                entries
-                    .filter_map(|entry| {
-                        entry.ok().and_then(|e| {
-                            let path = e.path();
-                            if path.extension()?.to_str()? == "json" {
-                                Some(())
-                            } else {
-                                None
-                            }
-                        })
-                    })
-                    .count()
-            }
-            Err(e) => {
-                eprintln!("Failed to read directory: {}", e);
-                0
-            }
-        }
-    }
-}
-
 pub(crate) struct IntersectResource {
     folder: Option<String>,
     pub(crate) count: usize,
@@ -73,18 +18,13 @@ impl IntersectResource {
 
     #[cfg(not(target_arch = "wasm32"))]
     pub(crate) fn with_path(folder: &str) -> Self {
-        let count = CurveTest::tests_count(folder);
+        let count = loader::tests_count(folder);
         Self { count, folder: Some(folder.to_string()), tests: Default::default() }
     }
 
     #[cfg(target_arch = "wasm32")]
     pub(crate) fn with_content(content: String) -> Self {
-        let tests_vec: Vec<CurveTest> = serde_json::from_str(&content).unwrap_or_else(|e| {
-            eprintln!("Failed to parse JSON content: {}", e);
-            vec![]
-        });
-
-        let tests: HashMap<usize, CurveTest> = tests_vec
+        let tests: HashMap<usize, CurveTest> = loader::parse_tests(&content)
             .into_iter()
             .enumerate() // Assign indices
             .collect();
@@ -97,6 +37,12 @@ impl IntersectResource {
         }
     }
 
+    /// The web build has no intersection tests.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn empty() -> Self {
+        Self { count: 0, folder: None, tests: Default::default() }
+    }
+
     pub(crate) fn load(&mut self, index: usize) -> Option<CurveTest> {
         if self.count <= index {
             return None;
@@ -106,10 +52,32 @@ impl IntersectResource {
         }
 
         let folder = if let Some(folder) = &self.folder { folder } else { return None; };
-        let test = CurveTest::load(index, folder.as_str())?;
+        let test: CurveTest = loader::load_test(index, folder.as_str())?;
 
         self.tests.insert(index, test.clone());
 
         Some(test)
     }
+
+    /// Overwrites the test on disk. Returns false if it can not be written, e.g. in the web build.
+    pub(crate) fn save(&mut self, index: usize, test: CurveTest) -> bool {
+        let folder = if let Some(folder) = &self.folder { folder } else { return false; };
+        if index >= self.count || !loader::save_test(index, folder.as_str(), &test) {
+            return false;
+        }
+        self.tests.insert(index, test);
+        true
+    }
+
+    /// Writes the test after the last one and returns its index.
+    pub(crate) fn append(&mut self, test: CurveTest) -> Option<usize> {
+        let folder = self.folder.as_ref()?;
+        let index = self.count;
+        if !loader::save_test(index, folder.as_str(), &test) {
+            return None;
+        }
+        self.tests.insert(index, test);
+        self.count += 1;
+        Some(index)
+    }
 }
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Reads `test_{index}.json` from the folder.
+pub(crate) fn load_test<T: DeserializeOwned>(index: usize, folder: &str) -> Option<T> {
+    let file_name = format!("test_{}.json", index);
+    let mut path_buf = PathBuf::from(folder);
+    path_buf.push(file_name);
+
+    let data = match std::fs::read_to_string(path_buf.as_path()) {
+        Ok(data) => {
+            data
+        }
+        Err(e) => {
+            eprintln!("{:?}", e);
+            return None;
+        }
+    };
+
+    match serde_json::from_str(&data) {
+        Ok(test) => Some(test),
+        Err(e) => {
+            eprintln!("Failed to parse JSON: {}", e);
+            None
+        }
+    }
+}
+
+/// Writes `test_{index}.json` to the folder with the 4 space indent of the existing tests.
+pub(crate) fn save_test<T: Serialize>(index: usize, folder: &str, test: &T) -> bool {
+    let file_name = format!("test_{}.json", index);
+    let mut path_buf = PathBuf::from(folder);
+    path_buf.push(file_name);
+
+    let mut data = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut data, formatter);
+    if let Err(e) = test.serialize(&mut serializer) {
+        eprintln!("Failed to serialize JSON: {}", e);
+        return false;
+    }
+
+    match std::fs::write(path_buf.as_path(), data) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("{:?}", e);
+            false
+        }
+    }
+}
+
+/// Counts the `test_N.json` files of the folder.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn tests_count(folder: &str) -> usize {
+    let folder_path = PathBuf::from(folder);
+    match std::fs::read_dir(folder_path) {
+        Ok(entries) => {
+            entries
+                .filter_map(|entry| {
+                    entry.ok().and_then(|e| {
+                        // skip the `test_N.expected.json` results of the regression runner
+                        let name = e.file_name().into_string().ok()?;
+                        name.strip_prefix("test_")?.strip_suffix(".json")?.parse::<usize>().ok()
+                    })
+                })
+                .count()
+        }
+        Err(e) => {
+            eprintln!("Failed to read directory: {}", e);
+            0
+        }
+    }
+}
+
+/// Parses a JSON array of tests, used by the web build where all tests come in one string.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn parse_tests<T: DeserializeOwned>(content: &str) -> Vec<T> {
+    serde_json::from_str(content).unwrap_or_else(|e| {
+        eprintln!("Failed to parse JSON content: {}", e);
+        vec![]
+    })
+}
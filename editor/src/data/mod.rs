@@ -1,2 +1,7 @@
 pub mod resource;
-pub mod intersect;
\ No newline at end of file
+pub mod intersect;
+pub mod boolean;
+pub mod string;
+pub mod stroke;
+pub mod outline;
+mod loader;
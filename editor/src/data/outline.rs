@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use qurvy::float::bezier::path::BezierPath;
+use serde::Deserialize;
+use crate::data::loader;
+
+/// Closed shapes offset by `offset`, outward when positive.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct OutlineTest {
+    pub(crate) offset: f64,
+    pub(crate) curves: Vec<BezierPath>,
+}
+
+pub(crate) struct OutlineResource {
+    folder: Option<String>,
+    pub(crate) count: usize,
+    pub(crate) tests: HashMap<usize, OutlineTest>
+}
+
+impl OutlineResource {
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn with_path(folder: &str) -> Self {
+        let count = loader::tests_count(folder);
+        Self { count, folder: Some(folder.to_string()), tests: Default::default() }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn with_content(content: String) -> Self {
+        let tests: HashMap<usize, OutlineTest> = loader::parse_tests(&content)
+            .into_iter()
+            .enumerate() // Assign indices
+            .collect();
+
+        let count = tests.len();
+        Self {
+            count,
+            folder: None,
+            tests,
+        }
+    }
+
+    pub(crate) fn load(&mut self, index: usize) -> Option<OutlineTest> {
+        if self.count <= index {
+            return None;
+        }
+        if let Some(test) = self.tests.get(&index) {
+            return Some(test.clone())
+        }
+
+        let folder = if let Some(folder) = &self.folder { folder } else { return None; };
+        let test: OutlineTest = loader::load_test(index, folder.as_str())?;
+
+        self.tests.insert(index, test.clone());
+
+        Some(test)
+    }
+}
@@ -1,25 +1,38 @@
+use crate::data::boolean::BooleanResource;
 use crate::data::intersect::IntersectResource;
+use crate::data::outline::OutlineResource;
+use crate::data::string::StringResource;
+use crate::data::stroke::StrokeResource;
 
 pub struct AppResource {
     pub(crate) intersect: IntersectResource,
+    pub(crate) boolean: BooleanResource,
+    pub(crate) string: StringResource,
+    pub(crate) stroke: StrokeResource,
+    pub(crate) outline: OutlineResource,
 }
 
 impl AppResource {
     #[cfg(not(target_arch = "wasm32"))]
-    pub fn with_paths(intersect: &str) -> Self {
+    pub fn with_paths(intersect: &str, boolean: &str, string: &str, stroke: &str, outline: &str) -> Self {
         Self {
             intersect: IntersectResource::with_path(intersect),
+            boolean: BooleanResource::with_path(boolean),
+            string: StringResource::with_path(string),
+            stroke: StrokeResource::with_path(stroke),
+            outline: OutlineResource::with_path(outline),
         }
     }
 
     #[cfg(target_arch = "wasm32")]
     pub fn with_content(boolean: String, string: String, stroke: String, outline: String) -> Self {
         Self {
-            intersect: BooleanResource::with_content(boolean),
+            intersect: IntersectResource::empty(),
+            boolean: BooleanResource::with_content(boolean),
             string: StringResource::with_content(string),
             stroke: StrokeResource::with_content(stroke),
             outline: OutlineResource::with_content(outline),
         }
     }
 
-}
\ No newline at end of file
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use qurvy::float::bezier::path::BezierPath;
+use qurvy::float::stroke::style::{LineCap, LineJoin};
+use serde::Deserialize;
+use crate::data::loader;
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct StrokeTest {
+    pub(crate) width: f64,
+    #[serde(default)]
+    pub(crate) cap: LineCap,
+    #[serde(default)]
+    pub(crate) join: LineJoin,
+    pub(crate) curves: Vec<BezierPath>,
+}
+
+pub(crate) struct StrokeResource {
+    folder: Option<String>,
+    pub(crate) count: usize,
+    pub(crate) tests: HashMap<usize, StrokeTest>
+}
+
+impl StrokeResource {
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn with_path(folder: &str) -> Self {
+        let count = loader::tests_count(folder);
+        Self { count, folder: Some(folder.to_string()), tests: Default::default() }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn with_content(content: String) -> Self {
+        let tests: HashMap<usize, StrokeTest> = loader::parse_tests(&content)
+            .into_iter()
+            .enumerate() // Assign indices
+            .collect();
+
+        let count = tests.len();
+        Self {
+            count,
+            folder: None,
+            tests,
+        }
+    }
+
+    pub(crate) fn load(&mut self, index: usize) -> Option<StrokeTest> {
+        if self.count <= index {
+            return None;
+        }
+        if let Some(test) = self.tests.get(&index) {
+            return Some(test.clone())
+        }
+
+        let folder = if let Some(folder) = &self.folder { folder } else { return None; };
+        let test: StrokeTest = loader::load_test(index, folder.as_str())?;
+
+        self.tests.insert(index, test.clone());
+
+        Some(test)
+    }
+}
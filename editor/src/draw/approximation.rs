@@ -0,0 +1,159 @@
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::{Clipboard, renderer, Shell};
+use iced::advanced::widget::{Tree, Widget};
+use iced::{Event, event, mouse, Color, Vector};
+use iced::{Element, Length, Rectangle, Renderer, Size, Theme};
+use qurvy::int::bezier::approximation::IntShort;
+use crate::compat::convert::Convert;
+use crate::geom::camera::Camera;
+use crate::mesh::path_builder::PathBuilder;
+
+/// Draws the chords of `int::bezier::approximation` coloured by their `split_factor`,
+/// their direction vectors, the split points (squares) where the `min_cos` test split
+/// a chord, and crosses on the chords that `min_len` kept from splitting further.
+pub(crate) struct ApproximationWidget<'a> {
+    shorts: &'a [IntShort],
+    camera: Camera,
+    min_len: u32,
+}
+
+impl<'a> ApproximationWidget<'a> {
+    const DIR_LENGTH: f32 = 16.0;
+    const MARK_SIZE: f32 = 3.0;
+
+    pub(crate) fn with_shorts(shorts: &'a [IntShort], camera: Camera, min_len: u32) -> Self {
+        Self { shorts, camera, min_len }
+    }
+
+    fn split_color(split_factor: u32) -> Color {
+        const PALETTE: [(u8, u8, u8); 8] = [
+            (230, 25, 75),
+            (245, 130, 48),
+            (255, 225, 25),
+            (60, 180, 75),
+            (70, 240, 240),
+            (0, 130, 200),
+            (145, 30, 180),
+            (240, 50, 230),
+        ];
+        let (r, g, b) = PALETTE[split_factor as usize % PALETTE.len()];
+        Color::from_rgb8(r, g, b)
+    }
+
+    fn add_square(builder: &mut PathBuilder, center: Vector<f32>, r: f32) {
+        let square = [
+            center + Vector::new(-r, -r),
+            center + Vector::new(r, -r),
+            center + Vector::new(r, r),
+            center + Vector::new(-r, r),
+        ];
+        for i in 0..4 {
+            builder.add_segment(square[i].convert(), square[(i + 1) % 4].convert(), 1.0);
+        }
+    }
+
+    fn add_cross(builder: &mut PathBuilder, center: Vector<f32>, r: f32) {
+        builder.add_segment((center + Vector::new(-r, -r)).convert(), (center + Vector::new(r, r)).convert(), 1.0);
+        builder.add_segment((center + Vector::new(-r, r)).convert(), (center + Vector::new(r, -r)).convert(), 1.0);
+    }
+}
+
+impl<Message> Widget<Message, Theme, Renderer> for ApproximationWidget<'_> {
+    fn size(&self) -> Size<Length> {
+        Size {
+            width: Length::Fill,
+            height: Length::Fill,
+        }
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut Tree,
+        _renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(limits.max())
+    }
+
+    fn on_event(
+        &mut self,
+        _tree: &mut Tree,
+        _event: Event,
+        _layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        _shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        event::Status::Ignored
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        use iced::advanced::graphics::mesh::Renderer as _;
+        use iced::advanced::Renderer as _;
+
+        let offset = layout.position();
+        let offset_vec = Vector::new(offset.x, offset.y);
+
+        let max_split_factor = self.shorts.iter().map(|s| s.split_factor).max().unwrap_or(0);
+        let mut chord_builders: Vec<_> = (0..=max_split_factor)
+            .map(|_| PathBuilder::new(self.camera, offset_vec.convert()))
+            .collect();
+        let mut split_builders: Vec<_> = (0..=max_split_factor)
+            .map(|_| PathBuilder::new(self.camera, offset_vec.convert()))
+            .collect();
+        let mut dir_builder = PathBuilder::new(self.camera, offset_vec.convert());
+        let mut len_builder = PathBuilder::new(self.camera, offset_vec.convert());
+
+        for short in self.shorts.iter() {
+            let a = self.camera.world_to_screen(offset_vec, short.a.convert());
+            let b = self.camera.world_to_screen(offset_vec, short.b.convert());
+            chord_builders[short.split_factor as usize].add_segment(a.convert(), b.convert(), 2.0);
+
+            // the direction is normalized to 1024 and the view y axis is flipped
+            let m = (a + b) * 0.5;
+            let dir = Vector::new(short.dir.x as f32, -short.dir.y as f32) * (Self::DIR_LENGTH / 1024.0);
+            dir_builder.add_segment(m.convert(), (m + dir).convert(), 1.0);
+
+            if short.step > 0 {
+                // the split level that produced the start point of the chord
+                let level = short.split_factor - short.step.trailing_zeros();
+                Self::add_square(&mut split_builders[level as usize], a, Self::MARK_SIZE);
+            }
+
+            if short.split_factor > 0 && short.is_len_limited(self.min_len) {
+                Self::add_cross(&mut len_builder, m, Self::MARK_SIZE);
+            }
+        }
+
+        let mut meshes = Vec::new();
+        for (split_factor, builder) in chord_builders.into_iter().enumerate() {
+            meshes.extend(builder.into_mesh(Self::split_color(split_factor as u32).scale_alpha(0.8)));
+        }
+        for (split_factor, builder) in split_builders.into_iter().enumerate() {
+            meshes.extend(builder.into_mesh(Self::split_color(split_factor as u32)));
+        }
+        meshes.extend(dir_builder.into_mesh(Color::from_rgb(0.6, 0.6, 0.6)));
+        meshes.extend(len_builder.into_mesh(Color::from_rgb(0.9, 0.1, 0.1)));
+
+        for mesh in meshes {
+            renderer.with_translation(Vector::new(0.0, 0.0), |renderer| renderer.draw_mesh(mesh));
+        }
+    }
+}
+
+impl<'a, Message: 'a> From<ApproximationWidget<'a>> for Element<'a, Message> {
+    fn from(widget: ApproximationWidget<'a>) -> Self {
+        Self::new(widget)
+    }
+}
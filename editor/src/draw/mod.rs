@@ -1 +1,2 @@
-pub(crate) mod path;
\ No newline at end of file
+pub(crate) mod path;
+pub(crate) mod approximation;
\ No newline at end of file
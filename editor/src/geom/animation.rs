@@ -0,0 +1,38 @@
+use iced::time::{Duration, Instant};
+use crate::geom::camera::Camera;
+
+/// A smooth transition of the workspace camera to a new view.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CameraAnimation {
+    from: Camera,
+    to: Camera,
+    start: Instant,
+}
+
+impl CameraAnimation {
+    const DURATION: Duration = Duration::from_millis(250);
+
+    pub(crate) fn new(from: Camera, to: Camera) -> Self {
+        Self { from, to, start: Instant::now() }
+    }
+
+    /// The view at the end of the animation.
+    #[inline]
+    pub(crate) fn target(&self) -> Camera {
+        self.to
+    }
+
+    /// The view at `now` and whether the animation is finished.
+    pub(crate) fn camera_at(&self, now: Instant) -> (Camera, bool) {
+        let elapsed = now.saturating_duration_since(self.start);
+        if elapsed >= Self::DURATION || self.from.is_empty() {
+            return (self.to, true);
+        }
+
+        // ease in and out
+        let t = elapsed.as_secs_f32() / Self::DURATION.as_secs_f32();
+        let t = t * t * (3.0 - 2.0 * t);
+
+        (self.from.lerp(&self.to, t), false)
+    }
+}
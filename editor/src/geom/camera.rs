@@ -1,5 +1,6 @@
 use i_triangle::i_overlay::i_float::int::rect::IntRect;
 use iced::{Size, Vector};
+use qurvy::int::bezier::path::IntBezierPath;
 use qurvy::int::math::point::IntPoint;
 
 #[derive(Debug, Clone, Copy)]
@@ -47,6 +48,53 @@ impl Camera {
         Camera { scale, i_scale, size, pos }
     }
 
+    /// Empty space around the framed box, in view pixels.
+    const FRAME_PADDING: f32 = 32.0;
+
+    /// The view with the same size that fits the world box `(min, max)` tightly.
+    /// A flat box keeps the current scale along its zero side.
+    /// `None` until the workspace has reported its size, there is nothing to fit the box into.
+    pub(crate) fn framed(&self, min: IntPoint, max: IntPoint) -> Option<Self> {
+        if self.size.width <= 0.0 || self.size.height <= 0.0 {
+            return None;
+        }
+
+        let width = (max.x - min.x) as f32;
+        let height = (max.y - min.y) as f32;
+        let view_width = (self.size.width - 2.0 * Self::FRAME_PADDING).max(1.0);
+        let view_height = (self.size.height - 2.0 * Self::FRAME_PADDING).max(1.0);
+
+        let sw = if width > 0.0 { view_width / width } else { f32::MAX };
+        let sh = if height > 0.0 { view_height / height } else { f32::MAX };
+        let scale = sw.min(sh);
+        let scale = if scale < f32::MAX { scale } else if self.is_not_empty() { self.scale } else { 1.0 };
+
+        let x = 0.5 * (min.x + max.x) as f32;
+        let y = 0.5 * (min.y + max.y) as f32;
+
+        Some(Camera { scale, i_scale: 1.0 / scale, size: self.size, pos: Vector::new(x, y) })
+    }
+
+    /// The view that fits the tight bounds of all curves, `None` if there are no anchors or no size.
+    pub(crate) fn framed_curves(&self, curves: &[IntBezierPath]) -> Option<Self> {
+        let mut bounds = curves.iter().filter_map(|c| c.bounds());
+        let first = bounds.next()?;
+        let (min, max) = bounds.fold(first, |(min, max), (a, b)| (
+            IntPoint::new(min.x.min(a.x), min.y.min(a.y)),
+            IntPoint::new(max.x.max(b.x), max.y.max(b.y)),
+        ));
+
+        self.framed(min, max)
+    }
+
+    /// The view between `self` at `t = 0` and `other` at `t = 1`. The scale is interpolated
+    /// exponentially, so the zoom speed looks constant.
+    pub(crate) fn lerp(&self, other: &Camera, t: f32) -> Self {
+        let scale = self.scale * (other.scale / self.scale).powf(t);
+        let pos = self.pos + (other.pos - self.pos) * t;
+        Camera { scale, i_scale: 1.0 / scale, size: other.size, pos }
+    }
+
     #[inline]
     pub(crate) fn int_world_to_screen(&self, view_left_top: Vector<f32>, world: IntPoint) -> Vector<f32> {
         let x = self.scale * (world.x as f32 - self.pos.x) + view_left_top.x + 0.5 * self.size.width;
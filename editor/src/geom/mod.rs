@@ -1,2 +1,3 @@
+pub(crate) mod animation;
 pub(crate) mod camera;
 pub(crate) mod vector;
\ No newline at end of file
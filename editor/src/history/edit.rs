@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use qurvy::int::bezier::path::IntBezierPath;
+use crate::bezier_editor::widget::BezierEditorEvent;
+
+/// A continuous gesture, its updates are coalesced into a single entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Gesture {
+    /// A drag of one anchor point or one of its handles.
+    Drag { curve_index: usize, anchor_index: usize },
+    /// A move, rotation or scale of the selection with the gizmo.
+    Transform,
+}
+
+/// A change of the curves, `before` is restored on undo and `after` on redo.
+#[derive(Debug, Clone)]
+struct CurveEdit {
+    gesture: Option<Gesture>,
+    before: Vec<IntBezierPath>,
+    after: Vec<IntBezierPath>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EditHistory {
+    undo: Vec<CurveEdit>,
+    redo: Vec<CurveEdit>,
+}
+
+impl EditHistory {
+    /// Applies the editor event to the curves and records it.
+    /// The updates of one drag gesture are coalesced into a single entry.
+    pub(crate) fn apply(&mut self, curves: &mut Vec<IntBezierPath>, event: BezierEditorEvent) {
+        let (gesture, is_start) = match &event {
+            BezierEditorEvent::Moved(update) => (
+                Some(Gesture::Drag { curve_index: update.curve_index, anchor_index: update.anchor_index }),
+                update.is_drag_start,
+            ),
+            _ => (None, true),
+        };
+
+        self.edit(curves, gesture, is_start, |curves| event.apply(&mut curves[event.curve_index()]));
+    }
+
+    /// Curves as they were before the running gesture, if the last entry belongs to it.
+    pub(crate) fn gesture_base(&self, gesture: Gesture) -> Option<&[IntBezierPath]> {
+        self.undo.last()
+            .filter(|last| last.gesture == Some(gesture))
+            .map(|last| last.before.as_slice())
+    }
+
+    /// Changes the curves with `f` and records the change.
+    /// A continuation of the last entry gesture only updates its `after` state.
+    pub(crate) fn edit(
+        &mut self,
+        curves: &mut Vec<IntBezierPath>,
+        gesture: Option<Gesture>,
+        is_start: bool,
+        f: impl FnOnce(&mut Vec<IntBezierPath>),
+    ) {
+        let before = curves.clone();
+        f(curves);
+
+        self.redo.clear();
+
+        if !is_start && gesture.is_some() {
+            if let Some(last) = self.undo.last_mut().filter(|last| last.gesture == gesture) {
+                last.after = curves.clone();
+                return;
+            }
+        }
+
+        self.undo.push(CurveEdit {
+            gesture,
+            before,
+            after: curves.clone(),
+        });
+    }
+
+    /// Restores the curves changed by the last edit. Returns false if there is nothing to undo.
+    pub(crate) fn undo(&mut self, curves: &mut Vec<IntBezierPath>) -> bool {
+        let edit = if let Some(edit) = self.undo.pop() { edit } else { return false; };
+        curves.clone_from(&edit.before);
+        self.redo.push(edit);
+        true
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.undo.is_empty() && self.redo.is_empty()
+    }
+
+    /// Reapplies the last undone edit. Returns false if there is nothing to redo.
+    pub(crate) fn redo(&mut self, curves: &mut Vec<IntBezierPath>) -> bool {
+        let edit = if let Some(edit) = self.redo.pop() { edit } else { return false; };
+        curves.clone_from(&edit.after);
+        self.undo.push(edit);
+        true
+    }
+}
+
+/// Edit histories of all test cases of a screen together with their edited curves,
+/// so switching tests keeps both and an undo starts from the curves the history recorded.
+#[derive(Debug, Default)]
+pub(crate) struct TestHistories {
+    histories: HashMap<usize, EditHistory>,
+    curves: HashMap<usize, Vec<IntBezierPath>>,
+}
+
+impl TestHistories {
+    #[inline]
+    pub(crate) fn get(&mut self, test: usize) -> &mut EditHistory {
+        self.histories.entry(test).or_default()
+    }
+
+    /// Keeps the curves of the test that is switched away from, if it has edits.
+    pub(crate) fn leave(&mut self, test: usize, curves: &[IntBezierPath]) {
+        if self.histories.get(&test).is_some_and(|history| !history.is_empty()) {
+            self.curves.insert(test, curves.to_vec());
+        }
+    }
+
+    /// The curves of the test as it was left, or the `loaded` ones if it has no edits.
+    pub(crate) fn enter(&mut self, test: usize, loaded: Vec<IntBezierPath>) -> Vec<IntBezierPath> {
+        self.curves.remove(&test).unwrap_or(loaded)
+    }
+}
@@ -4,6 +4,8 @@ mod app;
 mod draw;
 mod sheet;
 mod geom;
+mod history;
 mod bezier_editor;
+mod selection;
 mod mesh;
 mod compat;
\ No newline at end of file
@@ -3,7 +3,9 @@ mod app;
 mod draw;
 mod sheet;
 mod geom;
+mod history;
 mod bezier_editor;
+mod selection;
 mod compat;
 mod mesh;
 
@@ -21,6 +23,10 @@ fn run_desktop() -> iced::Result {
 
     let app_resource = AppResource::with_paths(
         "../tests/intersect",
+        "../tests/boolean",
+        "../tests/string",
+        "../tests/stroke",
+        "../tests/outline",
     );
 
     let app_initializer = || {
@@ -6,6 +6,7 @@ use iced::{Color, Rectangle, Transformation};
 use iced::advanced::graphics::color::pack;
 use iced::advanced::graphics::Mesh;
 use iced::advanced::graphics::mesh::{Indexed, SolidVertex2D};
+use iced::Vector;
 use qurvy::int::math::point::IntPoint;
 use crate::compat::convert::Convert;
 use crate::geom::camera::Camera;
@@ -32,10 +33,11 @@ impl PathBuilder {
 
     #[inline]
     pub(crate) fn add_paths(&mut self, points: &[IntPoint], closed: bool, width: f32) {
+        let view_left_top: Vector<f32> = self.offset.convert();
         let float_points: Vec<_> = points.iter()
             .map(|p| {
-                let screen = self.camera.world_to_screen(self.offset.convert(), p.convert());
-                screen.convert()
+                let screen = self.camera.world_to_screen(view_left_top, Vector::new(p.x as f32, p.y as f32));
+                FloatPoint::new(screen.x, screen.y)
             }).collect();
 
         let stroke_builder = ButtStrokeBuilder::new(StrokeStyle::with_width(width));
@@ -0,0 +1,4 @@
+pub(crate) mod set;
+pub(crate) mod state;
+pub(crate) mod transform;
+pub(crate) mod widget;
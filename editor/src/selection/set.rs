@@ -0,0 +1,178 @@
+use std::collections::BTreeSet;
+use qurvy::int::bezier::path::IntBezierPath;
+use qurvy::int::math::point::IntPoint;
+use crate::bezier_editor::widget::BezierEditorEvent;
+use crate::history::edit::{EditHistory, Gesture};
+use crate::selection::transform::Transform;
+use crate::selection::widget::SelectionEvent;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct AnchorRef {
+    pub(crate) curve_index: usize,
+    pub(crate) anchor_index: usize,
+}
+
+/// Selected anchor points of all curves of a workspace.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SelectionSet {
+    anchors: BTreeSet<AnchorRef>,
+}
+
+impl SelectionSet {
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        self.anchors.clear();
+    }
+
+    /// Selected anchor indices of one curve.
+    pub(crate) fn curve_anchors(&self, curve_index: usize) -> Vec<usize> {
+        self.anchors.iter()
+            .filter(|a| a.curve_index == curve_index)
+            .map(|a| a.anchor_index)
+            .collect()
+    }
+
+    /// Bounding box of the selected anchor points, `None` if nothing is selected.
+    pub(crate) fn bounds(&self, curves: &[IntBezierPath]) -> Option<(IntPoint, IntPoint)> {
+        let mut points = self.anchors.iter()
+            .filter_map(|a| curves.get(a.curve_index)?.anchors.get(a.anchor_index))
+            .map(|anchor| anchor.point);
+
+        let first = points.next()?;
+        Some(points.fold((first, first), |(min, max), p| (
+            IntPoint::new(min.x.min(p.x), min.y.min(p.y)),
+            IntPoint::new(max.x.max(p.x), max.y.max(p.y)),
+        )))
+    }
+
+    /// A click on an anchor selects only it, unless it is already part of the selection.
+    /// With `extend` the anchor is added to or removed from the selection.
+    pub(crate) fn click(&mut self, anchor: AnchorRef, extend: bool) {
+        if extend {
+            if !self.anchors.remove(&anchor) {
+                self.anchors.insert(anchor);
+            }
+        } else if !self.anchors.contains(&anchor) {
+            self.anchors.clear();
+            self.anchors.insert(anchor);
+        }
+    }
+
+    /// Selects the anchor points inside the rectangle.
+    pub(crate) fn select_rect(&mut self, curves: &[IntBezierPath], min: IntPoint, max: IntPoint, extend: bool) {
+        if !extend {
+            self.anchors.clear();
+        }
+        for (curve_index, curve) in curves.iter().enumerate() {
+            for (anchor_index, anchor) in curve.anchors.iter().enumerate() {
+                let p = anchor.point;
+                if min.x <= p.x && p.x <= max.x && min.y <= p.y && p.y <= max.y {
+                    self.anchors.insert(AnchorRef { curve_index, anchor_index });
+                }
+            }
+        }
+    }
+
+    /// Drops the anchors that no longer exist, e.g. after an undo.
+    pub(crate) fn retain_valid(&mut self, curves: &[IntBezierPath]) {
+        self.anchors.retain(|a| {
+            curves.get(a.curve_index).is_some_and(|c| a.anchor_index < c.anchors.len())
+        });
+    }
+
+    /// Keeps the selection in sync with an edit of a single curve.
+    pub(crate) fn track(&mut self, event: &BezierEditorEvent) {
+        if let BezierEditorEvent::Inserted { curve_index, spline_index, .. } = *event {
+            self.anchors.clear();
+            self.anchors.insert(AnchorRef { curve_index, anchor_index: spline_index + 1 });
+        }
+    }
+
+    /// Handles a selection event. Returns true if the curves were changed.
+    pub(crate) fn update(&mut self, event: SelectionEvent, curves: &mut Vec<IntBezierPath>, history: &mut EditHistory) -> bool {
+        match event {
+            SelectionEvent::Marquee { min, max, extend } => {
+                self.select_rect(curves, min, max, extend);
+                false
+            }
+            SelectionEvent::Clicked { anchor, extend } => {
+                self.click(anchor, extend);
+                false
+            }
+            SelectionEvent::Transformed { transform, is_start } => {
+                self.transform(curves, history, &transform, is_start)
+            }
+        }
+    }
+
+    /// Applies the transform to the selection as it was at the start of the gesture,
+    /// so the rounding of the intermediate steps does not accumulate.
+    pub(crate) fn transform(&self, curves: &mut Vec<IntBezierPath>, history: &mut EditHistory, transform: &Transform, is_start: bool) -> bool {
+        if self.anchors.is_empty() {
+            return false;
+        }
+
+        let base = if is_start {
+            None
+        } else {
+            history.gesture_base(Gesture::Transform).map(|base| base.to_vec())
+        };
+
+        history.edit(curves, Some(Gesture::Transform), is_start, |curves| {
+            if let Some(base) = base {
+                *curves = base;
+            }
+            for a in self.anchors.iter() {
+                if let Some(anchor) = curves.get_mut(a.curve_index).and_then(|c| c.anchors.get_mut(a.anchor_index)) {
+                    transform.anchor(anchor);
+                }
+            }
+            for curve in curves.iter_mut() {
+                curve.update_auto_handles();
+            }
+        });
+
+        true
+    }
+
+    /// Removes the selected anchors, a curve keeps its last two. Returns true if the curves were changed.
+    pub(crate) fn delete(&mut self, curves: &mut Vec<IntBezierPath>, history: &mut EditHistory) -> bool {
+        if self.anchors.is_empty() {
+            return false;
+        }
+
+        let anchors = std::mem::take(&mut self.anchors);
+        history.edit(curves, None, true, |curves| {
+            // from the back, so the indices of the remaining anchors stay valid
+            for a in anchors.iter().rev() {
+                if let Some(curve) = curves.get_mut(a.curve_index) {
+                    curve.remove_anchor(a.anchor_index);
+                }
+            }
+            for curve in curves.iter_mut() {
+                curve.update_auto_handles();
+            }
+        });
+
+        true
+    }
+
+    /// Closes or opens every curve with a selected anchor. Returns true if the curves were changed.
+    pub(crate) fn toggle_closed(&self, curves: &mut Vec<IntBezierPath>, history: &mut EditHistory) -> bool {
+        let curve_indices: BTreeSet<usize> = self.anchors.iter().map(|a| a.curve_index).collect();
+        if curve_indices.is_empty() {
+            return false;
+        }
+
+        history.edit(curves, None, true, |curves| {
+            for &index in curve_indices.iter() {
+                if let Some(curve) = curves.get_mut(index) {
+                    curve.closed = !curve.closed;
+                    curve.update_auto_handles();
+                }
+            }
+        });
+
+        true
+    }
+}
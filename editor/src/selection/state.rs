@@ -0,0 +1,163 @@
+use std::f64::consts::PI;
+use iced::{keyboard, Vector};
+use qurvy::int::math::point::IntPoint;
+use crate::geom::camera::Camera;
+use crate::selection::transform::Transform;
+use crate::selection::widget::SelectionWidget;
+
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum GizmoPart {
+    Body,
+    /// Corners of the box in world space: `min`, (max.x, min.y), `max`, (min.x, max.y).
+    Corner(usize),
+    Rotate,
+}
+
+#[derive(Clone, Copy)]
+struct GizmoDrag {
+    part: GizmoPart,
+    start_cursor: Vector<f32>,
+    min: IntPoint,
+    max: IntPoint,
+    is_moved: bool,
+}
+
+#[derive(Default)]
+pub(crate) struct SelectionState {
+    drag: Option<GizmoDrag>,
+    pub(super) modifiers: keyboard::Modifiers,
+}
+
+/// View space box of the selection with the padding, so a single anchor still has a grip.
+pub(super) struct GizmoFrame {
+    pub(super) min: Vector<f32>,
+    pub(super) max: Vector<f32>,
+}
+
+impl GizmoFrame {
+    const PADDING: f32 = 8.0;
+    const ROTATE_OFFSET: f32 = 24.0;
+
+    pub(super) fn new(camera: Camera, min: IntPoint, max: IntPoint) -> Self {
+        let a = camera.int_world_to_view(min);
+        let b = camera.int_world_to_view(max);
+        Self {
+            min: Vector::new(a.x.min(b.x) - Self::PADDING, a.y.min(b.y) - Self::PADDING),
+            max: Vector::new(a.x.max(b.x) + Self::PADDING, a.y.max(b.y) + Self::PADDING),
+        }
+    }
+
+    /// Corners in the order of `GizmoPart::Corner`, the view y axis is flipped.
+    pub(super) fn corners(&self) -> [Vector<f32>; 4] {
+        [
+            Vector::new(self.min.x, self.max.y),
+            Vector::new(self.max.x, self.max.y),
+            Vector::new(self.max.x, self.min.y),
+            Vector::new(self.min.x, self.min.y),
+        ]
+    }
+
+    pub(super) fn top_center(&self) -> Vector<f32> {
+        Vector::new(0.5 * (self.min.x + self.max.x), self.min.y)
+    }
+
+    pub(super) fn rotate_knob(&self) -> Vector<f32> {
+        self.top_center() - Vector::new(0.0, Self::ROTATE_OFFSET)
+    }
+
+    fn contains(&self, p: Vector<f32>) -> bool {
+        self.min.x <= p.x && p.x <= self.max.x && self.min.y <= p.y && p.y <= self.max.y
+    }
+}
+
+impl SelectionState {
+    pub(super) fn mouse_press<M>(&mut self, widget: &SelectionWidget<M>, cursor: Vector<f32>) -> bool {
+        let (min, max) = if let Some(bounds) = widget.bounds { bounds } else { return false; };
+        let frame = GizmoFrame::new(widget.camera, min, max);
+        let r2 = widget.handle_radius.powi(2);
+
+        let part = if let Some(index) = frame.corners().iter().position(|&c| Self::sqr_length(c, cursor) <= r2) {
+            GizmoPart::Corner(index)
+        } else if Self::sqr_length(frame.rotate_knob(), cursor) <= r2 {
+            GizmoPart::Rotate
+        } else if frame.contains(cursor) {
+            GizmoPart::Body
+        } else {
+            return false;
+        };
+
+        self.drag = Some(GizmoDrag { part, start_cursor: cursor, min, max, is_moved: false });
+        true
+    }
+
+    pub(super) fn mouse_release(&mut self) -> bool {
+        self.drag.take().is_some()
+    }
+
+    /// The transform from the start of the drag and whether it starts the gesture.
+    pub(super) fn mouse_move(&mut self, camera: Camera, cursor: Vector<f32>) -> Option<(Transform, bool)> {
+        let uniform = self.modifiers.shift();
+        let drag = self.drag.as_mut()?;
+        if drag.start_cursor == cursor {
+            return None;
+        }
+
+        let start: Vector<f32> = camera.view_to_world(drag.start_cursor);
+        let world: Vector<f32> = camera.view_to_world(cursor);
+        let (start, world) = ((start.x as f64, start.y as f64), (world.x as f64, world.y as f64));
+
+        let transform = match drag.part {
+            GizmoPart::Body => Transform::translate((world.0 - start.0).round(), (world.1 - start.1).round()),
+            GizmoPart::Corner(index) => {
+                let corners = Self::world_corners(drag.min, drag.max);
+                let corner = corners[index];
+                let pivot = corners[(index + 2) % 4];
+                let mut sx = Self::ratio(world.0 - pivot.0, corner.0 - pivot.0);
+                let mut sy = Self::ratio(world.1 - pivot.1, corner.1 - pivot.1);
+                if uniform {
+                    let s = if sx.abs() > sy.abs() { sx } else { sy };
+                    sx = s;
+                    sy = s;
+                }
+                Transform::scale(pivot, sx, sy)
+            }
+            GizmoPart::Rotate => {
+                let center = (
+                    0.5 * (drag.min.x + drag.max.x) as f64,
+                    0.5 * (drag.min.y + drag.max.y) as f64,
+                );
+                let a0 = (start.1 - center.1).atan2(start.0 - center.0);
+                let a1 = (world.1 - center.1).atan2(world.0 - center.0);
+                let mut angle = a1 - a0;
+                if uniform {
+                    const STEP: f64 = PI / 12.0;
+                    angle = (angle / STEP).round() * STEP;
+                }
+                Transform::rotate(center, angle)
+            }
+        };
+
+        let is_start = !drag.is_moved;
+        drag.is_moved = true;
+
+        Some((transform, is_start))
+    }
+
+    fn world_corners(min: IntPoint, max: IntPoint) -> [(f64, f64); 4] {
+        let (x0, y0, x1, y1) = (min.x as f64, min.y as f64, max.x as f64, max.y as f64);
+        [(x0, y0), (x1, y0), (x1, y1), (x0, y1)]
+    }
+
+    /// A flat box does not scale along its zero side.
+    #[inline]
+    fn ratio(a: f64, b: f64) -> f64 {
+        if b.abs() < 0.5 { 1.0 } else { a / b }
+    }
+
+    #[inline]
+    fn sqr_length(a: Vector<f32>, b: Vector<f32>) -> f32 {
+        let dx = a.x - b.x;
+        let dy = a.y - b.y;
+        dx * dx + dy * dy
+    }
+}
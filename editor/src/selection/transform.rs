@@ -0,0 +1,74 @@
+use qurvy::int::bezier::anchor::IntBezierAnchor;
+use qurvy::int::math::offset::IntOffset;
+use qurvy::int::math::point::IntPoint;
+
+/// Affine transform in world coordinates: `x' = a * x + b * y + tx`, `y' = c * x + d * y + ty`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Transform {
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+    tx: f64,
+    ty: f64,
+}
+
+impl Transform {
+    pub(crate) const IDENTITY: Self = Self { a: 1.0, b: 0.0, c: 0.0, d: 1.0, tx: 0.0, ty: 0.0 };
+
+    #[inline]
+    pub(crate) fn translate(dx: f64, dy: f64) -> Self {
+        Self { tx: dx, ty: dy, ..Self::IDENTITY }
+    }
+
+    /// Scales around `pivot`, the pivot itself stays in place.
+    #[inline]
+    pub(crate) fn scale(pivot: (f64, f64), sx: f64, sy: f64) -> Self {
+        Self {
+            a: sx,
+            b: 0.0,
+            c: 0.0,
+            d: sy,
+            tx: pivot.0 * (1.0 - sx),
+            ty: pivot.1 * (1.0 - sy),
+        }
+    }
+
+    /// Rotates counter-clockwise by `angle` radians around `pivot`.
+    #[inline]
+    pub(crate) fn rotate(pivot: (f64, f64), angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self {
+            a: cos,
+            b: -sin,
+            c: sin,
+            d: cos,
+            tx: pivot.0 - cos * pivot.0 + sin * pivot.1,
+            ty: pivot.1 - sin * pivot.0 - cos * pivot.1,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn point(&self, p: IntPoint) -> IntPoint {
+        let (x, y) = (p.x as f64, p.y as f64);
+        let tx = self.a * x + self.b * y + self.tx;
+        let ty = self.c * x + self.d * y + self.ty;
+        IntPoint::new(tx.round() as i64, ty.round() as i64)
+    }
+
+    /// Handles are directions, only the linear part applies to them.
+    #[inline]
+    pub(crate) fn offset(&self, o: IntOffset) -> IntOffset {
+        let (x, y) = (o.x as f64, o.y as f64);
+        let tx = self.a * x + self.b * y;
+        let ty = self.c * x + self.d * y;
+        IntOffset::new(tx.round() as i64, ty.round() as i64)
+    }
+
+    #[inline]
+    pub(crate) fn anchor(&self, anchor: &mut IntBezierAnchor) {
+        anchor.point = self.point(anchor.point);
+        anchor.handle_in = anchor.handle_in.map(|h| self.offset(h));
+        anchor.handle_out = anchor.handle_out.map(|h| self.offset(h));
+    }
+}
@@ -0,0 +1,170 @@
+use iced::{event, keyboard, mouse, Color, Element, Event, Length, Point, Rectangle, Renderer, Size, Theme, Vector};
+use iced::advanced::{layout, renderer, Clipboard, Layout, Shell, Widget};
+use iced::advanced::widget::{tree, Tree};
+use iced::advanced::widget::tree::State;
+use qurvy::int::math::point::IntPoint;
+use crate::compat::convert::Convert;
+use crate::geom::camera::Camera;
+use crate::mesh::path_builder::PathBuilder;
+use crate::selection::set::AnchorRef;
+use crate::selection::state::{GizmoFrame, SelectionState};
+use crate::selection::transform::Transform;
+
+/// A change of the selection or of the selected anchors.
+#[derive(Debug, Clone)]
+pub(crate) enum SelectionEvent {
+    /// A marquee dragged on the sheet, in world coordinates. Shift extends the selection.
+    Marquee { min: IntPoint, max: IntPoint, extend: bool },
+    /// A click on an anchor point. Shift adds it to or removes it from the selection.
+    Clicked { anchor: AnchorRef, extend: bool },
+    /// A gizmo drag, the transform is relative to the start of the gesture.
+    Transformed { transform: Transform, is_start: bool },
+}
+
+/// Bounding box gizmo of the selection: drag the box to move it, a corner to scale it
+/// around the opposite corner and the knob above it to rotate it. Shift keeps the
+/// proportions while scaling and snaps the rotation to 15 degrees.
+pub(crate) struct SelectionWidget<'a, Message> {
+    pub(super) bounds: Option<(IntPoint, IntPoint)>,
+    pub(super) camera: Camera,
+    pub(super) color: Color,
+    pub(super) handle_radius: f32,
+    on_transform: Box<dyn Fn(SelectionEvent) -> Message + 'a>,
+}
+
+impl<'a, Message> SelectionWidget<'a, Message> {
+    pub(crate) fn new(
+        bounds: Option<(IntPoint, IntPoint)>,
+        camera: Camera,
+        color: Color,
+        on_transform: impl Fn(SelectionEvent) -> Message + 'a,
+    ) -> Self {
+        Self {
+            bounds,
+            camera,
+            color,
+            handle_radius: 8.0,
+            on_transform: Box::new(on_transform),
+        }
+    }
+}
+
+impl<Message> Widget<Message, Theme, Renderer> for SelectionWidget<'_, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<SelectionState>()
+    }
+
+    fn state(&self) -> State {
+        State::new(SelectionState::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size {
+            width: Length::Fill,
+            height: Length::Fill,
+        }
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut Tree,
+        _renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(limits.max())
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<SelectionState>();
+
+        let bounds = layout.bounds();
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                if bounds.contains(position) {
+                    let view_cursor = position - bounds.position();
+                    if let Some((transform, is_start)) = state.mouse_move(self.camera, view_cursor) {
+                        shell.publish((self.on_transform)(SelectionEvent::Transformed { transform, is_start }));
+                        return event::Status::Captured;
+                    }
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let position = cursor.position().unwrap_or(Point::ORIGIN);
+                if bounds.contains(position) && state.mouse_press(&*self, position - bounds.position()) {
+                    return event::Status::Captured;
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                if state.mouse_release() {
+                    return event::Status::Captured;
+                }
+            }
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => state.modifiers = modifiers,
+            _ => {}
+        }
+
+        event::Status::Ignored
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let (min, max) = if let Some(bounds) = self.bounds { bounds } else { return; };
+
+        use iced::advanced::graphics::mesh::Renderer as _;
+        use iced::advanced::Renderer as _;
+
+        let offset = layout.position();
+        let offset_vec = Vector::new(offset.x, offset.y);
+        let frame = GizmoFrame::new(self.camera, min, max);
+        let corners = frame.corners().map(|c| c + offset_vec);
+
+        let mut builder = PathBuilder::new(self.camera, offset_vec.convert());
+        for i in 0..4 {
+            builder.add_segment(corners[i].convert(), corners[(i + 1) % 4].convert(), 1.0);
+        }
+
+        let knob = frame.rotate_knob() + offset_vec;
+        builder.add_segment((frame.top_center() + offset_vec).convert(), knob.convert(), 1.0);
+
+        let r = 0.5 * self.handle_radius;
+        for c in corners.iter().chain(std::iter::once(&knob)) {
+            let square = [
+                *c + Vector::new(-r, -r),
+                *c + Vector::new(r, -r),
+                *c + Vector::new(r, r),
+                *c + Vector::new(-r, r),
+            ];
+            for i in 0..4 {
+                builder.add_segment(square[i].convert(), square[(i + 1) % 4].convert(), 2.0);
+            }
+        }
+
+        if let Some(mesh) = builder.into_mesh(self.color) {
+            renderer.with_translation(Vector::new(0.0, 0.0), |renderer| renderer.draw_mesh(mesh));
+        }
+    }
+}
+
+impl<'a, Message: 'a> From<SelectionWidget<'a, Message>> for Element<'a, Message> {
+    fn from(widget: SelectionWidget<'a, Message>) -> Self {
+        Self::new(widget)
+    }
+}
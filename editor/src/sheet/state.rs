@@ -1,19 +1,30 @@
-use iced::{Size, Vector};
+use iced::{keyboard, Size, Vector};
 use iced::mouse::ScrollDelta;
+use qurvy::int::math::point::IntPoint;
+use crate::compat::convert::Convert;
 use crate::geom::camera::Camera;
+use crate::geom::vector::VectorExt;
+use crate::selection::widget::SelectionEvent;
 
 struct Drag {
     start_screen: Vector<f32>,
     start_world: Vector<f32>,
 }
 
+struct Marquee {
+    start: Vector<f32>,
+    end: Vector<f32>,
+}
+
 enum DragState {
     Drag(Drag),
+    Marquee(Marquee),
     None,
 }
 
 pub(super) struct SheetState {
     drag_state: DragState,
+    pub(super) modifiers: keyboard::Modifiers,
 }
 
 impl SheetState {
@@ -21,8 +32,45 @@ impl SheetState {
         self.drag_state = DragState::Drag(Drag { start_screen: view_cursor, start_world: camera.pos });
     }
 
-    pub(super) fn mouse_release(&mut self) {
-        self.drag_state = DragState::None;
+    pub(super) fn marquee_press(&mut self, view_cursor: Vector<f32>) {
+        self.drag_state = DragState::Marquee(Marquee { start: view_cursor, end: view_cursor });
+    }
+
+    /// Ends the drag, a finished marquee is returned as a selection in world coordinates.
+    pub(super) fn mouse_release(&mut self, camera: Camera) -> Option<SelectionEvent> {
+        let drag_state = std::mem::replace(&mut self.drag_state, DragState::None);
+        if let DragState::Marquee(marquee) = drag_state {
+            let a: IntPoint = camera.view_to_world(marquee.start).round().convert();
+            let b: IntPoint = camera.view_to_world(marquee.end).round().convert();
+            Some(SelectionEvent::Marquee {
+                min: IntPoint::new(a.x.min(b.x), a.y.min(b.y)),
+                max: IntPoint::new(a.x.max(b.x), a.y.max(b.y)),
+                extend: self.modifiers.shift(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Stretches the marquee. Returns false if no marquee is dragged.
+    pub(super) fn marquee_move(&mut self, view_cursor: Vector<f32>) -> bool {
+        if let DragState::Marquee(marquee) = &mut self.drag_state {
+            marquee.end = view_cursor;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The marquee rectangle in view coordinates.
+    pub(super) fn marquee_rect(&self) -> Option<(Vector<f32>, Vector<f32>)> {
+        if let DragState::Marquee(marquee) = &self.drag_state {
+            let min = Vector::new(marquee.start.x.min(marquee.end.x), marquee.start.y.min(marquee.end.y));
+            let max = Vector::new(marquee.start.x.max(marquee.end.x), marquee.start.y.max(marquee.end.y));
+            Some((min, max))
+        } else {
+            None
+        }
     }
 
     pub(super) fn mouse_move(&mut self, camera: Camera, view_cursor: Vector<f32>) -> Option<Vector<f32>> {
@@ -65,6 +113,7 @@ impl Default for SheetState {
     fn default() -> Self {
         Self {
             drag_state: DragState::None,
+            modifiers: Default::default(),
         }
     }
 }
@@ -10,6 +10,10 @@ use iced::{Element, Length, Rectangle, Renderer, Size, Theme, Vector};
 use iced::advanced::graphics::color::pack;
 use iced::advanced::graphics::Mesh;
 use iced::advanced::graphics::mesh::{SolidVertex2D, Indexed};
+use iced::{keyboard, window};
+use crate::compat::convert::Convert;
+use crate::mesh::path_builder::PathBuilder;
+use crate::selection::widget::SelectionEvent;
 
 pub(crate) struct SheetWidget<'a, Message> {
     camera: Camera,
@@ -17,6 +21,7 @@ pub(crate) struct SheetWidget<'a, Message> {
     on_size: Box<dyn Fn(Size) -> Message + 'a>,
     on_zoom: Box<dyn Fn(Camera) -> Message + 'a>,
     on_drag: Box<dyn Fn(Vector<f32>) -> Message + 'a>,
+    on_marquee: Option<Box<dyn Fn(SelectionEvent) -> Message + 'a>>,
 }
 
 impl<'a, Message: 'a> SheetWidget<'a, Message> {
@@ -33,9 +38,16 @@ impl<'a, Message: 'a> SheetWidget<'a, Message> {
             on_size: Box::new(on_size),
             on_zoom: Box::new(on_zoom),
             on_drag: Box::new(on_drag),
+            on_marquee: None,
         }
     }
 
+    /// Left drag selects with a marquee, the camera is then dragged with the right or middle button.
+    pub(crate) fn on_marquee(mut self, on_marquee: impl Fn(SelectionEvent) -> Message + 'a) -> Self {
+        self.on_marquee = Some(Box::new(on_marquee));
+        self
+    }
+
     pub(super) fn is_size_changed(&self, size: Size) -> bool {
         let w = (size.width - self.camera.size.width).abs();
         let h = (size.height - self.camera.size.height).abs();
@@ -68,6 +80,31 @@ impl<'a, Message: 'a> SheetWidget<'a, Message> {
     }
 }
 
+impl<Message> SheetWidget<'_, Message> {
+    fn draw_marquee(&self, state: &SheetState, renderer: &mut Renderer, layout: Layout<'_>) {
+        let (min, max) = if let Some(rect) = state.marquee_rect() { rect } else { return; };
+
+        use iced::advanced::graphics::mesh::Renderer as _;
+        use iced::advanced::Renderer as _;
+
+        let offset = layout.position();
+        let offset_vec = Vector::new(offset.x, offset.y);
+        let (min, max) = (min + offset_vec, max + offset_vec);
+
+        let fill = self.line_mesh(min.x, min.y, max.x, max.y, 0.1);
+        renderer.with_translation(Vector::new(0.0, 0.0), |renderer| renderer.draw_mesh(fill));
+
+        let corners = [min, Vector::new(max.x, min.y), max, Vector::new(min.x, max.y)];
+        let mut builder = PathBuilder::new(self.camera, offset_vec.convert());
+        for i in 0..4 {
+            builder.add_segment(corners[i].convert(), corners[(i + 1) % 4].convert(), 1.0);
+        }
+        if let Some(mesh) = builder.into_mesh(self.grid_color) {
+            renderer.with_translation(Vector::new(0.0, 0.0), |renderer| renderer.draw_mesh(mesh));
+        }
+    }
+}
+
 impl<Message> Widget<Message, Theme, Renderer> for SheetWidget<'_, Message> {
     fn tag(&self) -> tree::Tag {
         tree::Tag::of::<SheetState>()
@@ -118,22 +155,34 @@ impl<Message> Widget<Message, Theme, Renderer> for SheetWidget<'_, Message> {
                 mouse::Event::CursorMoved { position } => {
                     if bounds.contains(position) {
                         let view_cursor = position - bounds.position();
+                        if state.marquee_move(view_cursor) {
+                            shell.request_redraw(window::RedrawRequest::NextFrame);
+                            return event::Status::Captured;
+                        }
                         if let Some(drag) = state.mouse_move(self.camera, view_cursor) {
                             shell.publish((self.on_drag)(drag));
                             return event::Status::Captured;
                         }
                     }
                 }
-                mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                mouse::Event::ButtonPressed(button @ (mouse::Button::Left | mouse::Button::Right | mouse::Button::Middle)) => {
                     let position = cursor.position().unwrap_or(Point::ORIGIN);
                     if bounds.contains(position) {
                         let view_cursor = position - bounds.position();
-                        state.mouse_press(self.camera, view_cursor);
+                        if button == mouse::Button::Left && self.on_marquee.is_some() {
+                            state.marquee_press(view_cursor);
+                        } else if button == mouse::Button::Left || self.on_marquee.is_some() {
+                            state.mouse_press(self.camera, view_cursor);
+                        }
                         return event::Status::Captured;
                     }
                 }
-                mouse::Event::ButtonReleased(mouse::Button::Left) => {
-                    state.mouse_release();
+                mouse::Event::ButtonReleased(mouse::Button::Left | mouse::Button::Right | mouse::Button::Middle) => {
+                    if let Some(selection) = state.mouse_release(self.camera) {
+                        if let Some(on_marquee) = &self.on_marquee {
+                            shell.publish(on_marquee(selection));
+                        }
+                    }
                     return event::Status::Captured;
                 }
                 mouse::Event::WheelScrolled { delta } => {
@@ -152,12 +201,16 @@ impl<Message> Widget<Message, Theme, Renderer> for SheetWidget<'_, Message> {
             }
         }
 
+        if let Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) = event {
+            state.modifiers = modifiers;
+        }
+
         event::Status::Ignored
     }
 
     fn draw(
         &self,
-        _tree: &Tree,
+        tree: &Tree,
         renderer: &mut Renderer,
         _theme: &Theme,
         _style: &renderer::Style,
@@ -165,6 +218,8 @@ impl<Message> Widget<Message, Theme, Renderer> for SheetWidget<'_, Message> {
         _cursor: mouse::Cursor,
         _viewport: &Rectangle,
     ) {
+        self.draw_marquee(tree.state.downcast_ref::<SheetState>(), renderer, layout);
+
         const MIN_SCALE: f32 = 20.0;
         let scale = self.camera.scale - MIN_SCALE;
         if scale <= 0.0 {
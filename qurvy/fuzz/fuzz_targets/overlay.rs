@@ -0,0 +1,120 @@
+//! Builds a subject and a clip path from the bytes and runs the int boolean overlay.
+//! The result must not panic and its contours must be closed and must not cross each other.
+//!
+//! `cargo fuzz run overlay`
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use qurvy::int::bezier::anchor::{AnchorKind, IntBezierAnchor};
+use qurvy::int::bezier::path::IntBezierPath;
+use qurvy::int::bool::core::fill_rule::FillRule;
+use qurvy::int::bool::core::overlay::{Overlay, ShapeType};
+use qurvy::int::bool::core::overlay_rule::OverlayRule;
+use qurvy::int::math::offset::IntOffset;
+use qurvy::int::math::point::IntPoint;
+
+/// Point coordinates are `i16` values and handles `i8` values scaled by a power of two.
+/// The first byte picks the power, the largest one puts the handle points at `MAX_COORD`.
+const POINT_SHIFTS: [u32; 4] = [0, 8, 16, 24];
+const ANCHOR_BYTES: usize = 9;
+
+const OVERLAY_RULES: [OverlayRule; 4] = [OverlayRule::Union, OverlayRule::Intersect, OverlayRule::Difference, OverlayRule::Xor];
+const FILL_RULES: [FillRule; 4] = [FillRule::EvenOdd, FillRule::NonZero, FillRule::Positive, FillRule::Negative];
+
+struct Input {
+    subject: IntBezierPath,
+    clip: IntBezierPath,
+    overlay_rule: OverlayRule,
+    fill_rule: FillRule,
+}
+
+/// The first byte holds the closed flags, the rules and the scale, then every 9 bytes are an anchor:
+/// the point, the two handles and a flag byte. Bit 0 and 1 of the flag byte enable the handles,
+/// bit 2 moves the anchor to the clip path.
+fn decode(data: &[u8]) -> Option<Input> {
+    let (&head, data) = data.split_first()?;
+
+    let mut subject = IntBezierPath { anchors: Vec::new(), closed: head & 1 != 0 };
+    let mut clip = IntBezierPath { anchors: Vec::new(), closed: head & 2 != 0 };
+
+    let point_shift = POINT_SHIFTS[(head >> 6) as usize];
+    let handle_shift = point_shift + 8;
+
+    for chunk in data.chunks_exact(ANCHOR_BYTES) {
+        let coord = |i: usize| (i16::from_le_bytes([chunk[i], chunk[i + 1]]) as i64) << point_shift;
+        let handle = |i: usize| IntOffset::new(
+            (chunk[i] as i8 as i64) << handle_shift,
+            (chunk[i + 1] as i8 as i64) << handle_shift,
+        );
+        let flags = chunk[8];
+        let anchor = IntBezierAnchor {
+            point: IntPoint::new(coord(0), coord(2)),
+            handle_in: (flags & 1 != 0).then(|| handle(4)),
+            handle_out: (flags & 2 != 0).then(|| handle(6)),
+            kind: AnchorKind::Corner,
+        };
+        if flags & 4 == 0 {
+            subject.anchors.push(anchor);
+        } else {
+            clip.anchors.push(anchor);
+        }
+    }
+
+    Some(Input {
+        subject,
+        clip,
+        overlay_rule: OVERLAY_RULES[(head >> 2 & 3) as usize],
+        fill_rule: FILL_RULES[(head >> 4 & 3) as usize],
+    })
+}
+
+/// True if the open segments `a0 a1` and `b0 b1` cross at a point inside of both.
+fn is_crossing(a0: IntPoint, a1: IntPoint, b0: IntPoint, b1: IntPoint) -> bool {
+    let side = |p: IntPoint, q: IntPoint, r: IntPoint| {
+        let pq = (q.x - p.x) as i128 * (r.y - p.y) as i128;
+        let pr = (q.y - p.y) as i128 * (r.x - p.x) as i128;
+        (pq - pr).signum()
+    };
+
+    let d0 = side(a0, a1, b0);
+    let d1 = side(a0, a1, b1);
+    let d2 = side(b0, b1, a0);
+    let d3 = side(b0, b1, a1);
+
+    d0 * d1 < 0 && d2 * d3 < 0
+}
+
+fn check_contours(contours: &[Vec<IntPoint>]) {
+    let mut segments = Vec::new();
+    for contour in contours.iter() {
+        assert!(contour.len() >= 3, "open or degenerate contour {:?}", contour);
+        let n = contour.len();
+        for i in 0..n {
+            let (a, b) = (contour[i], contour[(i + 1) % n]);
+            assert_ne!(a, b, "repeated point in contour {:?}", contour);
+            segments.push((a, b));
+        }
+    }
+
+    for (i, &(a0, a1)) in segments.iter().enumerate() {
+        for &(b0, b1) in segments[i + 1..].iter() {
+            assert!(!is_crossing(a0, a1, b0, b1), "result segments cross: {:?}-{:?} and {:?}-{:?}", a0, a1, b0, b1);
+        }
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some(input) = decode(data) else {
+        return;
+    };
+
+    let mut overlay = Overlay::new();
+    overlay.try_add_bezier_path(&input.subject, ShapeType::Subject).expect("the subject is in range");
+    overlay.try_add_bezier_path(&input.clip, ShapeType::Clip).expect("the clip is in range");
+
+    let contours = overlay.overlay(input.overlay_rule, input.fill_rule);
+    check_contours(&contours);
+
+    let _ = overlay.crossings();
+});
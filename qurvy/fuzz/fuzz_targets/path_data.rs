@@ -0,0 +1,58 @@
+//! Feeds arbitrary text to the SVG path data parser. Writing a parsed path back must be stable
+//! and the path must survive the conversion to int coordinates and the boolean overlay.
+//!
+//! `cargo fuzz run path_data`
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use qurvy::convert::grid::Grid;
+use qurvy::float::bezier::path::BezierPath;
+use qurvy::float::math::offset::Offset;
+use qurvy::int::bool::core::fill_rule::FillRule;
+use qurvy::int::bool::core::overlay::{Overlay, ShapeType};
+use qurvy::int::bool::core::overlay_rule::OverlayRule;
+
+/// Handles are written as absolute control points, reading them back can change the last bits.
+fn is_same(paths: &[BezierPath], other: &[BezierPath]) -> bool {
+    let is_near = |a: f64, b: f64, scale: f64| (a - b).abs() <= 1e-12 * (1.0 + scale);
+
+    paths.len() == other.len() && paths.iter().zip(other).all(|(p0, p1)| {
+        p0.closed == p1.closed && p0.anchors.len() == p1.anchors.len() && p0.anchors.iter().zip(&p1.anchors).all(|(a0, a1)| {
+            let scale = a0.point.x.abs().max(a0.point.y.abs());
+            let is_same_handle = |h0: Option<Offset>, h1: Option<Offset>| match (h0, h1) {
+                (None, None) => true,
+                (Some(h0), Some(h1)) => {
+                    let scale = scale + h0.x.abs().max(h0.y.abs());
+                    is_near(h0.x, h1.x, scale) && is_near(h0.y, h1.y, scale)
+                }
+                _ => false,
+            };
+            a0.point == a1.point && is_same_handle(a0.handle_in, a1.handle_in) && is_same_handle(a0.handle_out, a1.handle_out)
+        })
+    })
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Some(paths) = BezierPath::from_svg_path(text) else {
+        return;
+    };
+
+    // the written data is parsed again to the same paths
+    let written = BezierPath::to_svg_path(&paths);
+    let reparsed = BezierPath::from_svg_path(&written).expect("written path data must parse");
+    assert!(is_same(&paths, &reparsed), "{:?} is parsed back as {:?}", paths, reparsed);
+
+    let grid = Grid::default();
+    let mut overlay = Overlay::new();
+    for path in paths.iter() {
+        // a path out of the int range is rejected, the others must not overflow
+        if let Ok(int_path) = path.try_to_int(&grid) {
+            overlay.add_bezier_path(&int_path, ShapeType::Subject);
+        }
+    }
+    let _ = overlay.overlay(OverlayRule::Union, FillRule::NonZero);
+});
@@ -0,0 +1,263 @@
+use crate::bezier::spline::{tangent, CADSpline};
+use crate::bezier::vector::SplineVector;
+use crate::data::list::{EMPTY_REF, LinkList};
+
+pub trait Approximation<P: SplineVector> {
+    fn approximate(&self, min_cos: P::Scalar, min_len: P::Scalar) -> Vec<Short<P>>;
+    fn approximate_points(&self, min_cos: P::Scalar, min_len: P::Scalar) -> Vec<P>;
+}
+
+impl<P: SplineVector, Spline: CADSpline<P>> Approximation<P> for Spline {
+    #[inline]
+    fn approximate(&self, min_cos: P::Scalar, min_len: P::Scalar) -> Vec<Short<P>> {
+        debug_assert!(min_cos <= P::MAX_COS);
+        Solver::approximate(self, min_cos, min_len)
+    }
+
+    #[inline]
+    fn approximate_points(&self, min_cos: P::Scalar, min_len: P::Scalar) -> Vec<P> {
+        let shorts = Solver::approximate(self, min_cos, min_len);
+        let mut points: Vec<_> = shorts.iter().map(|s| s.a).collect();
+        if let Some(last) = shorts.last() {
+            points.push(last.b);
+        }
+
+        points
+    }
+}
+
+/// A chord of the spline between `step / 2^split_factor` and `(step + 1) / 2^split_factor`.
+#[derive(Debug, Copy, Clone)]
+pub struct Short<P> {
+    pub step: usize,
+    pub split_factor: u32,
+    /// Chord direction normalized to the unit length of the stack.
+    pub dir: P,
+    pub a: P,
+    pub b: P,
+}
+
+struct Solver<'a, P: SplineVector, Spline> {
+    limits: P::Limits,
+    st_dir: P,
+    ed_dir: P,
+    spline: &'a Spline,
+    segments: LinkList<Short<P>>,
+}
+
+impl<'a, P: SplineVector, Spline: CADSpline<P>> Solver<'a, P, Spline> {
+    /// A chord that still fails the tests at this depth is kept, the rounding error is larger than the chord.
+    const MAX_SPLIT_FACTOR: u32 = 32;
+
+    #[inline]
+    fn approximate(spline: &Spline, min_cos: P::Scalar, min_len: P::Scalar) -> Vec<Short<P>> {
+        let st_dir = spline.start_dir();
+        let ed_dir = spline.end_dir();
+
+        let segments = LinkList::new(vec![Short {
+            step: 0,
+            split_factor: 0,
+            // the chord of a closed loop has no direction, it fails the angle test and gets split
+            dir: tangent(&[spline.end() - spline.start()]),
+            a: spline.start(),
+            b: spline.end(),
+        }]);
+
+        Solver {
+            limits: P::limits(min_cos, min_len),
+            st_dir,
+            ed_dir,
+            spline,
+            segments,
+        }
+        .process()
+    }
+
+    #[inline]
+    fn process(&mut self) -> Vec<Short<P>> {
+        let mut buffer = Vec::with_capacity(16);
+        buffer.push(0);
+
+        let mut to_split = Vec::with_capacity(16);
+
+        while !buffer.is_empty() {
+            for index in buffer.iter() {
+                if self.split_test(*index) {
+                    to_split.push(*index);
+                }
+            }
+
+            buffer.clear();
+            for &index in to_split.iter() {
+                self.split(index, &mut buffer);
+            }
+            to_split.clear();
+        }
+
+        let mut shorts = Vec::with_capacity(self.segments.len());
+        let mut index = 0;
+        while index != EMPTY_REF {
+            let node = self.segments.get(index);
+            shorts.push(node.item);
+            index = node.next
+        }
+
+        shorts
+    }
+
+    fn split_test(&self, index: u32) -> bool {
+        let node = self.segments.get(index);
+        let prev = node.prev;
+        let next = node.next;
+        let dir = node.item.dir;
+        let prev_dir = if prev != EMPTY_REF {
+            self.segments.get(prev).item.dir
+        } else {
+            self.st_dir
+        };
+
+        if dir.is_turn(&prev_dir, &self.limits) {
+            return true;
+        }
+
+        let next_dir = if next != EMPTY_REF {
+            self.segments.get(next).item.dir
+        } else {
+            self.ed_dir
+        };
+
+        dir.is_turn(&next_dir, &self.limits)
+    }
+
+    fn split(&mut self, index: u32, result: &mut Vec<u32>) {
+        let short = self.segments.get(index).item;
+
+        let split_factor = short.split_factor + 1;
+        let m = self.spline.split_at(short.step + 1, split_factor);
+        if m == short.a || m == short.b {
+            // the rounding of the spline points can not refine this chord any more
+            return;
+        }
+
+        let ma = m - short.a;
+        let bm = short.b - m;
+
+        let s0 = Short {
+            step: short.step << 1,
+            split_factor,
+            dir: ma.direction(),
+            a: short.a,
+            b: m,
+        };
+
+        let s1 = Short {
+            step: (short.step + 1) << 1,
+            split_factor,
+            dir: bm.direction(),
+            a: m,
+            b: short.b,
+        };
+
+        let (i0, i1) = self.segments.split_at(index, s0, s1);
+
+        if split_factor >= Self::MAX_SPLIT_FACTOR {
+            return;
+        }
+
+        if !ma.is_short(&self.limits) {
+            result.push(i0)
+        }
+
+        if !bm.is_short(&self.limits) {
+            result.push(i1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier::approximation::Approximation;
+    use crate::bezier::iter::SplinePointsIter;
+    use crate::bezier::length::SplineLength;
+    use crate::bezier::spline::CADSpline;
+    use crate::convert::grid::Grid;
+    use crate::convert::to_int::ToInt;
+    use crate::float::bezier::spline_quad::QuadSpline;
+    use crate::float::math::point::Point;
+    use crate::int::bezier::spline_quad::IntQuadSpline;
+    use crate::int::math::point::IntPoint;
+
+    // 2^12 int units per float unit
+    fn grid() -> Grid {
+        Grid::new(12, 0)
+    }
+
+    /// The same splines in both stacks: an arch, an S-curve, a zero length handle and a loop.
+    fn pairs() -> Vec<(QuadSpline, IntQuadSpline)> {
+        let grid = grid();
+        let points = [
+            [(0.0, 0.0), (0.0, 50.0), (100.0, 50.0), (100.0, 0.0)],
+            [(-20.5, 3.25), (40.0, 90.0), (-10.0, -60.0), (60.125, 10.0)],
+            [(0.0, 0.0), (0.0, 0.0), (30.0, 70.0), (100.0, 70.0)],
+            [(0.0, 0.0), (80.0, 60.0), (-80.0, 60.0), (0.0, 0.0)],
+        ];
+        points.iter().map(|p| {
+            let [a, am, bm, b] = p.map(|(x, y)| Point::new(x, y));
+            let float = QuadSpline { a, am, bm, b };
+            let int = IntQuadSpline { a: a.to_int(&grid), am: am.to_int(&grid), bm: bm.to_int(&grid), b: b.to_int(&grid) };
+            (float, int)
+        }).collect()
+    }
+
+    fn to_float(p: IntPoint) -> Point {
+        let grid = grid();
+        Point::new(grid.int_to_float(p.x), grid.int_to_float(p.y))
+    }
+
+    #[test]
+    fn test_00() {
+        for (float, int) in pairs() {
+            assert_eq!(to_float(int.start()), float.start());
+            assert_eq!(to_float(int.end()), float.end());
+
+            let dirs = [(float.start_dir(), int.start_dir()), (float.end_dir(), int.end_dir())];
+            for (f, i) in dirs {
+                let i = Point::new(i.x as f64, i.y as f64);
+                let cos = f.dot_product(&i) / i.length();
+                assert!(cos > 0.999, "{:?} {:?}", f, i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_01() {
+        // every de Casteljau level of the int stack truncates by less than a unit
+        let unit = grid().int_to_float(1);
+        for (float, int) in pairs() {
+            let fs: Vec<_> = float.points_iter(true, true, 4).collect();
+            let is: Vec<_> = int.points_iter(true, true, 4).collect();
+            assert_eq!(fs.len(), is.len());
+            for (f, i) in fs.iter().zip(is.iter()) {
+                assert!(f.distance(to_float(*i)) <= 4.0 * unit);
+            }
+        }
+    }
+
+    #[test]
+    fn test_02() {
+        // the same tolerances in the units of each stack
+        let unit = grid().int_to_float(1);
+        for (float, int) in pairs() {
+            let float_len = float.avg_length(0.99, 0.01);
+            let int_len = int.avg_length(1014, 41) * unit;
+            assert!((float_len - int_len).abs() < 1e-3 * float_len, "{} {}", float_len, int_len);
+
+            let fs = float.approximate_points(0.99, 0.01);
+            let is = int.approximate_points(1014, 41);
+            assert_eq!(fs.first().copied(), is.first().map(|p| to_float(*p)));
+            assert_eq!(fs.last().copied(), is.last().map(|p| to_float(*p)));
+            // the int stack compares lengths by powers of two, so it may split up to twice as often
+            assert!(fs.len() <= is.len() && is.len() <= 2 * fs.len(), "{} {}", fs.len(), is.len());
+        }
+    }
+}
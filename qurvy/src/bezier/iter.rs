@@ -1,36 +1,38 @@
-use crate::float::bezier::spline::CADSpline;
-use crate::float::math::point::Point;
+use std::marker::PhantomData;
+use crate::bezier::spline::CADSpline;
 
-pub(crate) trait SplinePointsIter {
-    type ResourceIter<'a>: Iterator<Item = Point>
+pub(crate) trait SplinePointsIter<P> {
+    type ResourceIter<'a>: Iterator<Item = P>
     where
         Self: 'a;
 
     fn points_iter(&self, start: bool, end: bool, split_factor: u32) -> Self::ResourceIter<'_>;
 }
 
-impl<Spline> SplinePointsIter for Spline
+impl<P, Spline> SplinePointsIter<P> for Spline
 where
-    Spline: CADSpline,
+    Spline: CADSpline<P>,
 {
-    type ResourceIter<'a> = SplinePointsIterator<'a, Spline>
+    type ResourceIter<'a>
+        = SplinePointsIterator<'a, P, Spline>
     where
         Spline: 'a;
 
     #[inline]
-    fn points_iter(&self, start: bool, end: bool, split_factor: u32) -> SplinePointsIterator<Self> {
+    fn points_iter(&self, start: bool, end: bool, split_factor: u32) -> SplinePointsIterator<'_, P, Self> {
         SplinePointsIterator::new(split_factor, start, end, self)
     }
 }
 
-pub(crate) struct SplinePointsIterator<'a, Spline> {
+pub(crate) struct SplinePointsIterator<'a, P, Spline> {
     spline: &'a Spline,
+    point: PhantomData<P>,
     count: usize,
     split_factor: u32,
     i: usize,
 }
 
-impl<'a, Spline> SplinePointsIterator<'a, Spline> {
+impl<'a, P, Spline> SplinePointsIterator<'a, P, Spline> {
     #[inline]
     pub(crate) fn new(split_factor: u32, start: bool, end: bool, spline: &'a Spline) -> Self {
         let count = (1 << split_factor) + end as usize;
@@ -40,12 +42,13 @@ impl<'a, Spline> SplinePointsIterator<'a, Spline> {
             count,
             split_factor,
             spline,
+            point: PhantomData,
         }
     }
 }
 
-impl<'a, Spline: CADSpline> Iterator for SplinePointsIterator<'a, Spline> {
-    type Item = Point;
+impl<'a, P, Spline: CADSpline<P>> Iterator for SplinePointsIterator<'a, P, Spline> {
+    type Item = P;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
@@ -63,4 +66,4 @@ impl<'a, Spline: CADSpline> Iterator for SplinePointsIterator<'a, Spline> {
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.count, Some(self.count))
     }
-}
\ No newline at end of file
+}
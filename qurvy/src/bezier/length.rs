@@ -0,0 +1,99 @@
+use crate::bezier::approximation::Approximation;
+use crate::bezier::spline::CADSpline;
+use crate::bezier::vector::SplineVector;
+
+pub(crate) trait SplineLength<P: SplineVector> {
+    /// The length of the approximation polyline.
+    fn avg_length(&self, min_cos: P::Scalar, min_len: P::Scalar) -> f64;
+}
+
+impl<P: SplineVector, Spline: CADSpline<P>> SplineLength<P> for Spline {
+    fn avg_length(&self, min_cos: P::Scalar, min_len: P::Scalar) -> f64 {
+        let points = self.approximate_points(min_cos, min_len);
+        let mut len = 0.0;
+        for w in points.windows(2) {
+            len += (w[1] - w[0]).length()
+        }
+
+        len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bezier::length::SplineLength;
+    use crate::float::bezier::spline_cube::CubeSpline;
+    use crate::float::bezier::spline_line::LineSpline;
+    use crate::float::bezier::spline_quad::QuadSpline;
+    use crate::float::math::point::Point;
+    use crate::int::bezier::spline_cube::IntCubeSpline;
+    use crate::int::bezier::spline_line::IntLineSpline;
+    use crate::int::bezier::spline_quad::IntQuadSpline;
+    use crate::int::math::point::IntPoint;
+
+    #[test]
+    fn test_00() {
+        let spline = IntLineSpline {
+            a: IntPoint::new(0, 0),
+            b: IntPoint::new(100, 100),
+        };
+
+        assert_eq!(spline.avg_length(800, 3) as usize, 141);
+    }
+
+    #[test]
+    fn test_01() {
+        let spline = IntCubeSpline {
+            a: IntPoint::new(0, 0),
+            m: IntPoint::new(0, 100),
+            b: IntPoint::new(100, 100),
+        };
+
+        assert_eq!(spline.avg_length(800, 3) as usize, 161);
+    }
+
+    #[test]
+    fn test_02() {
+        let spline = IntQuadSpline {
+            a: IntPoint::new(0, 0),
+            am: IntPoint::new(0, 50),
+            bm: IntPoint::new(50, 100),
+            b: IntPoint::new(100, 100),
+        };
+
+        assert_eq!(spline.avg_length(800, 3) as usize, 153);
+    }
+
+    #[test]
+    fn test_03() {
+        let spline = LineSpline {
+            a: Point::new(0.0, 0.0),
+            b: Point::new(100.0, 100.0),
+        };
+
+        assert_eq!(spline.avg_length(800.0, 3.0) as usize, 141);
+    }
+
+    #[test]
+    fn test_04() {
+        let spline = CubeSpline {
+            a: Point::new(0.0, 0.0),
+            m: Point::new(0.0, 100.0),
+            b: Point::new(100.0, 100.0),
+        };
+
+        assert_eq!(spline.avg_length(800.0, 3.0) as usize, 162);
+    }
+
+    #[test]
+    fn test_05() {
+        let spline = QuadSpline {
+            a: Point::new(0.0, 0.0),
+            am: Point::new(0.0, 50.0),
+            bm: Point::new(50.0, 100.0),
+            b: Point::new(100.0, 100.0),
+        };
+
+        assert_eq!(spline.avg_length(800.0, 3.0) as usize, 154);
+    }
+}
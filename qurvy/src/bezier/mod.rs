@@ -0,0 +1,5 @@
+pub mod vector;
+pub mod approximation;
+pub(crate) mod spline;
+pub(crate) mod iter;
+pub(crate) mod length;
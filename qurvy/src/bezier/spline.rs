@@ -0,0 +1,20 @@
+use crate::bezier::vector::SplineVector;
+
+pub(crate) trait CADSpline<P> {
+    fn start(&self) -> P;
+    fn start_dir(&self) -> P;
+    fn end_dir(&self) -> P;
+    fn end(&self) -> P;
+    fn split_at(&self, step: usize, split_factor: u32) -> P;
+}
+
+/// The tangent direction at an end of a spline: the first non-zero vector from that end to the
+/// following control points. Where the first derivative vanishes, the vector to the next
+/// control point has the direction of the second derivative, and so on.
+/// The zero vector for a spline that collapsed to a point.
+#[inline]
+pub(crate) fn tangent<P: SplineVector>(vectors: &[P]) -> P {
+    vectors.iter()
+        .find(|v| **v != P::zero())
+        .map_or(P::zero(), |v| v.direction())
+}
@@ -0,0 +1,28 @@
+use std::ops::Sub;
+
+/// The point arithmetic the spline algorithms of `crate::bezier` run on,
+/// implemented by the points of the float and of the int stack.
+pub trait SplineVector: Copy + PartialEq + Sub<Output = Self> {
+    /// The scalar of the approximation parameters `min_cos` and `min_len`.
+    type Scalar: Copy + PartialOrd;
+    /// `min_cos` and `min_len` prepared for `is_turn` and `is_short`.
+    type Limits: Copy;
+
+    /// The cos of a zero angle, the largest `min_cos`.
+    const MAX_COS: Self::Scalar;
+
+    fn zero() -> Self;
+
+    fn limits(min_cos: Self::Scalar, min_len: Self::Scalar) -> Self::Limits;
+
+    /// The vector scaled to the unit length of the stack, the zero vector stays zero.
+    fn direction(&self) -> Self;
+
+    /// True if the cos of the angle between two directions is below `min_cos`.
+    fn is_turn(&self, other: &Self, limits: &Self::Limits) -> bool;
+
+    /// True if the vector is too short to be split again.
+    fn is_short(&self, limits: &Self::Limits) -> bool;
+
+    fn length(&self) -> f64;
+}
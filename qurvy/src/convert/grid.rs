@@ -1,24 +1,112 @@
+use serde::{Deserialize, Serialize};
+use crate::convert::to_int::Rounding;
+use crate::error::Error;
+use crate::float::math::point::Point;
+use crate::int::math::range::MAX_COORD;
 
+/// Only the powers are serialized, the scales are derived from them.
+/// Powers out of the supported range are rejected on deserialization, see `try_new`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "GridPowers", into = "GridPowers")]
 pub struct Grid {
+    scale_power: i32,
     size_power: u32, // power of 2, default is 4 (2^4 = 16)
     scale_to_int: f64,
     scale_to_float: f64
 }
 
+#[derive(Serialize, Deserialize)]
+struct GridPowers {
+    scale_power: i32,
+    size_power: u32,
+}
+
+impl TryFrom<GridPowers> for Grid {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(powers: GridPowers) -> Result<Self, Error> {
+        Grid::try_new(powers.scale_power, powers.size_power)
+    }
+}
+
+impl From<Grid> for GridPowers {
+    #[inline]
+    fn from(grid: Grid) -> Self {
+        GridPowers { scale_power: grid.scale_power, size_power: grid.size_power }
+    }
+}
+
 impl Grid {
+    /// The largest cell, one cell spans the whole int range `MAX_COORD`.
+    pub const MAX_SIZE_POWER: u32 = MAX_COORD.trailing_zeros();
 
     pub fn new(scale_power: i32, size_power: u32) -> Self {
         let e = scale_power + size_power as i32;
         let scale_to_int = 2f64.powi(e);
         let scale_to_float = 2f64.powi(-e);
 
-        Self { size_power, scale_to_int, scale_to_float }
+        Self { scale_power, size_power, scale_to_int, scale_to_float }
+    }
+
+    /// `new` that fails for a cell larger than the int range, see `MAX_SIZE_POWER`, or for
+    /// a total scale `2^(scale_power + size_power)` that does not fit a normal `f64` both ways.
+    pub fn try_new(scale_power: i32, size_power: u32) -> Result<Self, Error> {
+        if size_power > Self::MAX_SIZE_POWER {
+            return Err(Error::InvalidParameter("size_power"));
+        }
+        let e = scale_power as i64 + size_power as i64;
+        if e.unsigned_abs() >= f64::MAX_EXP as u64 {
+            return Err(Error::InvalidParameter("scale_power"));
+        }
+
+        Ok(Self::new(scale_power, size_power))
     }
 
     pub fn debug() -> Self {
         Self::new(10,3)
     }
 
+    /// The grid with the largest scale that keeps the float `bounds` (min, max) within the safe
+    /// int range, and with grid cells no larger than `required_precision` in float units.
+    /// Half of the range is left as a margin for the snapping. The bounds must cover the handle
+    /// points as well, see `BezierPath::control_bounds`.
+    /// `None` for a bound that is not finite, a precision that is not positive, or a precision
+    /// too fine to fit the bounds into the range. The scale of tiny bounds is limited to what
+    /// an `f64` represents, see `try_new`.
+    pub fn fit(bounds: (Point, Point), required_precision: f64) -> Option<Self> {
+        let (min, max) = bounds;
+        let coords = [min.x, min.y, max.x, max.y];
+        if !coords.iter().all(|v| v.is_finite()) || required_precision <= 0.0 || !required_precision.is_finite() {
+            return None;
+        }
+
+        // the precision itself must be in range, it limits the scale of the empty bounds
+        let extent = coords.iter().fold(required_precision, |m, v| m.max(v.abs()));
+        // the difference of the logarithms, the quotient overflows for tiny bounds
+        let max_e = f64::MAX_EXP - 1;
+        let e = (((MAX_COORD >> 1) as f64).log2() - extent.log2()).floor() as i32;
+        let e = e.clamp(-max_e, max_e);
+        let size_power = (required_precision.log2() + e as f64).floor() as i32;
+        if size_power < 0 {
+            return None;
+        }
+
+        Self::try_new(e - size_power, size_power as u32).ok()
+    }
+
+    /// The power of 2 of the grid cells per float unit.
+    #[inline(always)]
+    pub fn scale_power(&self) -> i32 {
+        self.scale_power
+    }
+
+    /// The power of 2 of the int units per grid cell.
+    #[inline(always)]
+    pub fn size_power(&self) -> u32 {
+        self.size_power
+    }
+
     #[inline(always)]
     fn snap_to_grid_value(&self, a: i64) -> i64 {
         let p = self.size_power;
@@ -27,6 +115,12 @@ impl Grid {
         (c + s) << p
     }
 
+    /// Distance between two neighbour grid values in int coordinates.
+    #[inline(always)]
+    pub fn cell_size(&self) -> i64 {
+        1 << self.size_power
+    }
+
     #[inline(always)]
     pub fn int_to_float(&self, a: i64) -> f64 {
         a as f64 * self.scale_to_float
@@ -36,6 +130,37 @@ impl Grid {
     pub fn float_to_int(&self, a: f64) -> i64 {
         self.snap_to_grid_value((a * self.scale_to_int) as i64)
     }
+
+    /// `float_to_int` with the given rounding policy, returns the value and its rounding error
+    /// in float units.
+    #[inline]
+    pub fn float_to_int_rounded(&self, a: f64, rounding: Rounding) -> (i64, f64) {
+        let value = match rounding {
+            Rounding::Snap => self.float_to_int(a),
+            Rounding::Nearest => (a * self.scale_to_int).round() as i64,
+            Rounding::Floor => (a * self.scale_to_int).floor() as i64,
+        };
+
+        (value, (self.int_to_float(value) - a).abs())
+    }
+
+    /// The largest float magnitude that converts into the int range, `MAX_COORD` in float units.
+    #[inline]
+    pub fn max_float_value(&self) -> f64 {
+        MAX_COORD as f64 * self.scale_to_float
+    }
+
+    /// `float_to_int` that returns `None` for a value that is not finite or falls out of
+    /// `[-MAX_COORD, MAX_COORD]` after the snapping.
+    #[inline]
+    pub fn checked_float_to_int(&self, a: f64) -> Option<i64> {
+        let v = a * self.scale_to_int;
+        if !v.is_finite() || v.abs() > MAX_COORD as f64 {
+            return None;
+        }
+        let value = self.snap_to_grid_value(v as i64);
+        (value.unsigned_abs() <= MAX_COORD as u64).then_some(value)
+    }
 }
 
 impl Default for Grid {
@@ -47,6 +172,10 @@ impl Default for Grid {
 #[cfg(test)]
 mod tests {
     use crate::convert::grid::Grid;
+    use crate::convert::to_int::Rounding;
+    use crate::error::Error;
+    use crate::float::math::point::Point;
+    use crate::int::math::range::MAX_COORD;
 
     #[test]
     fn test_0() {
@@ -71,4 +200,107 @@ mod tests {
         let grid = Grid::new(0, 4);
         assert_eq!(grid.snap_to_grid_value(-9), -16);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_4() {
+        let grid = Grid::debug();
+        assert_eq!(grid.cell_size(), 8);
+        assert_eq!(grid.float_to_int(1.3) % grid.cell_size(), 0);
+    }
+
+    #[test]
+    fn test_5() {
+        let grid = Grid::default();
+        let max = grid.max_float_value();
+        assert_eq!(max, 65536.0);
+        assert_eq!(grid.checked_float_to_int(-max), Some(-MAX_COORD));
+        assert_eq!(grid.checked_float_to_int(1.3), Some(grid.float_to_int(1.3)));
+        assert_eq!(grid.checked_float_to_int(max + 1.0), None);
+        assert_eq!(grid.checked_float_to_int(f64::NAN), None);
+        assert_eq!(grid.checked_float_to_int(f64::INFINITY), None);
+    }
+
+    #[test]
+    fn test_6() {
+        // 2^-13 per int unit, 2^-10 per grid cell
+        let grid = Grid::debug();
+        let a = 5.0 * 2f64.powi(-13) - 1e-9;
+        assert_eq!(grid.float_to_int_rounded(a, Rounding::Snap).0, 8);
+        assert_eq!(grid.float_to_int_rounded(a, Rounding::Nearest).0, 5);
+        assert_eq!(grid.float_to_int_rounded(a, Rounding::Floor).0, 4);
+        assert_eq!(grid.float_to_int_rounded(-a, Rounding::Floor).0, -5);
+
+        for rounding in [Rounding::Snap, Rounding::Nearest, Rounding::Floor] {
+            let (value, error) = grid.float_to_int_rounded(a, rounding);
+            assert_eq!(error, (grid.int_to_float(value) - a).abs());
+        }
+        assert!(grid.float_to_int_rounded(a, Rounding::Nearest).1 <= 0.5 * 2f64.powi(-13));
+    }
+
+    #[test]
+    fn test_7() {
+        let bounds = (Point::new(-1000.0, -1000.0), Point::new(1000.0, 500.0));
+        let grid = Grid::fit(bounds, 0.001).unwrap();
+        let cell = grid.int_to_float(grid.cell_size());
+        assert_eq!(grid.scale_power(), 10);
+
+        // the finest scale that keeps the margin, the coarsest cell within the precision
+        assert!(cell <= 0.001 && 2.0 * cell > 0.001);
+        assert!(grid.max_float_value() >= 2000.0 && grid.max_float_value() < 4000.0);
+        assert_eq!(grid.checked_float_to_int(-1000.0), Some(-1000 << (grid.scale_power() + grid.size_power() as i32)));
+
+        assert!(Grid::fit((Point::new(0.0, 0.0), Point::new(0.0, 0.0)), 0.5).is_some());
+        assert!(Grid::fit(bounds, 0.0).is_none());
+        assert!(Grid::fit((Point::new(f64::NAN, 0.0), Point::new(1.0, 1.0)), 0.1).is_none());
+        assert!(Grid::fit((Point::new(-1e6, 0.0), Point::new(1e6, 1.0)), 1e-12).is_none());
+    }
+
+    #[test]
+    fn test_8() {
+        let grid = Grid::debug();
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(json, r#"{"scale_power":10,"size_power":3}"#);
+        assert_eq!(serde_json::from_str::<Grid>(&json).unwrap(), grid);
+    }
+
+    #[test]
+    fn test_9() {
+        let max = Grid::try_new(-40, Grid::MAX_SIZE_POWER).unwrap();
+        assert_eq!(max.cell_size(), MAX_COORD);
+        assert_eq!(max.float_to_int(2f64.powi(39)), MAX_COORD);
+        assert_eq!(Grid::try_new(1000, 23), Ok(Grid::new(1000, 23)));
+
+        assert_eq!(Grid::try_new(0, 41), Err(Error::InvalidParameter("size_power")));
+        assert_eq!(Grid::try_new(1020, 4), Err(Error::InvalidParameter("scale_power")));
+        assert_eq!(Grid::try_new(-1024, 0), Err(Error::InvalidParameter("scale_power")));
+        assert_eq!(Grid::try_new(i32::MAX, 40), Err(Error::InvalidParameter("scale_power")));
+
+        for json in [
+            r#"{"scale_power":10,"size_power":64}"#,
+            r#"{"scale_power":10,"size_power":41}"#,
+            r#"{"scale_power":2000,"size_power":4}"#,
+            r#"{"scale_power":-2000,"size_power":4}"#,
+        ] {
+            let error = serde_json::from_str::<Grid>(json).unwrap_err();
+            assert!(error.to_string().starts_with("invalid parameter"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_10() {
+        // the tiny bounds take the largest scale an `f64` represents
+        let tiny = Grid::fit((Point::new(0.0, 0.0), Point::new(1e-300, 1e-300)), 2.5e-301).unwrap();
+        assert_eq!(tiny.scale_power() + tiny.size_power() as i32, f64::MAX_EXP - 1);
+        let v = tiny.checked_float_to_int(1e-300).unwrap();
+        assert!(v > 0 && v <= MAX_COORD);
+
+        let huge = Grid::fit((Point::new(-1e300, 0.0), Point::new(1e300, 1.0)), 1e290).unwrap();
+        assert!(huge.checked_float_to_int(-1e300).is_some());
+        assert!(Grid::fit((Point::new(-1e300, 0.0), Point::new(1e300, 1.0)), 1.0).is_none());
+
+        for grid in [tiny, huge] {
+            let json = serde_json::to_string(&grid).unwrap();
+            assert_eq!(serde_json::from_str::<Grid>(&json).unwrap(), grid);
+        }
+    }
+}
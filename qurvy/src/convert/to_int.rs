@@ -1,5 +1,28 @@
 use crate::convert::grid::Grid;
 
+/// How `ToInt` rounds a float coordinate scaled by the grid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Rounding {
+    /// To the nearest grid cell, as `Grid::float_to_int` does.
+    #[default]
+    Snap,
+    /// To the nearest int value.
+    Nearest,
+    /// To the int value below.
+    Floor,
+}
+
+/// The conversion of float geometry into int geometry on a `Grid`.
+/// Implemented for `Point`, `Offset`, `BezierAnchor` and `BezierPath`.
 pub trait ToInt<T> {
-    fn to_int(&self, grid: &Grid) -> T;
-}
\ No newline at end of file
+    /// Converts with the `Rounding::Snap` policy.
+    #[inline]
+    fn to_int(&self, grid: &Grid) -> T {
+        self.to_int_rounded(grid, Rounding::Snap).0
+    }
+
+    /// Converts with the given rounding policy. Returns the result and the max rounding error
+    /// of a single coordinate in float units. A handle point adds the errors of its anchor
+    /// point and of its offset, so it can be off by twice that.
+    fn to_int_rounded(&self, grid: &Grid, rounding: Rounding) -> (T, f64);
+}
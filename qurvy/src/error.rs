@@ -0,0 +1,53 @@
+use std::fmt;
+use crate::int::math::range::RangeError;
+
+/// The failures of the fallible `try_` variants of the path APIs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The path has no anchors, or no splines where the API needs one.
+    EmptyPath,
+    /// All control points of the spline that starts at anchor `spline_index` coincide,
+    /// so it has no direction. `canonicalized` paths have no such splines.
+    DegenerateSpline { spline_index: usize },
+    /// A zero vector has no direction.
+    ZeroVector,
+    /// A coordinate is outside of the int range.
+    OutOfRange(RangeError),
+    /// A float coordinate is NaN or infinite.
+    NotFinite,
+    /// A parameter is outside of its domain, the value names it.
+    InvalidParameter(&'static str),
+    /// Rounding the crossings of the boolean edges kept producing new crossings,
+    /// so the edges could not be split into a planar graph.
+    UnresolvedCrossings,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::EmptyPath => write!(f, "the path has no anchors"),
+            Error::DegenerateSpline { spline_index } => write!(f, "spline {} is degenerate", spline_index),
+            Error::ZeroVector => write!(f, "a zero vector has no direction"),
+            Error::OutOfRange(e) => e.fmt(f),
+            Error::NotFinite => write!(f, "a coordinate is not finite"),
+            Error::InvalidParameter(name) => write!(f, "invalid parameter `{}`", name),
+            Error::UnresolvedCrossings => write!(f, "the crossings of the edges could not be resolved"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::OutOfRange(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<RangeError> for Error {
+    #[inline]
+    fn from(e: RangeError) -> Self {
+        Error::OutOfRange(e)
+    }
+}
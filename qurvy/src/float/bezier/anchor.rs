@@ -1,38 +1,112 @@
 use serde::{Deserialize, Serialize};
 use crate::convert::grid::Grid;
-use crate::convert::to_int::ToInt;
+use crate::convert::to_int::{Rounding, ToInt};
+use crate::float::math::number::FloatNumber;
 use crate::float::math::offset::Offset;
 use crate::float::math::point::Point;
-use crate::int::bezier::anchor::IntBezierAnchor;
+use crate::int::bezier::anchor::{AnchorKind, IntBezierAnchor};
 
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-pub struct BezierAnchor {
-    pub point: Point,
-    pub handle_in: Option<Offset>,
-    pub handle_out: Option<Offset>,
+pub struct BezierAnchor<T = f64> {
+    pub point: Point<T>,
+    pub handle_in: Option<Offset<T>>,
+    pub handle_out: Option<Offset<T>>,
+    #[serde(default, skip_serializing_if = "AnchorKind::is_corner")]
+    pub kind: AnchorKind,
 }
 
-impl BezierAnchor {
+impl<T: FloatNumber> BezierAnchor<T> {
 
     #[inline]
-    pub fn handle_in_point(&self) -> Option<Point> {
+    pub fn handle_in_point(&self) -> Option<Point<T>> {
         self.handle_in.map(|offset|self.point + offset)
     }
 
     #[inline]
-    pub fn handle_out_point(&self) -> Option<Point> {
+    pub fn handle_out_point(&self) -> Option<Point<T>> {
         self.handle_out.map(|offset|self.point + offset)
     }
-}
 
-impl ToInt<IntBezierAnchor> for BezierAnchor {
+    /// Sets the incoming handle and updates the outgoing one according to the anchor kind.
+    /// Editing a handle of an `Auto` anchor turns it into a `Smooth` one.
+    #[inline]
+    pub fn set_handle_in(&mut self, handle: Offset<T>) {
+        self.handle_in = Some(handle);
+        if self.kind == AnchorKind::Auto {
+            self.kind = AnchorKind::Smooth;
+        }
+        self.handle_out = Self::constrained(self.kind, handle, self.handle_out);
+    }
+
+    /// Sets the outgoing handle and updates the incoming one according to the anchor kind.
+    /// Editing a handle of an `Auto` anchor turns it into a `Smooth` one.
+    #[inline]
+    pub fn set_handle_out(&mut self, handle: Offset<T>) {
+        self.handle_out = Some(handle);
+        if self.kind == AnchorKind::Auto {
+            self.kind = AnchorKind::Smooth;
+        }
+        self.handle_in = Self::constrained(self.kind, handle, self.handle_in);
+    }
+
+    /// Changes the anchor kind. The outgoing handle is kept and the incoming one is adjusted.
     #[inline]
-    fn to_int(&self, grid: &Grid) -> IntBezierAnchor {
-        IntBezierAnchor {
-            point: self.point.to_int(grid),
-            handle_in: self.handle_in.map(|handle| handle.to_int(grid)),
-            handle_out: self.handle_out.map(|handle| handle.to_int(grid)),
+    pub fn set_kind(&mut self, kind: AnchorKind) {
+        self.kind = kind;
+        if let Some(handle) = self.handle_out {
+            self.handle_in = Self::constrained(kind, handle, self.handle_in);
+        } else if let Some(handle) = self.handle_in {
+            self.handle_out = Self::constrained(kind, handle, self.handle_out);
+        }
+    }
+
+    /// Absorbs the next anchor if the spline to it is shorter than `tolerance`, returns true then.
+    /// Two handles that were not tied together leave a `Corner`.
+    pub(crate) fn merge(&mut self, next: &BezierAnchor<T>, tolerance: T) -> bool {
+        if self.point.distance(next.point) > tolerance || self.handle_out.is_some() || next.handle_in.is_some() {
+            return false;
         }
+        self.handle_out = next.handle_out;
+        if self.handle_in.is_some() && self.handle_out.is_some() {
+            self.kind = AnchorKind::Corner;
+        }
+
+        true
+    }
+
+    fn constrained(kind: AnchorKind, lead: Offset<T>, other: Option<Offset<T>>) -> Option<Offset<T>> {
+        match kind {
+            AnchorKind::Corner | AnchorKind::Auto => other,
+            AnchorKind::Symmetric => Some(-lead),
+            AnchorKind::Smooth => {
+                let other = other?;
+                let lead_len = lead.length();
+                if lead_len == T::ZERO {
+                    return Some(other);
+                }
+                let s = -other.length() / lead_len;
+                Some(Offset::new(lead.x * s, lead.y * s))
+            }
+        }
+    }
+}
+
+impl<T: FloatNumber> ToInt<IntBezierAnchor> for BezierAnchor<T> {
+    #[inline]
+    fn to_int_rounded(&self, grid: &Grid, rounding: Rounding) -> (IntBezierAnchor, f64) {
+        let mut max_error = 0f64;
+        let mut round = |handle: Offset<T>| {
+            let (handle, error) = handle.to_int_rounded(grid, rounding);
+            max_error = max_error.max(error);
+            handle
+        };
+        let handle_in = self.handle_in.map(&mut round);
+        let handle_out = self.handle_out.map(&mut round);
+
+        let (point, error) = self.point.to_int_rounded(grid, rounding);
+        let anchor = IntBezierAnchor { point, handle_in, handle_out, kind: self.kind };
+
+        (anchor, max_error.max(error))
     }
-}
\ No newline at end of file
+}
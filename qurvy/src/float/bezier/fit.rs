@@ -0,0 +1,489 @@
+use crate::float::bezier::anchor::BezierAnchor;
+use crate::float::math::point::Point;
+use crate::int::bezier::anchor::AnchorKind;
+
+/// A piece of a fitted path. Missing control points mean a straight segment.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FitCurve {
+    pub(crate) a: Point,
+    pub(crate) am: Option<Point>,
+    pub(crate) bm: Option<Point>,
+    pub(crate) b: Point,
+}
+
+/// Least-squares Bézier fitter with recursive splitting on error (Schneider's algorithm).
+pub(crate) struct CurveFitter {
+    tolerance: f64,
+    sqr_tolerance: f64,
+    max_iteration_error: f64,
+    max_iterations: usize,
+}
+
+impl CurveFitter {
+    #[inline]
+    pub(crate) fn new(tolerance: f64) -> Self {
+        let tolerance = tolerance.max(f64::EPSILON);
+        Self {
+            tolerance,
+            sqr_tolerance: tolerance * tolerance,
+            max_iteration_error: 64.0 * tolerance * tolerance,
+            max_iterations: 8,
+        }
+    }
+
+    /// Fits a point sequence into anchors. Turns sharper than `corner_angle` (radians) become corners.
+    /// Returns the anchors and whether the sequence was detected as closed.
+    pub(crate) fn fit(&self, points: &[Point], corner_angle: f64) -> (Vec<BezierAnchor>, bool) {
+        let mut points = Self::dedup(points);
+        let closed = points.len() > 3
+            && points.first().unwrap().distance(*points.last().unwrap()) <= self.tolerance;
+        if closed {
+            points.pop();
+        }
+
+        if points.len() < 2 {
+            let anchors = points.iter().map(|&p| Self::anchor(p, AnchorKind::Corner)).collect();
+            return (anchors, false);
+        }
+
+        let min_cos = corner_angle.cos();
+        let mut corners = self.corners(&points, closed, min_cos);
+
+        let mut anchors = Vec::new();
+        if closed {
+            if corners.is_empty() {
+                // a smooth loop, start it at a smooth anchor
+                let n = points.len();
+                let tangent = (points[1] - points[n - 1]).normalized();
+                let mut run = points.clone();
+                run.push(points[0]);
+                let mut curves = Vec::new();
+                self.fit_run(&run, Some(tangent), Some(tangent * -1.0), &mut curves);
+                Self::append(&mut anchors, &curves, AnchorKind::Smooth);
+                Self::close(&mut anchors, AnchorKind::Smooth);
+                return (anchors, true);
+            }
+
+            points.rotate_left(corners[0]);
+            let shift = corners[0];
+            let n = points.len();
+            for c in corners.iter_mut() {
+                *c = (*c + n - shift) % n;
+            }
+            corners.push(n);
+            points.push(points[0]);
+        } else {
+            corners.insert(0, 0);
+            corners.push(points.len() - 1);
+        }
+
+        for w in corners.windows(2) {
+            let run = &points[w[0]..=w[1]];
+            let mut curves = Vec::new();
+            self.fit_run(run, None, None, &mut curves);
+            Self::append(&mut anchors, &curves, AnchorKind::Corner);
+        }
+
+        if closed {
+            Self::close(&mut anchors, AnchorKind::Corner);
+        }
+
+        (anchors, closed)
+    }
+
+    /// Fits one run without corners. `t0` points from the start into the curve, `t1` from the end into the curve,
+    /// a missing tangent leaves the control point free.
+    pub(crate) fn fit_run(&self, points: &[Point], t0: Option<Point>, t1: Option<Point>, result: &mut Vec<FitCurve>) {
+        let n = points.len();
+        let a = points[0];
+        let b = points[n - 1];
+
+        if n == 2 || self.is_straight(points) {
+            result.push(FitCurve { a, am: None, bm: None, b });
+            return;
+        }
+
+        let (curve, error, split) = self.fit_curve(points, t0, t1);
+        if error <= self.sqr_tolerance {
+            result.push(FitCurve { a, am: Some(curve[1]), bm: Some(curve[2]), b });
+            return;
+        }
+
+        let split = split.clamp(1, n - 2);
+        let center = points[split - 1] - points[split + 1];
+        let center = if center.sqr_length() > 0.0 {
+            center.normalized()
+        } else {
+            (points[split] - points[split + 1]).normalized()
+        };
+
+        self.fit_run(&points[..=split], t0, Some(center), result);
+        self.fit_run(&points[split..], Some(center * -1.0), t1, result);
+    }
+
+    /// Fits the points with a single cubic. Returns the control points,
+    /// the max squared error and the index of the worst point.
+    pub(crate) fn fit_curve(&self, points: &[Point], t0: Option<Point>, t1: Option<Point>) -> ([Point; 4], f64, usize) {
+        let mut u = Self::chord_parameters(points);
+        let mut curve = Self::generate(points, &u, t0, t1);
+        let (mut error, mut split) = Self::max_error(points, &curve, &u);
+
+        if error > self.sqr_tolerance && error < self.max_iteration_error {
+            for _ in 0..self.max_iterations {
+                Self::reparameterize(points, &curve, &mut u);
+                curve = Self::generate(points, &u, t0, t1);
+                (error, split) = Self::max_error(points, &curve, &u);
+                if error <= self.sqr_tolerance {
+                    break;
+                }
+            }
+        }
+
+        (curve, error, split)
+    }
+
+    fn dedup(points: &[Point]) -> Vec<Point> {
+        let mut result: Vec<Point> = Vec::with_capacity(points.len());
+        for &p in points.iter() {
+            if result.last().is_some_and(|last| last.x == p.x && last.y == p.y) {
+                continue;
+            }
+            result.push(p);
+        }
+        result
+    }
+
+    #[inline]
+    fn anchor(point: Point, kind: AnchorKind) -> BezierAnchor {
+        BezierAnchor { point, handle_in: None, handle_out: None, kind }
+    }
+
+    fn append(anchors: &mut Vec<BezierAnchor>, curves: &[FitCurve], end_kind: AnchorKind) {
+        if anchors.is_empty() {
+            anchors.push(Self::anchor(curves[0].a, AnchorKind::Corner));
+        }
+        for (i, c) in curves.iter().enumerate() {
+            let last = anchors.last_mut().unwrap();
+            last.handle_out = c.am.map(|m| (m - c.a).into());
+
+            let kind = if i + 1 == curves.len() { end_kind } else { AnchorKind::Smooth };
+            let mut anchor = Self::anchor(c.b, kind);
+            anchor.handle_in = c.bm.map(|m| (m - c.b).into());
+            anchors.push(anchor);
+        }
+    }
+
+    fn close(anchors: &mut Vec<BezierAnchor>, kind: AnchorKind) {
+        let last = anchors.pop().unwrap();
+        let first = &mut anchors[0];
+        first.handle_in = last.handle_in;
+        first.kind = kind;
+    }
+
+    fn corners(&self, points: &[Point], closed: bool, min_cos: f64) -> Vec<usize> {
+        let n = points.len();
+        let range = if closed { 0..n } else { 1..n - 1 };
+
+        let mut corners = Vec::new();
+        let mut group: Option<(usize, f64)> = None;
+        for i in range {
+            let cos = match (self.direction(points, i, closed, false), self.direction(points, i, closed, true)) {
+                (Some(back), Some(fwd)) => -back.dot_product(&fwd),
+                _ => 1.0,
+            };
+
+            if cos < min_cos {
+                // suppress neighbours of the same corner, keep the sharpest one
+                group = match group {
+                    Some((j, best)) if points[j].distance(points[i]) <= self.tolerance => {
+                        if cos < best { Some((i, cos)) } else { Some((j, best)) }
+                    }
+                    Some((j, _)) => {
+                        corners.push(j);
+                        Some((i, cos))
+                    }
+                    None => Some((i, cos)),
+                };
+            }
+        }
+
+        if let Some((j, _)) = group {
+            corners.push(j);
+        }
+
+        corners
+    }
+
+    /// Direction from `points[i]` to the first point farther than the tolerance.
+    fn direction(&self, points: &[Point], i: usize, closed: bool, forward: bool) -> Option<Point> {
+        let n = points.len();
+        let p = points[i];
+        let mut last = None;
+        for k in 1..n {
+            let j = if forward {
+                if !closed && i + k >= n { break; }
+                (i + k) % n
+            } else {
+                if !closed && k > i { break; }
+                (i + n - k) % n
+            };
+            let q = points[j];
+            last = Some(q);
+            if p.distance(q) > self.tolerance {
+                break;
+            }
+        }
+
+        let d = last? - p;
+        if d.sqr_length() > 0.0 {
+            Some(d.normalized())
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn is_straight(&self, points: &[Point]) -> bool {
+        let a = points[0];
+        let ab = points[points.len() - 1] - a;
+        let len = ab.length();
+        if len == 0.0 {
+            return false;
+        }
+        let dir = ab * (1.0 / len);
+        points.iter().all(|&p| {
+            let ap = p - a;
+            let t = ap.dot_product(&dir);
+            (0.0..=len).contains(&t) && ap.cross_product(&dir).abs() <= self.tolerance
+        })
+    }
+
+    fn chord_parameters(points: &[Point]) -> Vec<f64> {
+        let mut u = Vec::with_capacity(points.len());
+        u.push(0.0);
+        let mut len = 0.0;
+        for w in points.windows(2) {
+            len += w[0].distance(w[1]);
+            u.push(len);
+        }
+        let inv_len = 1.0 / len;
+        for t in u.iter_mut() {
+            *t *= inv_len;
+        }
+        u
+    }
+
+    fn generate(points: &[Point], u: &[f64], t0: Option<Point>, t1: Option<Point>) -> [Point; 4] {
+        let a = points[0];
+        let b = points[points.len() - 1];
+
+        // a fixed tangent gives one unknown (handle length), a free control point gives two (x, y)
+        let mut unknowns = Vec::with_capacity(4);
+        for (t, index) in [(t0, 1), (t1, 2)] {
+            match t {
+                Some(dir) => unknowns.push(Unknown::Along(dir, index)),
+                None => {
+                    unknowns.push(Unknown::X(index));
+                    unknowns.push(Unknown::Y(index));
+                }
+            }
+        }
+
+        let k = unknowns.len();
+        let mut m = [[0.0; 5]; 4];
+        let mut c = [Point::new(0.0, 0.0); 4];
+        for (&p, &t) in points.iter().zip(u.iter()) {
+            let bs = bernstein(t);
+            for (cj, unknown) in c.iter_mut().zip(unknowns.iter()) {
+                *cj = unknown.column(&bs);
+            }
+            let r = p - (a * (bs[0] + bs[1]) + b * (bs[2] + bs[3]));
+            for j in 0..k {
+                for i in 0..k {
+                    m[j][i] += c[j].dot_product(&c[i]);
+                }
+                m[j][k] += c[j].dot_product(&r);
+            }
+        }
+
+        let len = a.distance(b);
+        let fallback = |t: Option<Point>, origin: Point, target: Point| {
+            let dir = t.unwrap_or_else(|| (target - origin).normalized());
+            origin + dir * (len / 3.0)
+        };
+
+        let x = if let Some(x) = solve(&mut m, k) { x } else {
+            return [a, fallback(t0, a, b), fallback(t1, b, a), b];
+        };
+
+        let eps = 1.0e-6 * len;
+        let (am, j) = match t0 {
+            Some(t) if x[0] < eps => (fallback(Some(t), a, b), 1),
+            Some(t) => (a + t * x[0], 1),
+            None => (a + Point::new(x[0], x[1]), 2),
+        };
+        let bm = match t1 {
+            Some(t) if x[j] < eps => fallback(Some(t), b, a),
+            Some(t) => b + t * x[j],
+            None => b + Point::new(x[j], x[j + 1]),
+        };
+
+        [a, am, bm, b]
+    }
+
+    fn max_error(points: &[Point], curve: &[Point; 4], u: &[f64]) -> (f64, usize) {
+        let n = points.len();
+        let mut max = 0.0;
+        let mut split = n >> 1;
+        for i in 1..n - 1 {
+            let d = (point_at(curve, u[i]) - points[i]).sqr_length();
+            if d >= max {
+                max = d;
+                split = i;
+            }
+        }
+        (max, split)
+    }
+
+    fn reparameterize(points: &[Point], curve: &[Point; 4], u: &mut [f64]) {
+        let d1 = [
+            (curve[1] - curve[0]) * 3.0,
+            (curve[2] - curve[1]) * 3.0,
+            (curve[3] - curve[2]) * 3.0,
+        ];
+        let d2 = [(d1[1] - d1[0]) * 2.0, (d1[2] - d1[1]) * 2.0];
+
+        for (t, &p) in u.iter_mut().zip(points.iter()) {
+            let q = point_at(curve, *t) - p;
+            let s = 1.0 - *t;
+            let q1 = d1[0] * (s * s) + d1[1] * (2.0 * s * *t) + d1[2] * (*t * *t);
+            let q2 = d2[0] * s + d2[1] * *t;
+            let den = q1.dot_product(&q1) + q.dot_product(&q2);
+            if den.abs() > f64::EPSILON {
+                *t = (*t - q.dot_product(&q1) / den).clamp(0.0, 1.0);
+            }
+        }
+    }
+}
+
+enum Unknown {
+    Along(Point, usize),
+    X(usize),
+    Y(usize),
+}
+
+impl Unknown {
+    #[inline]
+    fn column(&self, bs: &[f64; 4]) -> Point {
+        match *self {
+            Unknown::Along(dir, i) => dir * bs[i],
+            Unknown::X(i) => Point::new(bs[i], 0.0),
+            Unknown::Y(i) => Point::new(0.0, bs[i]),
+        }
+    }
+}
+
+/// Solves a small linear system given as an augmented matrix with Gaussian elimination.
+fn solve(m: &mut [[f64; 5]; 4], k: usize) -> Option<[f64; 4]> {
+    for col in 0..k {
+        let pivot = (col..k).max_by(|&i, &j| m[i][col].abs().total_cmp(&m[j][col].abs()))?;
+        if m[pivot][col].abs() < 1.0e-12 {
+            return None;
+        }
+        m.swap(col, pivot);
+        let pivot_row = m[col];
+        for row in m.iter_mut().take(k).skip(col + 1) {
+            let f = row[col] / pivot_row[col];
+            for (v, p) in row[col..=k].iter_mut().zip(pivot_row[col..=k].iter()) {
+                *v -= f * p;
+            }
+        }
+    }
+
+    let mut x = [0.0; 4];
+    for row in (0..k).rev() {
+        let v: f64 = (row + 1..k).map(|i| m[row][i] * x[i]).sum();
+        x[row] = (m[row][k] - v) / m[row][row];
+    }
+
+    Some(x)
+}
+
+#[inline]
+fn bernstein(t: f64) -> [f64; 4] {
+    let s = 1.0 - t;
+    [s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t]
+}
+
+#[inline]
+pub(crate) fn point_at(curve: &[Point; 4], t: f64) -> Point {
+    let [b0, b1, b2, b3] = bernstein(t);
+    curve[0] * b0 + curve[1] * b1 + curve[2] * b2 + curve[3] * b3
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use crate::float::bezier::fit::point_at;
+    use crate::float::bezier::path::BezierPath;
+    use crate::float::math::point::Point;
+    use crate::int::bezier::anchor::AnchorKind;
+
+    #[test]
+    fn test_00() {
+        let curve = [
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 50.0),
+            Point::new(100.0, 50.0),
+            Point::new(100.0, 0.0),
+        ];
+        let points: Vec<_> = (0..=32).map(|i| point_at(&curve, i as f64 / 32.0)).collect();
+
+        let path = BezierPath::fit(&points, 0.1, 0.5 * PI);
+
+        assert_eq!(path.anchors.len(), 2);
+        assert!(!path.closed);
+    }
+
+    #[test]
+    fn test_01() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(5.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 5.0),
+            Point::new(10.0, 10.0),
+            Point::new(5.0, 10.0),
+            Point::new(0.0, 10.0),
+            Point::new(0.0, 5.0),
+            Point::new(0.0, 0.0),
+        ];
+
+        let path = BezierPath::fit(&points, 0.1, 0.25 * PI);
+
+        assert_eq!(path.anchors.len(), 4);
+        assert!(path.closed);
+        for anchor in path.anchors.iter() {
+            assert_eq!(anchor.kind, AnchorKind::Corner);
+            assert!(anchor.handle_in.is_none());
+            assert!(anchor.handle_out.is_none());
+        }
+    }
+
+    #[test]
+    fn test_02() {
+        let r = 100.0;
+        let points: Vec<_> = (0..=128).map(|i| {
+            let a = 2.0 * PI * i as f64 / 128.0;
+            let noise = if i % 2 == 0 { 0.05 } else { -0.05 };
+            Point::new((r + noise) * a.cos(), (r + noise) * a.sin())
+        }).collect();
+
+        let path = BezierPath::fit(&points, 0.5, 0.25 * PI);
+
+        assert!(path.closed);
+        assert!(path.anchors.len() <= 8);
+        for p in path.regular_points(4) {
+            assert!((p.length() - r).abs() < 1.0);
+        }
+    }
+}
@@ -4,6 +4,7 @@ pub(crate) mod spline_cube;
 pub(crate) mod spline_line;
 pub(crate) mod spline_quad;
 pub(crate) mod spline;
-pub(crate) mod iter;
-pub(crate) mod length;
 pub(crate) mod approximation;
+pub(crate) mod fit;
+pub(crate) mod simplify;
+pub(crate) mod svg;
@@ -1,20 +1,129 @@
 use serde::{Deserialize, Serialize};
 use crate::convert::grid::Grid;
-use crate::convert::to_int::ToInt;
+use crate::convert::to_int::{Rounding, ToInt};
+use crate::error::Error;
 use crate::float::bezier::anchor::BezierAnchor;
+use crate::float::bezier::fit::CurveFitter;
+use crate::float::bezier::simplify::PathSimplifier;
 use crate::float::bezier::spline::Spline;
+use crate::float::bezier::svg::{write_path_data, PathDataParser};
+use crate::float::math::number::FloatNumber;
+use crate::float::math::offset::Offset;
 use crate::float::math::point::Point;
+use crate::float::stroke::builder::StrokeBuilder;
+use crate::float::stroke::style::StrokeStyle;
+use crate::int::bezier::anchor::AnchorKind;
 use crate::int::bezier::path::IntBezierPath;
+use crate::int::math::range::RangeError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BezierPath {
-    pub anchors: Vec<BezierAnchor>,
+pub struct BezierPath<T = f64> {
+    pub anchors: Vec<BezierAnchor<T>>,
     pub closed: bool,
 }
 
-impl BezierPath {
+impl BezierPath<f64> {
+    /// Fits a (possibly noisy) polyline with a minimal set of anchors.
+    /// Every point stays within `tolerance` of the result, turns sharper than
+    /// `corner_angle` (in radians) become corner anchors. A polyline whose end meets its start
+    /// becomes a closed path.
+    pub fn fit(points: &[Point], tolerance: f64, corner_angle: f64) -> Self {
+        let (anchors, closed) = CurveFitter::new(tolerance).fit(points, corner_angle);
+        Self { anchors, closed }
+    }
+
+    /// Reduces the anchor count by merging consecutive splines into one curve while
+    /// the Hausdorff distance to the original stays within `tolerance`.
+    pub fn simplify(&self, tolerance: f64) -> Self {
+        PathSimplifier::new(tolerance).simplify(self)
+    }
+
+    /// Outlines the stroke of the path as closed contours fitted within `tolerance`.
+    /// An open path gives one contour, a closed path gives the contours on both of its sides.
+    pub fn stroke(&self, style: &StrokeStyle, tolerance: f64) -> Vec<BezierPath> {
+        StrokeBuilder::new(*style, tolerance).build(self)
+    }
+
+    /// `fit` that fails with `Error::EmptyPath` for no points, `NotFinite` for a point, or `InvalidParameter`
+    /// for a `tolerance` that is not positive or a `corner_angle` that is not finite.
+    pub fn try_fit(points: &[Point], tolerance: f64, corner_angle: f64) -> Result<Self, Error> {
+        if points.is_empty() {
+            return Err(Error::EmptyPath);
+        }
+        if !points.iter().all(|p| p.x.is_finite() && p.y.is_finite()) {
+            return Err(Error::NotFinite);
+        }
+        Self::check_tolerance(tolerance)?;
+        if !corner_angle.is_finite() {
+            return Err(Error::InvalidParameter("corner_angle"));
+        }
+        Ok(Self::fit(points, tolerance, corner_angle))
+    }
+
+    /// `simplify` that fails with `Error::EmptyPath`, `NotFinite` for an anchor or handle point, or
+    /// `InvalidParameter` for a `tolerance` that is not positive.
+    pub fn try_simplify(&self, tolerance: f64) -> Result<Self, Error> {
+        self.check_finite()?;
+        Self::check_tolerance(tolerance)?;
+        Ok(self.simplify(tolerance))
+    }
+
+    /// `stroke` with the errors of `try_simplify`, and `InvalidParameter` for a negative `width` or a
+    /// `miter_limit` below 1 as in SVG. A zero width is valid and gives no contours.
+    pub fn try_stroke(&self, style: &StrokeStyle, tolerance: f64) -> Result<Vec<BezierPath>, Error> {
+        self.check_finite()?;
+        Self::check_tolerance(tolerance)?;
+        if !style.width.is_finite() || style.width < 0.0 {
+            return Err(Error::InvalidParameter("width"));
+        }
+        if !style.miter_limit.is_finite() || style.miter_limit < 1.0 {
+            return Err(Error::InvalidParameter("miter_limit"));
+        }
+        Ok(self.stroke(style, tolerance))
+    }
+
+    fn check_finite(&self) -> Result<(), Error> {
+        if self.anchors.is_empty() {
+            return Err(Error::EmptyPath);
+        }
+        let is_finite = |p: Point| p.x.is_finite() && p.y.is_finite();
+        for a in self.anchors.iter() {
+            if ![Some(a.point), a.handle_in_point(), a.handle_out_point()].into_iter().flatten().all(is_finite) {
+                return Err(Error::NotFinite);
+            }
+        }
+        Ok(())
+    }
+
+    fn check_tolerance(tolerance: f64) -> Result<(), Error> {
+        if !tolerance.is_finite() || tolerance <= 0.0 {
+            return Err(Error::InvalidParameter("tolerance"));
+        }
+        Ok(())
+    }
+
+    /// Reads the subpaths of SVG path data (the `d` attribute), quadratic curves are elevated to cubic ones.
+    /// Returns `None` for malformed data and for the unsupported arc command.
+    pub fn from_svg_path(data: &str) -> Option<Vec<BezierPath>> {
+        PathDataParser::new(data).parse()
+    }
+
+    /// Writes the paths as one SVG path data string.
+    pub fn to_svg_path(paths: &[BezierPath]) -> String {
+        let mut data = String::new();
+        for path in paths.iter() {
+            if !data.is_empty() {
+                data.push(' ');
+            }
+            write_path_data(path, &mut data);
+        }
+        data
+    }
+}
+
+impl<T: FloatNumber> BezierPath<T> {
     #[inline]
-    pub fn regular_points(&self, split_factor: u32) -> Vec<Point> {
+    pub fn regular_points(&self, split_factor: u32) -> Vec<Point<T>> {
         let capacity = self.anchors.len() << split_factor;
         let mut points = Vec::with_capacity(capacity);
         for spline in self.splines() {
@@ -25,25 +134,200 @@ impl BezierPath {
     }
 
     #[inline]
-    pub(crate) fn splines(&self) -> impl Iterator<Item = Spline> + '_ {
+    pub fn approximate_points(&self, min_cos: T, min_len: T) -> Vec<Point<T>> {
+        let capacity = self.anchors.len() * 16;
+        let mut points = Vec::with_capacity(capacity);
+        for spline in self.splines() {
+            points.append(&mut spline.approximate_points(min_cos, min_len));
+        }
+
+        points
+    }
+
+    #[inline]
+    pub fn avg_length(&self, min_cos: T, min_len: T) -> f64 {
+        let mut len = 0.0;
+        for spline in self.splines() {
+            len += spline.avg_length(min_cos, min_len);
+        }
+
+        len
+    }
+
+    /// The bounding box `(min, max)` of the anchor and handle points, the curve stays inside of it.
+    /// `None` for a path without anchors.
+    pub fn control_bounds(&self) -> Option<(Point<T>, Point<T>)> {
+        let first = self.anchors.first()?.point;
+        let points = self.anchors.iter()
+            .flat_map(|a| [Some(a.point), a.handle_in_point(), a.handle_out_point()])
+            .flatten();
+        let bounds = points.fold((first, first), |(min, max), p| (
+            Point::new(min.x.min(p.x), min.y.min(p.y)),
+            Point::new(max.x.max(p.x), max.y.max(p.y)),
+        ));
+
+        Some(bounds)
+    }
+
+    /// Returns the path where every spline has two control points. The elevation is exact.
+    pub fn to_cubic(&self) -> Self {
+        let mut path = self.clone();
+        for (i, spline) in self.splines().enumerate() {
+            if let Spline::Quad(_) = spline {
+                continue;
+            }
+            path.set_spline(i, &Spline::Quad(spline.elevated()));
+        }
+
+        path
+    }
+
+    /// Returns the path without handles shorter than `tolerance`, and with the neighbour anchors
+    /// closer than `tolerance` that have no handles towards each other merged into the first one.
+    /// The merged anchor keeps the incoming handle of the first and the outgoing handle of the second.
+    pub fn canonicalized(&self, tolerance: T) -> Self {
+        let is_long = |h: &Offset<T>| h.length() > tolerance;
+        let mut anchors: Vec<BezierAnchor<T>> = Vec::with_capacity(self.anchors.len());
+        for anchor in self.anchors.iter() {
+            let mut anchor = *anchor;
+            anchor.handle_in = anchor.handle_in.filter(is_long);
+            anchor.handle_out = anchor.handle_out.filter(is_long);
+            if let Some(last) = anchors.last_mut()
+                && last.merge(&anchor, tolerance)
+            {
+                continue;
+            }
+            anchors.push(anchor);
+        }
+
+        if self.closed && anchors.len() > 1 {
+            let first = anchors[0];
+            let mut last = anchors[anchors.len() - 1];
+            if last.merge(&first, tolerance) {
+                anchors[0] = last;
+                anchors.pop();
+            }
+        }
+
+        Self { anchors, closed: self.closed }
+    }
+
+    /// Replaces every spline with the lowest degree one that stays within `tolerance`.
+    /// Returns the new path and the max error estimate.
+    pub fn reduced(&self, tolerance: T) -> (Self, T) {
+        let mut path = self.clone();
+        let mut max_error = T::ZERO;
+        for (i, spline) in self.splines().enumerate() {
+            let (reduced, error) = spline.reduced(tolerance);
+            if std::mem::discriminant(&reduced) != std::mem::discriminant(&spline) {
+                path.set_spline(i, &reduced);
+                max_error = max_error.max(error);
+            }
+        }
+
+        (path, max_error)
+    }
+
+    /// Writes the spline that starts at anchor `index` into the anchor handles.
+    fn set_spline(&mut self, index: usize, spline: &Spline<T>) {
+        let next = (index + 1) % self.anchors.len();
+        let (handle_out, handle_in) = match spline {
+            Spline::Line(_) => (None, None),
+            Spline::Cube(s) => (Some(s.m - s.a), None),
+            Spline::Quad(s) => (Some(s.am - s.a), Some(s.bm - s.b)),
+        };
+
+        let a = &mut self.anchors[index];
+        a.handle_out = handle_out.map(|h| h.into());
+        a.kind = a.kind.released();
+
+        let b = &mut self.anchors[next];
+        b.handle_in = handle_in.map(|h| h.into());
+        b.kind = b.kind.released();
+    }
+
+    /// Marks every anchor as `Auto` and computes its handles from the anchor points alone.
+    pub fn auto_smooth(&mut self) {
+        for anchor in self.anchors.iter_mut() {
+            anchor.kind = AnchorKind::Auto;
+        }
+        self.update_auto_handles();
+    }
+
+    /// Recomputes the handles of all `Auto` anchors with Catmull-Rom tangents.
+    /// Must be called after any anchor point of the path is moved.
+    pub fn update_auto_handles(&mut self) {
+        let n = self.anchors.len();
+        if n < 2 {
+            return;
+        }
+
+        for i in 0..n {
+            if self.anchors[i].kind != AnchorKind::Auto {
+                continue;
+            }
+
+            let is_first = i == 0 && !self.closed;
+            let is_last = i + 1 == n && !self.closed;
+
+            let prev = if i > 0 { i - 1 } else if self.closed { n - 1 } else { i };
+            let next = if i + 1 < n { i + 1 } else if self.closed { 0 } else { i };
+
+            let tangent = self.anchors[next].point - self.anchors[prev].point;
+            let k = T::from_f64(1.0 / 6.0);
+            let handle = Offset::new(tangent.x * k, tangent.y * k);
+
+            let anchor = &mut self.anchors[i];
+            if handle.length() == T::ZERO {
+                anchor.handle_in = None;
+                anchor.handle_out = None;
+            } else {
+                anchor.handle_in = if is_first { None } else { Some(-handle) };
+                anchor.handle_out = if is_last { None } else { Some(handle) };
+            }
+        }
+    }
+
+    /// `to_int` for a path that is not known to fit the int range of the grid, see `Grid::max_float_value`.
+    /// Fails for an anchor or handle point out of range or a coordinate that is not finite.
+    pub fn try_to_int(&self, grid: &Grid) -> Result<IntBezierPath, RangeError> {
+        let max = grid.max_float_value();
+        // false for NaN as well
+        let is_in_range = |p: Point<T>| p.x.to_f64().abs() <= max && p.y.to_f64().abs() <= max;
+        for (anchor_index, a) in self.anchors.iter().enumerate() {
+            let points = [Some(a.point), a.handle_in_point(), a.handle_out_point()];
+            if !points.into_iter().flatten().all(is_in_range) {
+                return Err(RangeError { anchor_index });
+            }
+        }
+
+        // the snapping can still push a value just out of range
+        let path = self.to_int(grid);
+        path.validate()?;
+
+        Ok(path)
+    }
+
+    #[inline]
+    pub(crate) fn splines(&self) -> impl Iterator<Item = Spline<T>> + '_ {
         SplineIterator::new(self)
     }
 }
 
-pub(crate) struct SplineIterator<'a> {
-    path: &'a BezierPath,
+pub(crate) struct SplineIterator<'a, T> {
+    path: &'a BezierPath<T>,
     i: usize,
 }
 
-impl<'a> SplineIterator<'a> {
+impl<'a, T> SplineIterator<'a, T> {
     #[inline]
-    fn new(path: &'a BezierPath) -> Self {
+    fn new(path: &'a BezierPath<T>) -> Self {
         Self { i: 1, path }
     }
 }
 
-impl<'a> Iterator for SplineIterator<'a> {
-    type Item = Spline;
+impl<'a, T: FloatNumber> Iterator for SplineIterator<'a, T> {
+    type Item = Spline<T>;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
@@ -54,8 +338,8 @@ impl<'a> Iterator for SplineIterator<'a> {
         if self.i == self.path.anchors.len() {
             self.i += 1;
             return if self.path.closed {
-                let first = self.path.anchors.first().unwrap();
-                let last = self.path.anchors.last().unwrap();
+                let first = self.path.anchors.first()?;
+                let last = self.path.anchors.last()?;
                 Some(Spline::new(last, first))
             } else {
                 None
@@ -71,12 +355,158 @@ impl<'a> Iterator for SplineIterator<'a> {
     }
 }
 
-impl ToInt<IntBezierPath> for BezierPath {
+impl<T: FloatNumber> ToInt<IntBezierPath> for BezierPath<T> {
     #[inline]
-    fn to_int(&self, grid: &Grid) -> IntBezierPath {
-        IntBezierPath {
-            anchors: self.anchors.iter().map(|a|a.to_int(grid)).collect(),
-            closed: self.closed,
+    fn to_int_rounded(&self, grid: &Grid, rounding: Rounding) -> (IntBezierPath, f64) {
+        let mut max_error = 0f64;
+        let anchors = self.anchors.iter().map(|a| {
+            let (anchor, error) = a.to_int_rounded(grid, rounding);
+            max_error = max_error.max(error);
+            anchor
+        }).collect();
+
+        (IntBezierPath { anchors, closed: self.closed }, max_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::convert::grid::Grid;
+    use crate::convert::to_float::ToFloat;
+    use crate::convert::to_int::{Rounding, ToInt};
+    use crate::error::Error;
+    use crate::float::bezier::anchor::BezierAnchor;
+    use crate::float::bezier::path::BezierPath;
+    use crate::float::math::offset::Offset;
+    use crate::float::math::point::Point;
+    use crate::float::stroke::style::StrokeStyle;
+    use crate::int::bezier::anchor::AnchorKind;
+    use crate::int::math::range::{RangeError, MAX_COORD};
+
+    #[test]
+    fn test_00() {
+        let anchor = |x: f64, handle_in: Option<Offset>, handle_out: Option<Offset>| BezierAnchor {
+            point: Point::new(x, 0.0),
+            handle_in,
+            handle_out,
+            kind: AnchorKind::Corner,
+        };
+        let up = Some(Offset::new(0.0, 5.0));
+
+        // the corner anchors are written as in the test files, without a kind
+        let mut path = BezierPath { anchors: vec![anchor(0.0, None, up), anchor(10.0, up, None)], closed: false };
+        let json = serde_json::to_string(&path).unwrap();
+        assert!(!json.contains("kind"));
+        let back: BezierPath = serde_json::from_str(&json).unwrap();
+        assert!(back.anchors.iter().all(|a| a.kind == AnchorKind::Corner));
+
+        path.anchors[1].kind = AnchorKind::Smooth;
+        let json = serde_json::to_string(&path).unwrap();
+        assert_eq!(json.matches("\"kind\":\"Smooth\"").count(), 1);
+        let back: BezierPath = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.anchors[1].kind, AnchorKind::Smooth);
+    }
+
+    #[test]
+    fn test_01() {
+        let grid = Grid::default();
+        let max = grid.max_float_value();
+        let data = format!("M{} 0 C 0 {} 0 {} {} 0", -max, max, max, max);
+        let mut path = BezierPath::from_svg_path(&data).unwrap().remove(0);
+
+        let int_path = path.try_to_int(&grid).unwrap();
+        assert_eq!(int_path.anchors[1].point.x, MAX_COORD);
+
+        path.anchors[1].handle_in = path.anchors[1].handle_in.map(|mut h| {
+            h.x = 1.0;
+            h
+        });
+        assert_eq!(path.try_to_int(&grid).unwrap_err(), RangeError { anchor_index: 1 });
+
+        path.anchors[0].point.y = f64::NAN;
+        assert_eq!(path.try_to_int(&grid).unwrap_err(), RangeError { anchor_index: 0 });
+    }
+
+    #[test]
+    fn test_02() {
+        let points: Vec<Point> = (0..=10).map(|i| Point::new(10.0 * i as f64, 0.0)).collect();
+        assert_eq!(BezierPath::try_fit(&[], 0.5, 1.0).unwrap_err(), Error::EmptyPath);
+        assert_eq!(BezierPath::try_fit(&points, 0.0, 1.0).unwrap_err(), Error::InvalidParameter("tolerance"));
+        assert_eq!(BezierPath::try_fit(&points, 0.5, f64::NAN).unwrap_err(), Error::InvalidParameter("corner_angle"));
+
+        let mut noisy = points.clone();
+        noisy[3].y = f64::INFINITY;
+        assert_eq!(BezierPath::try_fit(&noisy, 0.5, 1.0).unwrap_err(), Error::NotFinite);
+
+        let mut path = BezierPath::try_fit(&points, 0.5, 1.0).unwrap();
+        assert!(path.try_simplify(0.5).is_ok());
+        assert_eq!(path.try_simplify(f64::INFINITY).unwrap_err(), Error::InvalidParameter("tolerance"));
+
+        let style = StrokeStyle::new(4.0);
+        assert_eq!(path.try_stroke(&style, 0.1).unwrap().len(), 1);
+        assert_eq!(path.try_stroke(&StrokeStyle::new(-1.0), 0.1).unwrap_err(), Error::InvalidParameter("width"));
+        let style = StrokeStyle { miter_limit: 0.5, ..style };
+        assert_eq!(path.try_stroke(&style, 0.1).unwrap_err(), Error::InvalidParameter("miter_limit"));
+
+        path.anchors[0].handle_out = Some(Offset::new(f64::NAN, 0.0));
+        assert_eq!(path.try_simplify(0.5).unwrap_err(), Error::NotFinite);
+        let empty = BezierPath { anchors: vec![], closed: false };
+        assert_eq!(empty.try_stroke(&StrokeStyle::new(4.0), 0.1).unwrap_err(), Error::EmptyPath);
+    }
+
+    #[test]
+    fn test_03() {
+        let anchor = |x: f64, handle_out: Option<Offset>| BezierAnchor {
+            point: Point::new(x, 0.0),
+            handle_in: None,
+            handle_out,
+            kind: AnchorKind::Corner,
+        };
+        let path = BezierPath {
+            anchors: vec![
+                anchor(0.0, Some(Offset::new(0.0, 1e-9))),
+                anchor(1e-9, Some(Offset::new(0.0, 10.0))),
+                anchor(10.0, None),
+            ],
+            closed: false,
+        };
+
+        let path = path.canonicalized(1e-6);
+        assert_eq!(path.anchors.len(), 2);
+        assert_eq!(path.anchors[0].point, Point::new(0.0, 0.0));
+        assert_eq!(path.anchors[0].handle_out, Some(Offset::new(0.0, 10.0)));
+
+        // the approximation works on the degenerate path as well
+        let degenerate = BezierPath { anchors: vec![anchor(0.0, Some(Offset::new(0.0, 0.0))), anchor(10.0, None)], closed: false };
+        for spline in degenerate.splines() {
+            let points = spline.approximate_points(0.99, 1.0);
+            assert!(points.iter().all(|p| p.x.is_finite() && p.y.is_finite()));
+            assert!((spline.avg_length(0.99, 1.0) - 10.0).abs() < 1e-9);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_04() {
+        let path = BezierPath::from_svg_path("M0.1 0.2 C 3.14159 -2.5 7.77 9.001 10.3 4.06 Z").unwrap().remove(0);
+        let grid = Grid::debug();
+        let cell = grid.int_to_float(grid.cell_size());
+
+        for rounding in [Rounding::Snap, Rounding::Nearest, Rounding::Floor] {
+            let (int_path, max_error) = path.to_int_rounded(&grid, rounding);
+            assert!(max_error <= cell);
+
+            // every coordinate comes back within the reported error
+            let back: BezierPath = int_path.to_float(&grid);
+            for (a, b) in path.anchors.iter().zip(back.anchors.iter()) {
+                assert!(a.point.distance(b.point) <= max_error * 2f64.sqrt());
+                for (ha, hb) in [(a.handle_in, b.handle_in), (a.handle_out, b.handle_out)] {
+                    let (ha, hb) = (ha.unwrap_or(Offset::new(0.0, 0.0)), hb.unwrap_or(Offset::new(0.0, 0.0)));
+                    assert!((ha.x - hb.x).abs() <= max_error && (ha.y - hb.y).abs() <= max_error);
+                }
+            }
+        }
+
+        assert_eq!(path.to_int_rounded(&grid, Rounding::Snap).0.anchors[1].point, path.to_int(&grid).anchors[1].point);
+        assert!(path.to_int_rounded(&grid, Rounding::Nearest).1 <= 0.5 * grid.int_to_float(1));
+    }
+}
@@ -0,0 +1,290 @@
+use crate::float::bezier::fit::{point_at, CurveFitter, FitCurve};
+use crate::float::bezier::path::BezierPath;
+use crate::float::bezier::spline::Spline;
+use crate::float::math::distance::hausdorff_distance;
+use crate::float::math::point::Point;
+
+// a turn of less than about 1 degree is smooth
+const SMOOTH_COS: f64 = 0.9998;
+
+/// Merges runs of consecutive splines into single curves while the Hausdorff distance
+/// to the original run stays within the tolerance.
+pub(crate) struct PathSimplifier {
+    fitter: CurveFitter,
+    tolerance: f64,
+    split_factor: u32,
+}
+
+impl PathSimplifier {
+    #[inline]
+    pub(crate) fn new(tolerance: f64) -> Self {
+        Self {
+            fitter: CurveFitter::new(tolerance),
+            tolerance,
+            split_factor: 4,
+        }
+    }
+
+    pub(crate) fn simplify(&self, path: &BezierPath) -> BezierPath {
+        let n = path.anchors.len();
+        if n < 3 {
+            return path.clone();
+        }
+        if !path.closed {
+            return self.merge_runs(path);
+        }
+
+        // merges never cross the first anchor, so a closed path starts at a corner if it has one
+        let (corner, cos) = Self::sharpest_turn(path);
+        if cos < SMOOTH_COS {
+            return self.merge_runs(&Self::rotated(path, corner));
+        }
+
+        // a smooth outline has no corner to start at, the second pass starts where the first run
+        // ended and lets the first anchor be merged away when that costs no extra anchor
+        let result = self.merge_runs(path);
+        let splines: Vec<_> = path.splines().collect();
+        let next = self.run(&splines, 0).map_or(1, |(last, _)| last + 1);
+        if next == n {
+            return result;
+        }
+        let other = self.merge_runs(&Self::rotated(path, next));
+        if other.anchors.len() <= result.anchors.len() {
+            other
+        } else {
+            result
+        }
+    }
+
+    fn merge_runs(&self, path: &BezierPath) -> BezierPath {
+        let n = path.anchors.len();
+        let splines: Vec<_> = path.splines().collect();
+        let m = splines.len();
+
+        let mut anchors = vec![path.anchors[0]];
+        let mut i = 0;
+        while i < m {
+            let (last, curve) = if let Some(merged) = self.run(&splines, i) {
+                merged
+            } else {
+                // nothing to merge, keep the spline as is
+                if i + 1 < n {
+                    anchors.push(path.anchors[i + 1]);
+                }
+                i += 1;
+                continue;
+            };
+
+            let prev = anchors.last_mut().unwrap();
+            prev.handle_out = curve.am.map(|p| (p - curve.a).into());
+            prev.kind = prev.kind.released();
+
+            let handle_in = curve.bm.map(|p| (p - curve.b).into());
+            if last + 1 < n {
+                let mut next = path.anchors[last + 1];
+                next.handle_in = handle_in;
+                next.kind = next.kind.released();
+                anchors.push(next);
+            } else {
+                let first = &mut anchors[0];
+                first.handle_in = handle_in;
+                first.kind = first.kind.released();
+            }
+
+            i = last + 1;
+        }
+
+        BezierPath { anchors, closed: path.closed }
+    }
+
+    /// The longest run of splines from `i` that merges into one curve, with the index of its last spline.
+    fn run(&self, splines: &[Spline], i: usize) -> Option<(usize, FitCurve)> {
+        let mut merged = None;
+        let mut j = i + 1;
+        while j < splines.len() {
+            if let Some(curve) = self.merge(&splines[i..=j]) {
+                merged = Some((j, curve));
+                j += 1;
+            } else {
+                break;
+            }
+        }
+
+        merged
+    }
+
+    /// The anchor of a closed path where the direction turns the most, with the cosine of the turn.
+    fn sharpest_turn(path: &BezierPath) -> (usize, f64) {
+        let splines: Vec<_> = path.splines().collect();
+        let m = splines.len();
+        let mut sharpest = (0, f64::MAX);
+        for (k, spline) in splines.iter().enumerate() {
+            let back = splines[(k + m - 1) % m].end_dir();
+            let fwd = spline.start_dir();
+            // a degenerate spline has no direction and does not make a corner
+            let cos = if back.sqr_length() > 0.0 && fwd.sqr_length() > 0.0 {
+                back.dot_product(&fwd)
+            } else {
+                1.0
+            };
+            if cos < sharpest.1 {
+                sharpest = (k, cos);
+            }
+        }
+
+        sharpest
+    }
+
+    #[inline]
+    fn rotated(path: &BezierPath, start: usize) -> BezierPath {
+        let mut anchors = path.anchors.clone();
+        anchors.rotate_left(start);
+        BezierPath { anchors, closed: path.closed }
+    }
+
+    fn merge(&self, splines: &[Spline]) -> Option<FitCurve> {
+        let mut points = Vec::with_capacity(splines.len() << self.split_factor);
+        for spline in splines.iter() {
+            points.append(&mut spline.regular_points(self.split_factor));
+        }
+        let last = splines.last().unwrap();
+        points.push(last.end());
+
+        let a = points[0];
+        let b = *points.last().unwrap();
+
+        if self.fitter.is_straight(&points) {
+            return Some(FitCurve { a, am: None, bm: None, b });
+        }
+
+        let t0 = Self::valid_dir(splines[0].start_dir());
+        let t1 = Self::valid_dir(last.end_dir() * -1.0);
+        let (curve, _, _) = self.fitter.fit_curve(&points, t0, t1);
+
+        let count = points.len();
+        let scale = 1.0 / (count - 1) as f64;
+        let candidate: Vec<_> = (0..count).map(|k| point_at(&curve, k as f64 * scale)).collect();
+
+        if hausdorff_distance(&points, &candidate) <= self.tolerance {
+            Some(FitCurve { a, am: Some(curve[1]), bm: Some(curve[2]), b })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn valid_dir(dir: Point) -> Option<Point> {
+        if dir.x.is_finite() && dir.y.is_finite() {
+            Some(dir)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::bezier::anchor::BezierAnchor;
+    use crate::float::bezier::path::BezierPath;
+    use crate::float::math::distance::hausdorff_distance;
+    use crate::float::math::offset::Offset;
+    use crate::float::math::point::Point;
+    use crate::int::bezier::anchor::AnchorKind;
+
+    fn corner(x: f64, y: f64) -> BezierAnchor {
+        BezierAnchor { point: Point::new(x, y), handle_in: None, handle_out: None, kind: AnchorKind::Corner }
+    }
+
+    #[test]
+    fn test_00() {
+        let path = BezierPath {
+            anchors: (0..5).map(|i| corner(10.0 * i as f64, 0.0)).collect(),
+            closed: false,
+        };
+
+        let result = path.simplify(0.01);
+
+        assert_eq!(result.anchors.len(), 2);
+        assert_eq!(result.anchors[1].point.x, 40.0);
+    }
+
+    #[test]
+    fn test_01() {
+        let path = BezierPath {
+            anchors: vec![
+                corner(0.0, 0.0),
+                corner(10.0, 0.0),
+                corner(10.0, 10.0),
+                corner(0.0, 10.0),
+            ],
+            closed: true,
+        };
+
+        let result = path.simplify(0.1);
+
+        assert_eq!(result.anchors.len(), 4);
+    }
+
+    /// A circle of `count` arcs, the anchors keep the smooth kind of an import.
+    fn circle(count: usize) -> BezierPath {
+        let r = 100.0;
+        let step = 2.0 * std::f64::consts::PI / count as f64;
+        let k = r * 4.0 / 3.0 * (step / 4.0).tan();
+        let anchors = (0..count).map(|i| {
+            let a = step * i as f64;
+            let (sin, cos) = a.sin_cos();
+            let mut anchor = corner(r * cos, r * sin);
+            anchor.handle_in = Some(Offset::new(k * sin, -k * cos));
+            anchor.handle_out = Some(Offset::new(-k * sin, k * cos));
+            anchor.kind = AnchorKind::Symmetric;
+            anchor
+        }).collect();
+        BezierPath { anchors, closed: true }
+    }
+
+    fn outline(path: &BezierPath) -> Vec<Point> {
+        let mut points = path.regular_points(4);
+        points.push(points[0]);
+        points
+    }
+
+    #[test]
+    fn test_02() {
+        // circle made of 8 arcs
+        let path = circle(8);
+
+        let result = path.simplify(0.1);
+
+        assert!(result.anchors.len() <= 4);
+        assert!(hausdorff_distance(&outline(&path), &outline(&result)) < 0.2);
+    }
+
+    #[test]
+    fn test_03() {
+        // over-segmented circle, two arcs merge into one curve, so the arcs on both sides
+        // of the first anchor merge too rather than leaving a single arc before the seam
+        let path = circle(9);
+
+        let result = path.simplify(0.1);
+
+        assert_eq!(result.anchors.len(), 5);
+        assert!(result.anchors.iter().all(|a| a.point.distance(path.anchors[0].point) > 0.0));
+        assert!(hausdorff_distance(&outline(&path), &outline(&result)) < 0.2);
+    }
+
+    #[test]
+    fn test_04() {
+        // a closed path starts at its corner, the runs no longer stop at the first anchor
+        let mut path = circle(8);
+        path.anchors[4].handle_in = None;
+        path.anchors[4].handle_out = None;
+        path.anchors[4].kind = AnchorKind::Corner;
+        path.anchors[4].point = Point::new(path.anchors[4].point.x * 1.5, path.anchors[4].point.y * 1.5);
+
+        let result = path.simplify(0.1);
+
+        assert!(result.anchors.len() < path.anchors.len());
+        assert!(result.anchors.iter().any(|a| a.point.distance(path.anchors[4].point) == 0.0));
+        assert!(result.anchors.iter().all(|a| a.point.distance(path.anchors[0].point) > 0.0));
+    }
+}
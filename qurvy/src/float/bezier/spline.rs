@@ -1,22 +1,24 @@
 use crate::float::bezier::anchor::BezierAnchor;
-use crate::float::bezier::approximation::Approximation;
-use crate::float::bezier::iter::SplinePointsIter;
+use crate::bezier::approximation::Approximation;
+use crate::bezier::iter::SplinePointsIter;
+use crate::bezier::length::SplineLength;
+use crate::bezier::spline::CADSpline;
 use crate::float::bezier::spline_cube::CubeSpline;
 use crate::float::bezier::spline_line::LineSpline;
 use crate::float::bezier::spline_quad::QuadSpline;
-use crate::float::math::length::SplineLength;
+use crate::float::math::number::FloatNumber;
 use crate::float::math::point::Point;
 
 #[derive(Debug, Clone)]
-pub(crate) enum Spline {
-    Line(LineSpline),
-    Cube(CubeSpline),
-    Quad(QuadSpline),
+pub(crate) enum Spline<T = f64> {
+    Line(LineSpline<T>),
+    Cube(CubeSpline<T>),
+    Quad(QuadSpline<T>),
 }
 
-impl Spline {
+impl<T: FloatNumber> Spline<T> {
     #[inline]
-    pub(super) fn new(a: &BezierAnchor, b: &BezierAnchor) -> Self {
+    pub(super) fn new(a: &BezierAnchor<T>, b: &BezierAnchor<T>) -> Self {
         match (a.handle_out_point(), b.handle_in_point()) {
             (Some(am), Some(bm)) => Spline::Quad(QuadSpline {
                 a: a.point,
@@ -42,7 +44,34 @@ impl Spline {
     }
 
     #[inline]
-    pub fn regular_points(&self, split_factor: u32) -> Vec<Point> {
+    pub fn start_dir(&self) -> Point<T> {
+        match self {
+            Spline::Line(s) => s.start_dir(),
+            Spline::Cube(s) => s.start_dir(),
+            Spline::Quad(s) => s.start_dir(),
+        }
+    }
+
+    #[inline]
+    pub fn end_dir(&self) -> Point<T> {
+        match self {
+            Spline::Line(s) => s.end_dir(),
+            Spline::Cube(s) => s.end_dir(),
+            Spline::Quad(s) => s.end_dir(),
+        }
+    }
+
+    #[inline]
+    pub fn end(&self) -> Point<T> {
+        match self {
+            Spline::Line(s) => s.end(),
+            Spline::Cube(s) => s.end(),
+            Spline::Quad(s) => s.end(),
+        }
+    }
+
+    #[inline]
+    pub fn regular_points(&self, split_factor: u32) -> Vec<Point<T>> {
         match self {
             Spline::Line(s) => s.points_iter(true, false, split_factor).collect(),
             Spline::Cube(s) => s.points_iter(true, false, split_factor).collect(),
@@ -51,7 +80,7 @@ impl Spline {
     }
 
     #[inline]
-    pub fn approximate_points(&self, min_cos: f64, min_len: f64) -> Vec<Point> {
+    pub fn approximate_points(&self, min_cos: T, min_len: T) -> Vec<Point<T>> {
         match self {
             Spline::Line(s) => s.approximate_points(min_cos, min_len),
             Spline::Cube(s) => s.approximate_points(min_cos, min_len),
@@ -60,7 +89,7 @@ impl Spline {
     }
 
     #[inline]
-    pub fn avg_length(&self, min_cos: f64, min_len: f64) -> f64 {
+    pub fn avg_length(&self, min_cos: T, min_len: T) -> f64 {
         match self {
             Spline::Line(s) => s.avg_length(min_cos, min_len),
             Spline::Cube(s) => s.avg_length(min_cos, min_len),
@@ -69,10 +98,136 @@ impl Spline {
     }
 }
 
-pub(crate) trait CADSpline {
-    fn start(&self) -> Point;
-    fn start_dir(&self) -> Point;
-    fn end_dir(&self) -> Point;
-    fn end(&self) -> Point;
-    fn split_at(&self, step: usize, split_factor: u32) -> Point;
-}
\ No newline at end of file
+impl<T: FloatNumber> Spline<T> {
+    /// Exact elevation to the two control points form.
+    #[inline]
+    pub fn elevated(&self) -> QuadSpline<T> {
+        match self {
+            Spline::Line(s) => s.elevated(),
+            Spline::Cube(s) => s.elevated(),
+            Spline::Quad(s) => s.clone(),
+        }
+    }
+
+    /// The lowest degree spline that stays within `tolerance`, and its error estimate.
+    pub fn reduced(&self, tolerance: T) -> (Spline<T>, T) {
+        match self {
+            Spline::Line(_) => (self.clone(), T::ZERO),
+            Spline::Cube(s) => {
+                let (line, error) = s.reduced();
+                if error <= tolerance {
+                    (Spline::Line(line), error)
+                } else {
+                    (self.clone(), T::ZERO)
+                }
+            }
+            Spline::Quad(s) => {
+                let (line, error) = s.reduced_to_line();
+                if error <= tolerance {
+                    return (Spline::Line(line), error);
+                }
+                let (cube, error) = s.reduced_to_cube();
+                if error <= tolerance {
+                    (Spline::Cube(cube), error)
+                } else {
+                    (self.clone(), T::ZERO)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::bezier::spline::Spline;
+    use crate::float::bezier::spline_cube::CubeSpline;
+    use crate::float::bezier::spline_line::LineSpline;
+    use crate::float::bezier::spline_quad::QuadSpline;
+    use crate::float::math::point::Point;
+
+    fn assert_same(a: &Spline, b: &Spline) {
+        for (p0, p1) in a.regular_points(4).iter().zip(b.regular_points(4).iter()) {
+            assert!(p0.distance(*p1) < 1.0e-9);
+        }
+    }
+
+    #[test]
+    fn test_00() {
+        let line = Spline::Line(LineSpline {
+            a: Point::new(0.0, 0.0),
+            b: Point::new(30.0, 60.0),
+        });
+        let quad = Spline::Quad(line.elevated());
+
+        assert_same(&line, &quad);
+        let (reduced, error) = quad.reduced(0.001);
+        assert!(matches!(reduced, Spline::Line(_)));
+        assert!(error < 1.0e-9);
+    }
+
+    #[test]
+    fn test_01() {
+        let cube = Spline::Cube(CubeSpline {
+            a: Point::new(0.0, 0.0),
+            m: Point::new(0.0, 100.0),
+            b: Point::new(100.0, 100.0),
+        });
+        let quad = Spline::Quad(cube.elevated());
+
+        assert_same(&cube, &quad);
+        let (reduced, error) = quad.reduced(0.001);
+        assert!(matches!(reduced, Spline::Cube(_)));
+        assert!(error < 1.0e-9);
+        assert_same(&cube, &reduced);
+    }
+
+    #[test]
+    fn test_02() {
+        let quad = Spline::Quad(QuadSpline {
+            a: Point::new(0.0, 0.0),
+            am: Point::new(0.0, 50.0),
+            bm: Point::new(100.0, 50.0),
+            b: Point::new(100.0, 0.0),
+        });
+
+        let (reduced, error) = quad.reduced(0.001);
+        assert!(matches!(reduced, Spline::Quad(_)));
+        assert_eq!(error, 0.0);
+
+        let (reduced, error) = quad.reduced(100.0);
+        assert!(matches!(reduced, Spline::Line(_)));
+        assert!(error > 37.5);
+
+        let (reduced, error) = quad.reduced(30.0);
+        let max = reduced.regular_points(4).iter().zip(quad.regular_points(4).iter())
+            .map(|(a, b)| a.distance(*b))
+            .fold(0.0, f64::max);
+        assert!(matches!(reduced, Spline::Cube(_)));
+        assert!(max <= error);
+    }
+
+    #[test]
+    fn test_03() {
+        let quad64 = Spline::Quad(QuadSpline {
+            a: Point::new(0.0, 0.0),
+            am: Point::new(0.0, 50.0),
+            bm: Point::new(100.0, 50.0),
+            b: Point::new(100.0, 0.0),
+        });
+        let quad32: Spline<f32> = Spline::Quad(QuadSpline {
+            a: Point::new(0.0, 0.0),
+            am: Point::new(0.0, 50.0),
+            bm: Point::new(100.0, 50.0),
+            b: Point::new(100.0, 0.0),
+        });
+
+        for (p0, p1) in quad64.regular_points(4).iter().zip(quad32.regular_points(4).iter()) {
+            assert!((p0.x - p1.x as f64).abs() < 1.0e-4);
+            assert!((p0.y - p1.y as f64).abs() < 1.0e-4);
+        }
+
+        let len64 = quad64.avg_length(0.99, 1.0);
+        let len32 = quad32.avg_length(0.99, 1.0);
+        assert!((len64 - len32).abs() < 1.0e-3);
+    }
+}
@@ -1,35 +1,38 @@
-use crate::float::bezier::spline::CADSpline;
+use crate::bezier::spline::{tangent, CADSpline};
+use crate::float::bezier::spline_line::LineSpline;
+use crate::float::bezier::spline_quad::QuadSpline;
 use crate::float::math::line::Line;
+use crate::float::math::number::FloatNumber;
 use crate::float::math::point::Point;
 use crate::int::bezier::spline_cube::IntCubeSpline;
 
 #[derive(Debug, Clone)]
-pub(crate) struct CubeSpline {
-    pub(crate) a: Point,
-    pub(crate) m: Point,
-    pub(crate) b: Point,
+pub(crate) struct CubeSpline<T = f64> {
+    pub(crate) a: Point<T>,
+    pub(crate) m: Point<T>,
+    pub(crate) b: Point<T>,
 }
 
-impl CADSpline for CubeSpline {
+impl<T: FloatNumber> CADSpline<Point<T>> for CubeSpline<T> {
     #[inline]
-    fn start(&self) -> Point {
+    fn start(&self) -> Point<T> {
         self.a
     }
     #[inline]
-    fn start_dir(&self) -> Point {
-        (self.m - self.a).normalized()
+    fn start_dir(&self) -> Point<T> {
+        tangent(&[self.m - self.a, self.b - self.a])
     }
     #[inline]
-    fn end_dir(&self) -> Point {
-        (self.b - self.m).normalized()
+    fn end_dir(&self) -> Point<T> {
+        tangent(&[self.b - self.m, self.b - self.a])
     }
     #[inline]
-    fn end(&self) -> Point {
+    fn end(&self) -> Point<T> {
         self.b
     }
 
     #[inline]
-    fn split_at(&self, step: usize, split_factor: u32) -> Point {
+    fn split_at(&self, step: usize, split_factor: u32) -> Point<T> {
         let l0 = Line::new(self.a, self.m);
         let l1 = Line::new(self.m, self.b);
         let p10 = l0.split_at(step, split_factor);
@@ -38,7 +41,29 @@ impl CADSpline for CubeSpline {
     }
 }
 
-impl From<&IntCubeSpline> for CubeSpline {
+impl<T: FloatNumber> CubeSpline<T> {
+    /// Exact elevation to the two control points form.
+    #[inline]
+    pub(crate) fn elevated(&self) -> QuadSpline<T> {
+        let k = T::from_f64(2.0 / 3.0);
+        QuadSpline {
+            a: self.a,
+            am: self.a + (self.m - self.a) * k,
+            bm: self.b + (self.m - self.b) * k,
+            b: self.b,
+        }
+    }
+
+    /// Best fit line and the max distance between the curves at the same parameter.
+    #[inline]
+    pub(crate) fn reduced(&self) -> (LineSpline<T>, T) {
+        let mid = (self.a + self.b) * T::from_f64(0.5);
+        let error = T::from_f64(0.5) * (self.m - mid).length();
+        (LineSpline { a: self.a, b: self.b }, error)
+    }
+}
+
+impl<T: FloatNumber> From<&IntCubeSpline> for CubeSpline<T> {
     fn from(value: &IntCubeSpline) -> Self {
         Self {
             a: value.a.into(),
@@ -1,41 +1,56 @@
-use crate::float::bezier::spline::CADSpline;
+use crate::bezier::spline::{tangent, CADSpline};
+use crate::float::bezier::spline_quad::QuadSpline;
 use crate::float::math::line::Line;
+use crate::float::math::number::FloatNumber;
 use crate::float::math::point::Point;
 use crate::int::bezier::spline_line::IntLineSpline;
 
 #[derive(Debug, Clone)]
-pub(crate) struct LineSpline {
-    pub(crate) a: Point,
-    pub(crate) b: Point,
+pub(crate) struct LineSpline<T = f64> {
+    pub(crate) a: Point<T>,
+    pub(crate) b: Point<T>,
 }
 
-impl CADSpline for LineSpline {
+impl<T: FloatNumber> CADSpline<Point<T>> for LineSpline<T> {
     #[inline]
-    fn start(&self) -> Point {
+    fn start(&self) -> Point<T> {
         self.a
     }
     #[inline]
-    fn start_dir(&self) -> Point {
-        (self.b - self.a).normalized()
+    fn start_dir(&self) -> Point<T> {
+        tangent(&[self.b - self.a])
     }
 
     #[inline]
-    fn end_dir(&self) -> Point {
-        (self.b - self.a).normalized()
+    fn end_dir(&self) -> Point<T> {
+        tangent(&[self.b - self.a])
     }
     #[inline]
-    fn end(&self) -> Point {
+    fn end(&self) -> Point<T> {
         self.b
     }
 
     #[inline]
-    fn split_at(&self, step: usize, split_factor: u32) -> Point {
+    fn split_at(&self, step: usize, split_factor: u32) -> Point<T> {
         Line::new(self.a, self.b).split_at(step, split_factor)
     }
 }
 
+impl<T: FloatNumber> LineSpline<T> {
+    /// Exact elevation to the two control points form.
+    #[inline]
+    pub(crate) fn elevated(&self) -> QuadSpline<T> {
+        let ab = self.b - self.a;
+        QuadSpline {
+            a: self.a,
+            am: self.a + ab * T::from_f64(1.0 / 3.0),
+            bm: self.a + ab * T::from_f64(2.0 / 3.0),
+            b: self.b,
+        }
+    }
+}
 
-impl From<IntLineSpline> for LineSpline {
+impl<T: FloatNumber> From<IntLineSpline> for LineSpline<T> {
     fn from(value: IntLineSpline) -> Self {
         Self {
             a: value.a.into(),
@@ -1,37 +1,40 @@
-use crate::float::bezier::spline::CADSpline;
+use crate::bezier::spline::{tangent, CADSpline};
+use crate::float::bezier::spline_cube::CubeSpline;
+use crate::float::bezier::spline_line::LineSpline;
 use crate::float::math::line::Line;
+use crate::float::math::number::FloatNumber;
 use crate::float::math::point::Point;
 use crate::int::bezier::spline_quad::IntQuadSpline;
 
 
 #[derive(Debug, Clone)]
-pub(crate) struct QuadSpline {
-    pub(super) a: Point,
-    pub(super) am: Point,
-    pub(super) bm: Point,
-    pub(super) b: Point,
+pub(crate) struct QuadSpline<T = f64> {
+    pub(crate) a: Point<T>,
+    pub(crate) am: Point<T>,
+    pub(crate) bm: Point<T>,
+    pub(crate) b: Point<T>,
 }
 
-impl CADSpline for QuadSpline {
+impl<T: FloatNumber> CADSpline<Point<T>> for QuadSpline<T> {
     #[inline]
-    fn start(&self) -> Point {
+    fn start(&self) -> Point<T> {
         self.a
     }
     #[inline]
-    fn start_dir(&self) -> Point {
-        (self.am - self.a).normalized()
+    fn start_dir(&self) -> Point<T> {
+        tangent(&[self.am - self.a, self.bm - self.a, self.b - self.a])
     }
     #[inline]
-    fn end_dir(&self) -> Point {
-        (self.b - self.bm).normalized()
+    fn end_dir(&self) -> Point<T> {
+        tangent(&[self.b - self.bm, self.b - self.am, self.b - self.a])
     }
     #[inline]
-    fn end(&self) -> Point {
+    fn end(&self) -> Point<T> {
         self.b
     }
 
     #[inline]
-    fn split_at(&self, step: usize, split_factor: u32) -> Point {
+    fn split_at(&self, step: usize, split_factor: u32) -> Point<T> {
         let l0 = Line::new(self.a, self.am);
         let l1 = Line::new(self.am, self.bm);
         let l2 = Line::new(self.bm, self.b);
@@ -47,7 +50,27 @@ impl CADSpline for QuadSpline {
     }
 }
 
-impl From<&IntQuadSpline> for QuadSpline {
+impl<T: FloatNumber> QuadSpline<T> {
+    /// Best fit line and the max distance between the curves at the same parameter.
+    #[inline]
+    pub(crate) fn reduced_to_line(&self) -> (LineSpline<T>, T) {
+        let ab = self.b - self.a;
+        let e0 = (self.am - (self.a + ab * T::from_f64(1.0 / 3.0))).length();
+        let e1 = (self.bm - (self.a + ab * T::from_f64(2.0 / 3.0))).length();
+        (LineSpline { a: self.a, b: self.b }, T::from_f64(0.75) * e0.max(e1))
+    }
+
+    /// Best fit single control point spline and the max distance between the curves at the same parameter.
+    #[inline]
+    pub(crate) fn reduced_to_cube(&self) -> (CubeSpline<T>, T) {
+        let m = ((self.am + self.bm) * T::from_f64(3.0) - (self.a + self.b)) * T::from_f64(0.25);
+        let d = self.b - self.a + (self.am - self.bm) * T::from_f64(3.0);
+        let error = T::from_f64(3.0f64.sqrt() / 36.0) * d.length();
+        (CubeSpline { a: self.a, m, b: self.b }, error)
+    }
+}
+
+impl<T: FloatNumber> From<&IntQuadSpline> for QuadSpline<T> {
     fn from(value: &IntQuadSpline) -> Self {
         Self {
             a: value.a.into(),
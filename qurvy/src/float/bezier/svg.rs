@@ -0,0 +1,395 @@
+use std::fmt::Write;
+use crate::float::bezier::anchor::BezierAnchor;
+use crate::float::bezier::path::BezierPath;
+use crate::float::bezier::spline::Spline;
+use crate::float::math::offset::Offset;
+use crate::float::math::point::Point;
+use crate::int::bezier::anchor::AnchorKind;
+
+/// Reads the `d` attribute of an SVG `<path>`: the `M L H V C S Q T Z` commands
+/// in absolute and relative form. Arcs are not supported.
+pub(crate) struct PathDataParser<'a> {
+    data: &'a [u8],
+    pos: usize,
+    paths: Vec<BezierPath>,
+    anchors: Vec<BezierAnchor>,
+    start: Point,
+    current: Point,
+    /// The second control point of the last curve, reflected by `S` and `T`.
+    last_cubic: Option<Point>,
+    last_quad: Option<Point>,
+}
+
+impl<'a> PathDataParser<'a> {
+    #[inline]
+    pub(crate) fn new(data: &'a str) -> Self {
+        Self {
+            data: data.as_bytes(),
+            pos: 0,
+            paths: Vec::new(),
+            anchors: Vec::new(),
+            start: Point::new(0.0, 0.0),
+            current: Point::new(0.0, 0.0),
+            last_cubic: None,
+            last_quad: None,
+        }
+    }
+
+    /// Returns `None` if the data is malformed, uses an arc, or a number or a computed point is not finite.
+    pub(crate) fn parse(mut self) -> Option<Vec<BezierPath>> {
+        let mut command = None;
+        loop {
+            self.skip_separators();
+            let Some(&c) = self.data.get(self.pos) else {
+                break;
+            };
+
+            if c.is_ascii_alphabetic() {
+                self.pos += 1;
+                command = Some(c);
+                if c == b'Z' || c == b'z' {
+                    self.close();
+                    continue;
+                }
+            } else if command.is_none() || command == Some(b'Z') || command == Some(b'z') {
+                // numbers without a command
+                return None;
+            }
+
+            let c = command?;
+            self.segment(c)?;
+
+            // the coordinates after a move are implicit line commands
+            command = match c {
+                b'M' => Some(b'L'),
+                b'm' => Some(b'l'),
+                _ => Some(c),
+            };
+        }
+
+        self.flush(false);
+        Some(self.paths)
+    }
+
+    fn segment(&mut self, command: u8) -> Option<()> {
+        let relative = command.is_ascii_lowercase();
+        let origin = if relative { self.current } else { Point::new(0.0, 0.0) };
+
+        let mut last_cubic = None;
+        let mut last_quad = None;
+
+        match command.to_ascii_uppercase() {
+            b'M' => {
+                let p = self.point(origin)?;
+                self.flush(false);
+                self.anchors.push(Self::corner(p));
+                self.start = p;
+                self.current = p;
+            }
+            b'L' => {
+                let p = self.point(origin)?;
+                self.line_to(p)?;
+            }
+            b'H' => {
+                let x = self.number()?;
+                let x = if relative { self.current.x + x } else { x };
+                self.line_to(Point::new(x, self.current.y))?;
+            }
+            b'V' => {
+                let y = self.number()?;
+                let y = if relative { self.current.y + y } else { y };
+                self.line_to(Point::new(self.current.x, y))?;
+            }
+            b'C' => {
+                let c0 = self.point(origin)?;
+                let c1 = self.point(origin)?;
+                let p = self.point(origin)?;
+                self.cubic_to(c0, c1, p)?;
+                last_cubic = Some(c1);
+            }
+            b'S' => {
+                let c0 = self.reflected(self.last_cubic);
+                let c1 = self.point(origin)?;
+                let p = self.point(origin)?;
+                self.cubic_to(c0, c1, p)?;
+                last_cubic = Some(c1);
+            }
+            b'Q' => {
+                let c = self.point(origin)?;
+                let p = self.point(origin)?;
+                self.quad_to(c, p)?;
+                last_quad = Some(c);
+            }
+            b'T' => {
+                let c = self.reflected(self.last_quad);
+                let p = self.point(origin)?;
+                self.quad_to(c, p)?;
+                last_quad = Some(c);
+            }
+            _ => return None,
+        }
+
+        self.last_cubic = last_cubic;
+        self.last_quad = last_quad;
+
+        Some(())
+    }
+
+    fn line_to(&mut self, p: Point) -> Option<()> {
+        if !is_finite(p) {
+            return None;
+        }
+        self.ensure_started();
+        self.anchors.push(Self::corner(p));
+        self.current = p;
+        Some(())
+    }
+
+    /// The control points are checked as they are written back: an anchor point plus its handle.
+    fn cubic_to(&mut self, c0: Point, c1: Point, p: Point) -> Option<()> {
+        let a = self.current;
+        let handle_out: Offset = (c0 - a).into();
+        let handle_in: Offset = (c1 - p).into();
+        if !is_finite(p) || !is_finite(a + handle_out) || !is_finite(p + handle_in) {
+            return None;
+        }
+
+        self.ensure_started();
+        if let Some(last) = self.anchors.last_mut() {
+            last.handle_out = Some(handle_out);
+        }
+        let mut anchor = Self::corner(p);
+        anchor.handle_in = Some(handle_in);
+        self.anchors.push(anchor);
+        self.current = p;
+        Some(())
+    }
+
+    /// The quadratic curve is elevated exactly to a cubic one.
+    fn quad_to(&mut self, c: Point, p: Point) -> Option<()> {
+        let a = self.current;
+        let k = 2.0 / 3.0;
+        let c0 = a + Offset::from((c - a) * k);
+        let c1 = p + Offset::from((c - p) * k);
+        self.cubic_to(c0, c1, p)
+    }
+
+    #[inline]
+    fn reflected(&self, control: Option<Point>) -> Point {
+        match control {
+            Some(c) => self.current + Offset::from(self.current - c),
+            None => self.current,
+        }
+    }
+
+    /// A drawing command right after `Z` starts a new subpath at the start of the closed one.
+    #[inline]
+    fn ensure_started(&mut self) {
+        if self.anchors.is_empty() {
+            self.anchors.push(Self::corner(self.current));
+            self.start = self.current;
+        }
+    }
+
+    fn close(&mut self) {
+        if !self.anchors.is_empty() {
+            self.flush(true);
+            self.current = self.start;
+        }
+        self.last_cubic = None;
+        self.last_quad = None;
+    }
+
+    /// Finishes the current subpath. A closing segment that ends on the start anchor
+    /// is folded into it. A lone move is dropped.
+    fn flush(&mut self, closed: bool) {
+        let mut anchors = std::mem::take(&mut self.anchors);
+        if closed && anchors.len() > 1 {
+            let last = anchors[anchors.len() - 1];
+            if last.point == anchors[0].point {
+                anchors[0].handle_in = last.handle_in;
+                anchors.pop();
+            }
+        }
+
+        if anchors.len() > 1 || closed && !anchors.is_empty() {
+            self.paths.push(BezierPath { anchors, closed });
+        }
+    }
+
+    #[inline]
+    fn corner(point: Point) -> BezierAnchor {
+        BezierAnchor { point, handle_in: None, handle_out: None, kind: AnchorKind::Corner }
+    }
+
+    /// The next coordinate pair added to `origin`, a relative one can still overflow.
+    #[inline]
+    fn point(&mut self, origin: Point) -> Option<Point> {
+        let p = origin + self.offset()?;
+        is_finite(p).then_some(p)
+    }
+
+    #[inline]
+    fn offset(&mut self) -> Option<Offset> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Some(Offset::new(x, y))
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        self.skip_separators();
+        let begin = self.pos;
+
+        if matches!(self.peek(), Some(b'+' | b'-')) {
+            self.pos += 1;
+        }
+        let int_digits = self.skip_digits();
+        let mut frac_digits = 0;
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            frac_digits = self.skip_digits();
+        }
+        if int_digits + frac_digits == 0 {
+            return None;
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if self.skip_digits() == 0 {
+                return None;
+            }
+        }
+
+        let text = std::str::from_utf8(&self.data[begin..self.pos]).ok()?;
+        let value: f64 = text.parse().ok()?;
+
+        value.is_finite().then_some(value)
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    #[inline]
+    fn skip_digits(&mut self) -> usize {
+        let begin = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.pos - begin
+    }
+
+    #[inline]
+    fn skip_separators(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace() || c == b',') {
+            self.pos += 1;
+        }
+    }
+}
+
+#[inline]
+fn is_finite(p: Point) -> bool {
+    p.x.is_finite() && p.y.is_finite()
+}
+
+/// Writes the path as SVG path data, a spline becomes an `L`, `Q` or `C` command.
+/// The closing line of a closed path is left to `Z`, unless it ends where it starts.
+pub(crate) fn write_path_data(path: &BezierPath, out: &mut String) {
+    let (Some(first), Some(last)) = (path.anchors.first(), path.anchors.last()) else {
+        return;
+    };
+    let _ = write!(out, "M{} {}", first.point.x, first.point.y);
+
+    let count = path.anchors.len();
+    for (i, spline) in path.splines().enumerate() {
+        let _ = match spline {
+            Spline::Line(_) if i + 1 == count && last.point != first.point => Ok(()),
+            Spline::Line(s) => write!(out, " L{} {}", s.b.x, s.b.y),
+            Spline::Cube(s) => write!(out, " Q{} {} {} {}", s.m.x, s.m.y, s.b.x, s.b.y),
+            Spline::Quad(s) => write!(out, " C{} {} {} {} {} {}", s.am.x, s.am.y, s.bm.x, s.bm.y, s.b.x, s.b.y),
+        };
+    }
+
+    if path.closed {
+        out.push_str(" Z");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::bezier::path::BezierPath;
+    use crate::float::math::offset::Offset;
+    use crate::float::math::point::Point;
+
+    #[test]
+    fn test_00() {
+        let paths = BezierPath::from_svg_path("M0,0 L10 0 l0-10 H-5 v5 z").unwrap();
+
+        assert_eq!(paths.len(), 1);
+        let path = &paths[0];
+        assert!(path.closed);
+        assert_eq!(path.anchors.len(), 5);
+        assert_eq!(path.anchors[2].point, Point::new(10.0, -10.0));
+        assert_eq!(path.anchors[4].point, Point::new(-5.0, -5.0));
+        assert!(path.anchors.iter().all(|a| a.handle_in.is_none() && a.handle_out.is_none()));
+    }
+
+    #[test]
+    fn test_01() {
+        // the closing curve ends on the start point and is folded into the first anchor
+        let paths = BezierPath::from_svg_path("M0 0 C 0 10 10 10 10 0 S 0 -10 0 0 Z").unwrap();
+
+        let path = &paths[0];
+        assert!(path.closed);
+        assert_eq!(path.anchors.len(), 2);
+        assert_eq!(path.anchors[0].handle_out, Some(Offset::new(0.0, 10.0)));
+        assert_eq!(path.anchors[0].handle_in, Some(Offset::new(0.0, -10.0)));
+        // `S` reflects the previous control point
+        assert_eq!(path.anchors[1].handle_out, Some(Offset::new(0.0, -10.0)));
+    }
+
+    #[test]
+    fn test_02() {
+        let paths = BezierPath::from_svg_path("M0 0 Q 3 3 6 0 T 12 0 m 0 10 h5 M20 20").unwrap();
+
+        assert_eq!(paths.len(), 2);
+        let quad = &paths[0];
+        assert_eq!(quad.anchors[0].handle_out, Some(Offset::new(2.0, 2.0)));
+        assert_eq!(quad.anchors[1].handle_in, Some(Offset::new(-2.0, 2.0)));
+        assert_eq!(quad.anchors[1].handle_out, Some(Offset::new(2.0, -2.0)));
+        assert_eq!(paths[1].anchors[0].point, Point::new(12.0, 10.0));
+    }
+
+    #[test]
+    fn test_03() {
+        assert!(BezierPath::from_svg_path("M0 0 A 5 5 0 0 1 10 0").is_none());
+        assert!(BezierPath::from_svg_path("10 0").is_none());
+        assert!(BezierPath::from_svg_path("M0 0 L").is_none());
+        assert!(BezierPath::from_svg_path("M0 0 L1e999 0").is_none());
+        // the relative coordinates add up past the `f64` range
+        assert!(BezierPath::from_svg_path("M1e308 0 l1e308 0").is_none());
+        assert!(BezierPath::from_svg_path("M1e308 0 h1e308").is_none());
+        assert!(BezierPath::from_svg_path("M-1e308 0 C 1e308 0 0 0 0 0").is_none());
+        assert!(BezierPath::from_svg_path("M1e308 0 q1e308 0 0 0").is_none());
+        assert!(BezierPath::from_svg_path("M. 0").is_none());
+        assert!(BezierPath::from_svg_path("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_04() {
+        let data = "M0 0 C 0 10 10 10 10 0 L 10 -5 Q 5 -10 0 -5 Z M1.5.5 l.25-1e1 M2 2 L4 2 L2 2 L2 2 Z";
+        let paths = BezierPath::from_svg_path(data).unwrap();
+        let written = BezierPath::to_svg_path(&paths);
+        // the closing line of the first path is left to `Z`, the one of the last path is not
+        assert!(written.starts_with("M0 0 C0 10 10 10 10 0 L10 -5 C"));
+        assert!(written.ends_with(" 0 -5 Z M1.5 0.5 L1.75 -9.5 M2 2 L4 2 L2 2 L2 2 Z"));
+
+        let reparsed = BezierPath::from_svg_path(&written).unwrap();
+        assert_eq!(reparsed.len(), 3);
+        assert_eq!(reparsed[2].anchors.len(), 3);
+        assert_eq!(BezierPath::to_svg_path(&reparsed), written);
+    }
+}
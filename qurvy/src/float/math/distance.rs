@@ -0,0 +1,54 @@
+use crate::float::math::point::Point;
+
+/// Distance from `p` to the segment `ab`.
+#[inline]
+pub(crate) fn segment_distance(p: Point, a: Point, b: Point) -> f64 {
+    let ab = b - a;
+    let ap = p - a;
+    let sqr_len = ab.sqr_length();
+    if sqr_len == 0.0 {
+        return ap.length();
+    }
+    let t = (ap.dot_product(&ab) / sqr_len).clamp(0.0, 1.0);
+    (ap - ab * t).length()
+}
+
+/// Distance from `p` to the closest point of a polyline.
+pub(crate) fn polyline_distance(p: Point, polyline: &[Point]) -> f64 {
+    match polyline.len() {
+        0 => f64::INFINITY,
+        1 => p.distance(polyline[0]),
+        _ => polyline
+            .windows(2)
+            .map(|w| segment_distance(p, w[0], w[1]))
+            .fold(f64::INFINITY, f64::min),
+    }
+}
+
+/// Symmetric Hausdorff distance between two polylines.
+pub(crate) fn hausdorff_distance(a: &[Point], b: &[Point]) -> f64 {
+    let ab = a.iter().map(|&p| polyline_distance(p, b)).fold(0.0, f64::max);
+    let ba = b.iter().map(|&p| polyline_distance(p, a)).fold(0.0, f64::max);
+    ab.max(ba)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::math::distance::{hausdorff_distance, segment_distance};
+    use crate::float::math::point::Point;
+
+    #[test]
+    fn test_00() {
+        let a = Point::new(0.0, 0.0);
+        let b = Point::new(10.0, 0.0);
+        assert_eq!(segment_distance(Point::new(5.0, 3.0), a, b), 3.0);
+        assert_eq!(segment_distance(Point::new(13.0, 4.0), a, b), 5.0);
+    }
+
+    #[test]
+    fn test_01() {
+        let a = vec![Point::new(0.0, 0.0), Point::new(10.0, 0.0)];
+        let b = vec![Point::new(0.0, 1.0), Point::new(5.0, 2.0), Point::new(10.0, 1.0)];
+        assert_eq!(hausdorff_distance(&a, &b), 2.0);
+    }
+}
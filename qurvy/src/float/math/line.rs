@@ -1,29 +1,30 @@
+use crate::float::math::number::FloatNumber;
 use crate::float::math::point::Point;
 
-pub(crate) struct Line {
-    a: Point,
-    b: Point
+pub(crate) struct Line<T = f64> {
+    a: Point<T>,
+    b: Point<T>
 }
 
-impl Line {
+impl<T: FloatNumber> Line<T> {
 
     #[inline]
-    pub(crate) fn new(a: Point, b: Point) -> Self {
+    pub(crate) fn new(a: Point<T>, b: Point<T>) -> Self {
         Self { a, b }
     }
 
     #[inline]
-    pub(crate) fn split_at(&self, step: usize, split_factor: u32) -> Point {
+    pub(crate) fn split_at(&self, step: usize, split_factor: u32) -> Point<T> {
         let i_pow = 2f64.powi(-(split_factor as i32));
-        let weight = (step as f64) * i_pow;
+        let weight = T::from_f64((step as f64) * i_pow);
         self.split_by_weight(weight)
     }
 
     #[inline]
-    pub(crate) fn split_by_weight(&self, weight: f64) -> Point {
+    pub(crate) fn split_by_weight(&self, weight: T) -> Point<T> {
         let x = self.a.x + weight * (self.b.x - self.a.x);
         let y = self.a.y + weight * (self.b.y - self.a.y);
 
         Point::new(x, y)
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,5 @@
+pub mod number;
 pub mod point;
 pub mod offset;
-pub(crate) mod length;
-pub(crate) mod line;
\ No newline at end of file
+pub(crate) mod line;
+pub(crate) mod distance;
\ No newline at end of file
@@ -1,35 +1,54 @@
+use std::ops;
 use serde::{Deserialize, Serialize};
 use crate::convert::grid::Grid;
-use crate::convert::to_int::ToInt;
+use crate::convert::to_int::{Rounding, ToInt};
+use crate::float::math::number::FloatNumber;
 use crate::float::math::point::Point;
 use crate::int::math::offset::IntOffset;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-pub struct Offset {
-    pub x: f64,
-    pub y: f64,
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Offset<T = f64> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Offset {
+impl<T: FloatNumber> Offset<T> {
     #[inline]
-    pub fn new(x: f64, y: f64) -> Self {
+    pub fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
+
+    #[inline]
+    pub fn length(&self) -> T {
+        self.x.hypot(self.y)
+    }
+}
+
+impl<T: FloatNumber> ops::Neg for Offset<T> {
+    type Output = Offset<T>;
+
+    #[inline(always)]
+    fn neg(self) -> Offset<T> {
+        Offset {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
 }
 
-impl From<Point> for Offset {
+impl<T: FloatNumber> From<Point<T>> for Offset<T> {
     #[inline]
-    fn from(value: Point) -> Self {
+    fn from(value: Point<T>) -> Self {
         Self::new(value.x, value.y)
     }
 }
 
-impl ToInt<IntOffset> for Offset {
+impl<T: FloatNumber> ToInt<IntOffset> for Offset<T> {
     #[inline]
-    fn to_int(&self, grid: &Grid) -> IntOffset {
-        let x = grid.float_to_int(self.x);
-        let y = grid.float_to_int(self.y);
+    fn to_int_rounded(&self, grid: &Grid, rounding: Rounding) -> (IntOffset, f64) {
+        let (x, ex) = grid.float_to_int_rounded(self.x.to_f64(), rounding);
+        let (y, ey) = grid.float_to_int_rounded(self.y.to_f64(), rounding);
 
-        IntOffset::new(x, y)
+        (IntOffset::new(x, y), ex.max(ey))
     }
-}
\ No newline at end of file
+}
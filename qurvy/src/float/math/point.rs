@@ -1,24 +1,25 @@
 use std::ops;
 use serde::{Deserialize, Serialize};
 use crate::convert::grid::Grid;
-use crate::convert::to_int::ToInt;
+use crate::convert::to_int::{Rounding, ToInt};
+use crate::float::math::number::FloatNumber;
 use crate::float::math::offset::Offset;
 use crate::int::math::point::IntPoint;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-pub struct Point {
-    pub x: f64,
-    pub y: f64,
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Point<T = f64> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Point {
+impl<T: FloatNumber> Point<T> {
     #[inline]
-    pub fn new(x: f64, y: f64) -> Self {
+    pub fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
 
     #[inline]
-    pub fn sqr_length(&self) -> f64 {
+    pub fn sqr_length(&self) -> T {
         let dx = self.x;
         let dy = self.y;
 
@@ -26,12 +27,12 @@ impl Point {
     }
 
     #[inline]
-    pub fn length(&self) -> f64 {
+    pub fn length(&self) -> T {
         self.sqr_length().sqrt()
     }
 
     #[inline]
-    pub fn distance(&self, other: Point) -> f64 {
+    pub fn distance(&self, other: Point<T>) -> T {
         let dx = self.x - other.x;
         let dy = self.y - other.y;
 
@@ -39,38 +40,43 @@ impl Point {
     }
 
     #[inline]
-    pub fn normalized(&self) -> Point {
-        let inv_len = 1.0 / self.length();
+    pub fn normalized(&self) -> Point<T> {
+        let inv_len = T::ONE / self.length();
         let x = self.x * inv_len;
         let y = self.y * inv_len;
         Point { x, y }
     }
 
     #[inline]
-    pub fn dot_product(&self, other: &Self) -> f64 {
+    pub fn dot_product(&self, other: &Self) -> T {
         self.x * other.x + self.y * other.y
     }
+
+    #[inline]
+    pub fn cross_product(&self, other: &Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
 }
 
-impl From<Offset> for Point {
+impl<T: FloatNumber> From<Offset<T>> for Point<T> {
     #[inline]
-    fn from(value: Offset) -> Self {
+    fn from(value: Offset<T>) -> Self {
         Self::new(value.x, value.y)
     }
 }
 
-impl From<IntPoint> for Point {
+impl<T: FloatNumber> From<IntPoint> for Point<T> {
     #[inline]
     fn from(value: IntPoint) -> Self {
-        Self::new(value.x as f64, value.y as f64)
+        Self::new(T::from_f64(value.x as f64), T::from_f64(value.y as f64))
     }
 }
 
-impl ops::Add for Point {
-    type Output = Point;
+impl<T: FloatNumber> ops::Add for Point<T> {
+    type Output = Point<T>;
 
     #[inline(always)]
-    fn add(self, other: Point) -> Point {
+    fn add(self, other: Point<T>) -> Point<T> {
         Point {
             x: self.x + other.x,
             y: self.y + other.y,
@@ -78,11 +84,11 @@ impl ops::Add for Point {
     }
 }
 
-impl ops::Add<Offset> for Point {
-    type Output = Point;
+impl<T: FloatNumber> ops::Add<Offset<T>> for Point<T> {
+    type Output = Point<T>;
 
     #[inline(always)]
-    fn add(self, other: Offset) -> Point {
+    fn add(self, other: Offset<T>) -> Point<T> {
         Point {
             x: self.x + other.x,
             y: self.y + other.y,
@@ -90,11 +96,11 @@ impl ops::Add<Offset> for Point {
     }
 }
 
-impl ops::Sub for Point {
-    type Output = Point;
+impl<T: FloatNumber> ops::Sub for Point<T> {
+    type Output = Point<T>;
 
     #[inline(always)]
-    fn sub(self, other: Point) -> Point {
+    fn sub(self, other: Point<T>) -> Point<T> {
         Point {
             x: self.x - other.x,
             y: self.y - other.y,
@@ -102,11 +108,11 @@ impl ops::Sub for Point {
     }
 }
 
-impl ops::Sub<Offset> for Point {
-    type Output = Point;
+impl<T: FloatNumber> ops::Sub<Offset<T>> for Point<T> {
+    type Output = Point<T>;
 
     #[inline(always)]
-    fn sub(self, other: Offset) -> Point {
+    fn sub(self, other: Offset<T>) -> Point<T> {
         Point {
             x: self.x - other.x,
             y: self.y - other.y,
@@ -114,12 +120,24 @@ impl ops::Sub<Offset> for Point {
     }
 }
 
-impl ToInt<IntPoint> for Point {
+impl<T: FloatNumber> ops::Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    #[inline(always)]
+    fn mul(self, scale: T) -> Point<T> {
+        Point {
+            x: self.x * scale,
+            y: self.y * scale,
+        }
+    }
+}
+
+impl<T: FloatNumber> ToInt<IntPoint> for Point<T> {
     #[inline]
-    fn to_int(&self, grid: &Grid) -> IntPoint {
-        let x = grid.float_to_int(self.x);
-        let y = grid.float_to_int(self.y);
+    fn to_int_rounded(&self, grid: &Grid, rounding: Rounding) -> (IntPoint, f64) {
+        let (x, ex) = grid.float_to_int_rounded(self.x.to_f64(), rounding);
+        let (y, ey) = grid.float_to_int_rounded(self.y.to_f64(), rounding);
 
-        IntPoint::new(x, y)
+        (IntPoint::new(x, y), ex.max(ey))
     }
-}
\ No newline at end of file
+}
@@ -1,2 +1,3 @@
 pub mod bezier;
-pub mod math;
\ No newline at end of file
+pub mod math;
+pub mod stroke;
\ No newline at end of file
@@ -0,0 +1,332 @@
+use std::f64::consts::PI;
+use crate::float::bezier::path::BezierPath;
+use crate::float::bezier::spline::Spline;
+use crate::float::math::point::Point;
+use crate::float::stroke::style::{LineCap, LineJoin, StrokeStyle};
+
+const SPLIT_FACTOR: u32 = 4;
+const CORNER_ANGLE: f64 = PI / 6.0;
+const MAX_ARC_STEP: f64 = PI / 8.0;
+
+// ~1 degree, a corner anchor below it is treated as smooth
+const SMOOTH_COS: f64 = 0.9998;
+
+/// Outlines a stroke: the flattened centreline is offset to both sides, joined and capped,
+/// and the resulting polygons are fitted back into curves.
+pub(crate) struct StrokeBuilder {
+    style: StrokeStyle,
+    radius: f64,
+    tolerance: f64,
+    min_len: f64,
+    arc_step: f64,
+}
+
+impl StrokeBuilder {
+    #[inline]
+    pub(crate) fn new(style: StrokeStyle, tolerance: f64) -> Self {
+        let radius = 0.5 * style.width.abs();
+        let tolerance = tolerance.max(f64::EPSILON);
+
+        // the chord of a step deviates from the circle by at most the tolerance
+        let arc_step = if radius > tolerance {
+            (2.0 * (1.0 - tolerance / radius).acos()).min(MAX_ARC_STEP)
+        } else {
+            MAX_ARC_STEP
+        };
+
+        Self {
+            style,
+            radius,
+            tolerance,
+            min_len: 0.001 * tolerance,
+            arc_step,
+        }
+    }
+
+    pub(crate) fn build(&self, path: &BezierPath) -> Vec<BezierPath> {
+        if self.radius == 0.0 {
+            return Vec::new();
+        }
+
+        let (points, corners) = self.centreline(path);
+        let n = points.len();
+        if n < 2 {
+            return Vec::new();
+        }
+
+        let back_points: Vec<_> = points.iter().rev().copied().collect();
+        let back_corners: Vec<_> = corners.iter().rev().copied().collect();
+
+        if path.closed && n > 2 {
+            let outer = self.side(&points, &corners, true);
+            let inner = self.side(&back_points, &back_corners, true);
+            return vec![self.fit(outer), self.fit(inner)];
+        }
+
+        let mut contour = self.side(&points, &corners, false);
+        self.cap(points[n - 1], (points[n - 1] - points[n - 2]).normalized(), &mut contour);
+        contour.append(&mut self.side(&back_points, &back_corners, false));
+        self.cap(points[0], (points[0] - points[1]).normalized(), &mut contour);
+
+        vec![self.fit(contour)]
+    }
+
+    /// Flattens the path and marks the points where two splines meet.
+    fn centreline(&self, path: &BezierPath) -> (Vec<Point>, Vec<bool>) {
+        let mut points = Vec::new();
+        let mut corners = Vec::new();
+        let mut end = None;
+        for spline in path.splines() {
+            let split_factor = if let Spline::Line(_) = spline { 0 } else { SPLIT_FACTOR };
+            for (i, p) in spline.regular_points(split_factor).into_iter().enumerate() {
+                self.push(&mut points, &mut corners, p, i == 0);
+            }
+            end = Some(spline.end());
+        }
+
+        if path.closed {
+            if points.len() > 1 && points[0].distance(*points.last().unwrap()) <= self.min_len {
+                points.pop();
+                corners.pop();
+                corners[0] = true;
+            }
+        } else if let Some(end) = end {
+            self.push(&mut points, &mut corners, end, true);
+        }
+
+        (points, corners)
+    }
+
+    #[inline]
+    fn push(&self, points: &mut Vec<Point>, corners: &mut Vec<bool>, p: Point, is_corner: bool) {
+        if points.last().is_some_and(|last| last.distance(p) <= self.min_len) {
+            *corners.last_mut().unwrap() |= is_corner;
+            return;
+        }
+        points.push(p);
+        corners.push(is_corner);
+    }
+
+    /// Offsets the polyline to its left by the stroke radius.
+    fn side(&self, points: &[Point], corners: &[bool], closed: bool) -> Vec<Point> {
+        let n = points.len();
+        let mut result = Vec::with_capacity(2 * n);
+
+        if closed {
+            for i in 0..n {
+                let prev = points[(i + n - 1) % n];
+                let next = points[(i + 1) % n];
+                self.vertex(points[i], prev, next, corners[i], &mut result);
+            }
+        } else {
+            let d = (points[1] - points[0]).normalized();
+            result.push(points[0] + normal(d) * self.radius);
+            for i in 1..n - 1 {
+                self.vertex(points[i], points[i - 1], points[i + 1], corners[i], &mut result);
+            }
+            let d = (points[n - 1] - points[n - 2]).normalized();
+            result.push(points[n - 1] + normal(d) * self.radius);
+        }
+
+        result
+    }
+
+    fn vertex(&self, p: Point, prev: Point, next: Point, is_corner: bool, result: &mut Vec<Point>) {
+        let d0 = (p - prev).normalized();
+        let d1 = (next - p).normalized();
+        let n0 = normal(d0);
+        let n1 = normal(d1);
+        let dot = d0.dot_product(&d1).clamp(-1.0, 1.0);
+
+        let is_smooth = if is_corner { dot > SMOOTH_COS } else { dot > 0.0 };
+        if is_smooth {
+            // both offset edges meet at the miter point
+            result.push(p + (n0 + n1) * (self.radius / (1.0 + dot)));
+            return;
+        }
+
+        let cross = d0.cross_product(&d1);
+        if cross > 0.0 {
+            // inner side, the offset edges cross at the miter point unless it lies beyond them
+            let shift = self.radius * cross / (1.0 + dot);
+            if 2.0 * shift <= prev.distance(p).min(next.distance(p)) {
+                result.push(p + (n0 + n1) * (self.radius / (1.0 + dot)));
+            } else {
+                result.push(p + n0 * self.radius);
+                result.push(p);
+                result.push(p + n1 * self.radius);
+            }
+            return;
+        }
+
+        result.push(p + n0 * self.radius);
+        match self.style.join {
+            LineJoin::Miter => {
+                // the miter length ratio is 1 / cos(a / 2) = sqrt(2 / (1 + cos(a)))
+                let limit = self.style.miter_limit;
+                if (1.0 + dot) * limit * limit >= 2.0 {
+                    result.push(p + (n0 + n1) * (self.radius / (1.0 + dot)));
+                }
+            }
+            LineJoin::Round => self.arc(p, n0, d0, dot.acos(), result),
+            LineJoin::Bevel => {}
+        }
+        result.push(p + n1 * self.radius);
+    }
+
+    /// Adds the cap between the left and the right side. `dir` points out of the path.
+    fn cap(&self, p: Point, dir: Point, result: &mut Vec<Point>) {
+        let n = normal(dir);
+        match self.style.cap {
+            LineCap::Butt => {}
+            LineCap::Round => self.arc(p, n, dir, PI, result),
+            LineCap::Square => {
+                result.push(p + (n + dir) * self.radius);
+                result.push(p + (dir - n) * self.radius);
+            }
+        }
+    }
+
+    /// Inner points of the arc that rotates `n` towards `dir` by `angle` around `p`.
+    fn arc(&self, p: Point, n: Point, dir: Point, angle: f64, result: &mut Vec<Point>) {
+        let count = (angle / self.arc_step).ceil() as usize;
+        for i in 1..count {
+            let (sin, cos) = (angle * i as f64 / count as f64).sin_cos();
+            result.push(p + (n * cos + dir * sin) * self.radius);
+        }
+    }
+
+    #[inline]
+    fn fit(&self, mut contour: Vec<Point>) -> BezierPath {
+        contour.push(contour[0]);
+        BezierPath::fit(&contour, self.tolerance, CORNER_ANGLE)
+    }
+}
+
+#[inline(always)]
+fn normal(dir: Point) -> Point {
+    Point::new(-dir.y, dir.x)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::bezier::anchor::BezierAnchor;
+    use crate::float::bezier::path::BezierPath;
+    use crate::float::math::offset::Offset;
+    use crate::float::math::point::Point;
+    use crate::float::stroke::style::{LineCap, LineJoin, StrokeStyle};
+    use crate::int::bezier::anchor::AnchorKind;
+
+    fn path(points: &[(f64, f64)], closed: bool) -> BezierPath {
+        BezierPath {
+            anchors: points.iter()
+                .map(|&(x, y)| BezierAnchor { point: Point::new(x, y), handle_in: None, handle_out: None, kind: AnchorKind::Corner })
+                .collect(),
+            closed,
+        }
+    }
+
+    fn bounds(path: &BezierPath) -> [f64; 4] {
+        path.regular_points(4).iter().fold(
+            [f64::MAX, f64::MAX, f64::MIN, f64::MIN],
+            |b, p| [b[0].min(p.x), b[1].min(p.y), b[2].max(p.x), b[3].max(p.y)],
+        )
+    }
+
+    fn assert_bounds(path: &BezierPath, expected: [f64; 4], tolerance: f64) {
+        let bounds = bounds(path);
+        for (a, b) in bounds.iter().zip(expected.iter()) {
+            assert!((a - b).abs() <= tolerance, "{:?} != {:?}", bounds, expected);
+        }
+    }
+
+    #[test]
+    fn test_00() {
+        let line = path(&[(0.0, 0.0), (10.0, 0.0)], false);
+        let result = line.stroke(&StrokeStyle::new(2.0), 0.01);
+
+        assert_eq!(result.len(), 1);
+        assert!(result[0].closed);
+        assert_eq!(result[0].anchors.len(), 4);
+        assert_bounds(&result[0], [0.0, -1.0, 10.0, 1.0], 0.001);
+    }
+
+    #[test]
+    fn test_01() {
+        let line = path(&[(0.0, 0.0), (10.0, 0.0)], false);
+        let style = StrokeStyle::new(2.0).with_cap(LineCap::Square);
+        let result = line.stroke(&style, 0.01);
+
+        assert_eq!(result[0].anchors.len(), 4);
+        assert_bounds(&result[0], [-1.0, -1.0, 11.0, 1.0], 0.001);
+    }
+
+    #[test]
+    fn test_02() {
+        let line = path(&[(0.0, 0.0), (10.0, 0.0)], false);
+        let style = StrokeStyle::new(2.0).with_cap(LineCap::Round);
+        let result = line.stroke(&style, 0.01);
+
+        assert_eq!(result.len(), 1);
+        assert!(result[0].anchors.len() < 12);
+        assert_bounds(&result[0], [-1.0, -1.0, 11.0, 1.0], 0.02);
+    }
+
+    #[test]
+    fn test_03() {
+        let square = path(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)], true);
+        let result = square.stroke(&StrokeStyle::new(2.0), 0.01);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].anchors.len(), 4);
+        assert_eq!(result[1].anchors.len(), 4);
+        assert_bounds(&result[0], [1.0, 1.0, 9.0, 9.0], 0.001);
+        assert_bounds(&result[1], [-1.0, -1.0, 11.0, 11.0], 0.001);
+    }
+
+    #[test]
+    fn test_04() {
+        let square = path(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)], true);
+        let bevel = square.stroke(&StrokeStyle::new(2.0).with_join(LineJoin::Bevel), 0.01);
+        let round = square.stroke(&StrokeStyle::new(2.0).with_join(LineJoin::Round), 0.01);
+
+        assert_eq!(bevel[1].anchors.len(), 8);
+        assert_bounds(&bevel[1], [-1.0, -1.0, 11.0, 11.0], 0.001);
+        assert_bounds(&round[1], [-1.0, -1.0, 11.0, 11.0], 0.02);
+    }
+
+    #[test]
+    fn test_05() {
+        // a sharp turn: the miter is ~10 times longer than the width
+        let corner = path(&[(0.0, 0.0), (10.0, 0.0), (0.0, 2.0)], false);
+        let miter = corner.stroke(&StrokeStyle::new(2.0).with_miter_limit(20.0), 0.01);
+        let limited = corner.stroke(&StrokeStyle::new(2.0), 0.01);
+
+        assert!(bounds(&miter[0])[2] > 15.0);
+        assert!(bounds(&limited[0])[2] < 11.0);
+    }
+
+    #[test]
+    fn test_06() {
+        let dot = path(&[(5.0, 5.0)], false);
+        assert!(dot.stroke(&StrokeStyle::new(2.0), 0.01).is_empty());
+
+        let line = path(&[(0.0, 0.0), (10.0, 0.0)], false);
+        assert!(line.stroke(&StrokeStyle::new(0.0), 0.01).is_empty());
+    }
+
+    #[test]
+    fn test_07() {
+        let mut wave = path(&[(-15.0, 0.0), (0.0, 0.0), (15.0, 0.0)], false);
+        wave.anchors[0].handle_out = Some(Offset::new(5.0, 10.0));
+        wave.anchors[1].handle_in = Some(Offset::new(-5.0, -10.0));
+        wave.anchors[1].handle_out = Some(Offset::new(5.0, 10.0));
+        wave.anchors[2].handle_in = Some(Offset::new(-5.0, -10.0));
+
+        let result = wave.stroke(&StrokeStyle::new(2.0).with_cap(LineCap::Round), 0.05);
+
+        assert_eq!(result.len(), 1);
+        assert!(result[0].closed);
+        assert!(result[0].anchors.len() <= 20, "{}", result[0].anchors.len());
+    }
+}
@@ -0,0 +1,2 @@
+pub mod style;
+pub(crate) mod builder;
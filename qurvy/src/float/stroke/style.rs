@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+/// The shape drawn at the open ends of a stroked path.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineCap {
+    /// The stroke ends exactly at the end point.
+    #[default]
+    Butt,
+    /// A half circle around the end point.
+    Round,
+    /// The stroke is extended by half of the width.
+    Square,
+}
+
+/// The shape drawn on the outer side of a corner anchor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineJoin {
+    /// Sharp corner, falls back to `Bevel` when it is longer than the miter limit.
+    #[default]
+    Miter,
+    /// A circular arc around the anchor.
+    Round,
+    /// A straight line between the two offset edges.
+    Bevel,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct StrokeStyle {
+    pub width: f64,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    /// Max ratio of the miter length to the stroke width.
+    pub miter_limit: f64,
+}
+
+impl StrokeStyle {
+    #[inline]
+    pub fn new(width: f64) -> Self {
+        Self {
+            width,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            miter_limit: 4.0,
+        }
+    }
+
+    #[inline]
+    pub fn with_cap(self, cap: LineCap) -> Self {
+        Self { cap, ..self }
+    }
+
+    #[inline]
+    pub fn with_join(self, join: LineJoin) -> Self {
+        Self { join, ..self }
+    }
+
+    #[inline]
+    pub fn with_miter_limit(self, miter_limit: f64) -> Self {
+        Self { miter_limit, ..self }
+    }
+}
+
+impl Default for StrokeStyle {
+    #[inline]
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
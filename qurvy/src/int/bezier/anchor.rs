@@ -2,14 +2,48 @@ use serde::{Deserialize, Serialize};
 use crate::convert::grid::Grid;
 use crate::convert::to_float::ToFloat;
 use crate::float::bezier::anchor::BezierAnchor;
+use crate::float::math::number::FloatNumber;
 use crate::int::math::offset::IntOffset;
 use crate::int::math::point::IntPoint;
 
+/// Describes how the two handles of an anchor are tied together.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnchorKind {
+    /// Handles are independent.
+    #[default]
+    Corner,
+    /// Handles stay collinear, each keeps its own length.
+    Smooth,
+    /// Handles stay collinear and have the same length.
+    Symmetric,
+    /// Handles are computed from the neighbour anchors (Catmull-Rom style).
+    Auto,
+}
+
+impl AnchorKind {
+    /// `Corner` is the default kind, it is not written to the serialized anchors.
+    #[inline]
+    pub fn is_corner(&self) -> bool {
+        *self == AnchorKind::Corner
+    }
+
+    /// The kind left after a handle was changed by an operation that keeps only its direction.
+    #[inline]
+    pub(crate) fn released(self) -> Self {
+        match self {
+            AnchorKind::Symmetric | AnchorKind::Auto => AnchorKind::Smooth,
+            _ => self,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct IntBezierAnchor {
     pub point: IntPoint,
     pub handle_in: Option<IntOffset>,
     pub handle_out: Option<IntOffset>,
+    #[serde(default, skip_serializing_if = "AnchorKind::is_corner")]
+    pub kind: AnchorKind,
 }
 
 impl IntBezierAnchor {
@@ -23,15 +57,147 @@ impl IntBezierAnchor {
     pub fn handle_out_point(&self) -> Option<IntPoint> {
         self.handle_out.map(|offset|self.point + offset)
     }
+
+    /// True if the point and both handle points are within `MAX_COORD`.
+    #[inline]
+    pub fn is_in_range(&self) -> bool {
+        let is_handle_in_range = |handle: Option<IntOffset>| handle.is_none_or(|h| {
+            match (self.point.x.checked_add(h.x), self.point.y.checked_add(h.y)) {
+                (Some(x), Some(y)) => IntPoint::new(x, y).is_in_range(),
+                _ => false,
+            }
+        });
+
+        self.point.is_in_range() && is_handle_in_range(self.handle_in) && is_handle_in_range(self.handle_out)
+    }
+
+    /// Sets the incoming handle and updates the outgoing one according to the anchor kind.
+    /// Editing a handle of an `Auto` anchor turns it into a `Smooth` one.
+    #[inline]
+    pub fn set_handle_in(&mut self, handle: IntOffset) {
+        self.handle_in = Some(handle);
+        if self.kind == AnchorKind::Auto {
+            self.kind = AnchorKind::Smooth;
+        }
+        self.handle_out = Self::constrained(self.kind, handle, self.handle_out);
+    }
+
+    /// Sets the outgoing handle and updates the incoming one according to the anchor kind.
+    /// Editing a handle of an `Auto` anchor turns it into a `Smooth` one.
+    #[inline]
+    pub fn set_handle_out(&mut self, handle: IntOffset) {
+        self.handle_out = Some(handle);
+        if self.kind == AnchorKind::Auto {
+            self.kind = AnchorKind::Smooth;
+        }
+        self.handle_in = Self::constrained(self.kind, handle, self.handle_in);
+    }
+
+    /// Changes the anchor kind. The outgoing handle is kept and the incoming one is adjusted.
+    #[inline]
+    pub fn set_kind(&mut self, kind: AnchorKind) {
+        self.kind = kind;
+        if let Some(handle) = self.handle_out {
+            self.handle_in = Self::constrained(kind, handle, self.handle_in);
+        } else if let Some(handle) = self.handle_in {
+            self.handle_out = Self::constrained(kind, handle, self.handle_out);
+        }
+    }
+
+    /// Absorbs the next anchor if the spline to it collapses to a point, returns true then.
+    /// Two handles that were not tied together leave a `Corner`.
+    pub(crate) fn merge(&mut self, next: &IntBezierAnchor) -> bool {
+        if self.point != next.point || self.handle_out.is_some() || next.handle_in.is_some() {
+            return false;
+        }
+        self.handle_out = next.handle_out;
+        if self.handle_in.is_some() && self.handle_out.is_some() {
+            self.kind = AnchorKind::Corner;
+        }
+
+        true
+    }
+
+    fn constrained(kind: AnchorKind, lead: IntOffset, other: Option<IntOffset>) -> Option<IntOffset> {
+        match kind {
+            AnchorKind::Corner | AnchorKind::Auto => other,
+            AnchorKind::Symmetric => Some(-lead),
+            AnchorKind::Smooth => {
+                let other = other?;
+                let lead_len = lead.length();
+                if lead_len == 0.0 {
+                    return Some(other);
+                }
+                let s = -other.length() / lead_len;
+                let x = (lead.x as f64 * s).round() as i64;
+                let y = (lead.y as f64 * s).round() as i64;
+                Some(IntOffset::new(x, y))
+            }
+        }
+    }
 }
 
-impl ToFloat<BezierAnchor> for IntBezierAnchor {
+impl<T: FloatNumber> ToFloat<BezierAnchor<T>> for IntBezierAnchor {
     #[inline]
-    fn to_float(&self, grid: &Grid) -> BezierAnchor {
+    fn to_float(&self, grid: &Grid) -> BezierAnchor<T> {
         BezierAnchor {
             point: self.point.to_float(grid),
             handle_in: self.handle_in.map(|handle| handle.to_float(grid)),
             handle_out: self.handle_out.map(|handle| handle.to_float(grid)),
+            kind: self.kind,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::bezier::anchor::{AnchorKind, IntBezierAnchor};
+    use crate::int::math::offset::IntOffset;
+    use crate::int::math::point::IntPoint;
+
+    fn anchor(kind: AnchorKind) -> IntBezierAnchor {
+        IntBezierAnchor {
+            point: IntPoint::new(0, 0),
+            handle_in: Some(IntOffset::new(-100, 0)),
+            handle_out: Some(IntOffset::new(50, 0)),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_00() {
+        let mut a = anchor(AnchorKind::Corner);
+        a.set_handle_in(IntOffset::new(0, 30));
+        assert_eq!(a.handle_in, Some(IntOffset::new(0, 30)));
+        assert_eq!(a.handle_out, Some(IntOffset::new(50, 0)));
+    }
+
+    #[test]
+    fn test_01() {
+        let mut a = anchor(AnchorKind::Smooth);
+        a.set_handle_in(IntOffset::new(0, 30));
+        assert_eq!(a.handle_out, Some(IntOffset::new(0, -50)));
+    }
+
+    #[test]
+    fn test_02() {
+        let mut a = anchor(AnchorKind::Symmetric);
+        a.set_handle_out(IntOffset::new(30, 40));
+        assert_eq!(a.handle_in, Some(IntOffset::new(-30, -40)));
+    }
+
+    #[test]
+    fn test_03() {
+        let mut a = anchor(AnchorKind::Auto);
+        a.set_handle_out(IntOffset::new(0, 10));
+        assert_eq!(a.kind, AnchorKind::Smooth);
+        assert_eq!(a.handle_in, Some(IntOffset::new(0, -100)));
+    }
+
+    #[test]
+    fn test_04() {
+        let mut a = anchor(AnchorKind::Corner);
+        a.set_kind(AnchorKind::Symmetric);
+        assert_eq!(a.handle_in, Some(IntOffset::new(-50, 0)));
+    }
+}
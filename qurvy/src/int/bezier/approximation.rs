@@ -1,179 +1,72 @@
-use crate::data::list::{EMPTY_REF, LinkList};
-use crate::int::bezier::spline::IntCADSpline;
+use crate::bezier::vector::SplineVector;
+use crate::float::math::point::Point;
 use crate::int::math::point::IntPoint;
 
-pub trait IntApproximation {
-    fn approximate(&self, min_cos: u32, min_len: u32) -> Vec<IntShort>;
-    fn approximate_points(&self, min_cos: u32, min_len: u32) -> Vec<IntPoint>;
-}
+pub use crate::bezier::approximation::Approximation as IntApproximation;
 
-impl<Spline: IntCADSpline> IntApproximation for Spline {
-    #[inline]
-    fn approximate(&self, min_cos: u32, min_len: u32) -> Vec<IntShort> {
-        debug_assert!(min_cos <= 1024);
-        Solver::approximate(self, min_cos, min_len)
-    }
+/// A chord of the spline, its direction is normalized to the length of 1024.
+pub type IntShort = crate::bezier::approximation::Short<IntPoint>;
 
+impl IntShort {
+    /// True if the chord is too short to be split again, whatever the `min_cos` test says.
     #[inline]
-    fn approximate_points(&self, min_cos: u32, min_len: u32) -> Vec<IntPoint> {
-        let shorts = Solver::approximate(self, min_cos, min_len);
-        let mut points: Vec<_> = shorts.iter().map(|s| s.a).collect();
-        points.push(shorts.last().unwrap().b);
-
-        points
+    pub fn is_len_limited(&self, min_len: u32) -> bool {
+        (self.b - self.a).is_small(min_len.ilog2())
     }
 }
 
-#[derive(Copy, Clone)]
-pub struct IntShort {
-    pub step: usize,
-    pub split_factor: u32,
-    pub dir: IntPoint,
-    pub a: IntPoint,
-    pub b: IntPoint,
-}
+impl SplineVector for IntPoint {
+    type Scalar = u32;
+    /// The cos scaled by 2^20 and the log2 of `min_len`.
+    type Limits = (i64, u32);
 
-struct Solver<'a, Spline> {
-    min_cos: i64,
-    st_dir: IntPoint,
-    ed_dir: IntPoint,
-    min_len_power: u32,
-    spline: &'a Spline,
-    segments: LinkList<IntShort>,
-}
+    const MAX_COS: u32 = 1024;
 
-impl<'a, Spline: IntCADSpline> Solver<'a, Spline> {
     #[inline]
-    fn approximate(spline: &Spline, min_cos: u32, min_len: u32) -> Vec<IntShort> {
-        let min_len_power = min_len.ilog2();
-        let st_dir = spline.start_dir();
-        let ed_dir = spline.end_dir();
-
-        let segments = LinkList::new(vec![IntShort {
-            step: 0,
-            split_factor: 0,
-            dir: (spline.end() - spline.start()).normalized_10bit(),
-            a: spline.start(),
-            b: spline.end(),
-        }]);
-
-        Solver {
-            min_cos: (min_cos << 10) as i64,
-            st_dir,
-            ed_dir,
-            min_len_power,
-            spline,
-            segments,
-        }
-        .process()
+    fn zero() -> Self {
+        IntPoint::zero()
     }
 
     #[inline]
-    fn process(&mut self) -> Vec<IntShort> {
-        let mut buffer = Vec::with_capacity(16);
-        buffer.push(0);
-
-        let mut to_split = Vec::with_capacity(16);
-
-        while !buffer.is_empty() {
-            for index in buffer.iter() {
-                if self.split_test(*index) {
-                    to_split.push(*index);
-                }
-            }
-
-            buffer.clear();
-            for &index in to_split.iter() {
-                self.split(index, &mut buffer);
-            }
-            to_split.clear();
-        }
-
-        let mut shorts = Vec::with_capacity(self.segments.len());
-        let mut index = 0;
-        while index != EMPTY_REF {
-            let node = self.segments.get(index);
-            shorts.push(node.item);
-            index = node.next
-        }
-
-        shorts
+    fn limits(min_cos: u32, min_len: u32) -> (i64, u32) {
+        // the directions are normalized to 1024, so their dot product is the cos scaled by 2^20
+        ((min_cos as i64) << 10, min_len.ilog2())
     }
 
-    fn split_test(&self, index: u32) -> bool {
-        let node = self.segments.get(index);
-        let prev = node.prev;
-        let next = node.next;
-        let dir = node.item.dir;
-        let prev_dir = if prev != EMPTY_REF {
-            self.segments.get(prev).item.dir
-        } else {
-            self.st_dir
-        };
-
-        let prev_dot_product = dir.dot_product(&prev_dir);
-        if prev_dot_product < self.min_cos {
-            return true;
-        }
-
-        let next_dir = if next != EMPTY_REF {
-            self.segments.get(next).item.dir
-        } else {
-            self.ed_dir
-        };
-
-        let next_dot_product = dir.dot_product(&next_dir);
-        next_dot_product < self.min_cos
+    #[inline]
+    fn direction(&self) -> Self {
+        self.try_normalized_10bit().unwrap_or(IntPoint::zero())
     }
 
-    fn split(&mut self, index: u32, result: &mut Vec<u32>) {
-        let short = self.segments.get(index).item;
-
-        let split_factor = short.split_factor + 1;
-        let m = self.spline.split_at(short.step + 1, split_factor);
-        let ma = m - short.a;
-        let bm = short.b - m;
-
-        let s0 = IntShort {
-            step: short.step << 1,
-            split_factor,
-            dir: ma.normalized_10bit(),
-            a: short.a,
-            b: m,
-        };
-
-        let s1 = IntShort {
-            step: (short.step + 1) << 1,
-            split_factor,
-            dir: bm.normalized_10bit(),
-            a: m,
-            b: short.b,
-        };
-
-        let (i0, i1) = self.segments.split_at(index, s0, s1);
+    #[inline]
+    fn is_turn(&self, other: &Self, limits: &(i64, u32)) -> bool {
+        self.dot_product(other) < limits.0
+    }
 
-        if !ma.is_small(self.min_len_power) {
-            result.push(i0)
-        }
+    #[inline]
+    fn is_short(&self, limits: &(i64, u32)) -> bool {
+        self.is_small(limits.1)
+    }
 
-        if !bm.is_small(self.min_len_power) {
-            result.push(i1)
-        }
+    #[inline]
+    fn length(&self) -> f64 {
+        Point::from(*self).length()
     }
 }
 
 impl IntPoint {
     #[inline]
     fn is_small(&self, power: u32) -> bool {
-        debug_assert!(power < 30);
-        let xx = self.x.unsigned_abs().ilog2();
-        let yy = self.y.unsigned_abs().ilog2();
+        // `power` is the log2 of an u32, so it is below 32 and the sum of the squares fits i64
+        debug_assert!(power < 32);
+        // a zero coordinate has no logarithm, it is small for any power
+        let xx = self.x.unsigned_abs().checked_ilog2().unwrap_or(0);
+        let yy = self.y.unsigned_abs().checked_ilog2().unwrap_or(0);
         let is_x_small = xx < power;
         let is_y_small = yy < power;
         if is_x_small && is_y_small {
-            // we safe to use i64
             let len = (self.x.pow(2) + self.y.pow(2)).isqrt();
-            len.ilog2() < power
+            len.checked_ilog2().unwrap_or(0) < power
         } else {
             false
         }
@@ -183,6 +76,8 @@ impl IntPoint {
 #[cfg(test)]
 mod tests {
     use crate::int::bezier::approximation::IntApproximation;
+    use crate::bezier::spline::CADSpline;
+    use crate::int::bezier::spline_cube::IntCubeSpline;
     use crate::int::bezier::spline_quad::IntQuadSpline;
     use crate::int::math::point::IntPoint;
 
@@ -199,6 +94,14 @@ mod tests {
         assert_eq!(p.is_small(8), true);
     }
 
+    #[test]
+    fn test_05() {
+        assert!(!IntPoint::new(0, 100).is_small(6));
+        assert!(IntPoint::new(0, 100).is_small(7));
+        assert!(IntPoint::new(-100, 0).is_small(8));
+        assert!(IntPoint::new(0, 0).is_small(1));
+    }
+
     #[test]
     fn test_01() {
         let spline = IntQuadSpline {
@@ -237,4 +140,96 @@ mod tests {
         let shorts = spline.approximate(900, 4);
         assert_eq!(shorts.len(), 8);
     }
+
+    #[test]
+    fn test_04() {
+        let spline = IntQuadSpline {
+            a: IntPoint::new(0, 0),
+            am: IntPoint::new(0, 50),
+            bm: IntPoint::new(100, 50),
+            b: IntPoint::new(100, 0),
+        };
+
+        let shorts = spline.approximate(1000, 32);
+        assert!(shorts.iter().any(|s| s.is_len_limited(32)));
+        assert!(!shorts[0].is_len_limited(2));
+    }
+
+    #[test]
+    fn test_06() {
+        // the spline turns back sharply, some split points fall on the ends of their chords
+        let spline = IntQuadSpline {
+            a: IntPoint::new(18433, 781),
+            am: IntPoint::new(21775, 191),
+            bm: IntPoint::new(-12333, -16732),
+            b: IntPoint::new(-7827, -14476),
+        };
+
+        let shorts = spline.approximate(944, 4);
+        assert!(shorts.iter().all(|s| s.dir != IntPoint::zero()));
+    }
+
+    #[test]
+    fn test_07() {
+        // the rounded points near the end never come closer to it than min_len
+        let spline = IntQuadSpline {
+            a: IntPoint::new(10914, 11573),
+            am: IntPoint::new(13565, 7542),
+            bm: IntPoint::new(-1130, -12896),
+            b: IntPoint::new(-514, -12812),
+        };
+
+        let shorts = spline.approximate(947, 4);
+        assert!(shorts.iter().all(|s| s.split_factor < 32));
+        assert!(shorts.iter().all(|s| s.a != s.b));
+    }
+
+    #[test]
+    fn test_08() {
+        // zero length control vectors, the tangents come from the next control points
+        let spline = IntQuadSpline {
+            a: IntPoint::new(0, 0),
+            am: IntPoint::new(0, 0),
+            bm: IntPoint::new(100, 100),
+            b: IntPoint::new(100, 100),
+        };
+        assert_eq!(spline.start_dir(), IntPoint::new(726, 726));
+        assert_eq!(spline.end_dir(), IntPoint::new(726, 726));
+
+        let points = spline.approximate_points(1000, 4);
+        assert_eq!(points, vec![IntPoint::new(0, 0), IntPoint::new(100, 100)]);
+
+        let spline = IntCubeSpline {
+            a: IntPoint::new(0, 0),
+            m: IntPoint::new(200, 0),
+            b: IntPoint::new(200, 0),
+        };
+        assert_eq!(spline.end_dir(), IntPoint::new(1024, 0));
+        assert!(spline.approximate(1000, 4).iter().all(|s| s.dir != IntPoint::zero()));
+    }
+
+    #[test]
+    fn test_09() {
+        // a loop back to the start point
+        let spline = IntQuadSpline {
+            a: IntPoint::new(0, 0),
+            am: IntPoint::new(100, 100),
+            bm: IntPoint::new(-100, 100),
+            b: IntPoint::new(0, 0),
+        };
+        let points = spline.approximate_points(1000, 4);
+        assert!(points.len() > 8);
+        assert_eq!(points.first(), Some(&IntPoint::new(0, 0)));
+        assert_eq!(points.last(), Some(&IntPoint::new(0, 0)));
+        assert!(points.iter().any(|p| p.y >= 70));
+
+        // a spline collapsed to a point
+        let spline = IntCubeSpline {
+            a: IntPoint::new(5, 5),
+            m: IntPoint::new(5, 5),
+            b: IntPoint::new(5, 5),
+        };
+        assert_eq!(spline.start_dir(), IntPoint::zero());
+        assert_eq!(spline.approximate_points(1000, 4), vec![IntPoint::new(5, 5); 2]);
+    }
 }
@@ -0,0 +1,155 @@
+use crate::float::math::point::Point;
+use crate::int::bezier::spline::IntSpline;
+use crate::int::math::point::IntPoint;
+
+/// A spline parameter `t = p / q` within `[0, 1]`, evaluated without intermediate rounding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RationalT {
+    p: u64,
+    q: u64,
+}
+
+impl RationalT {
+    /// The largest denominator: a cubic spline weights its control points by `q^3`,
+    /// with coordinates within `MAX_COORD` that still fits `i128`.
+    pub const MAX_DENOMINATOR: u64 = 1 << 28;
+
+    /// `None` if `q` is zero or above `MAX_DENOMINATOR`, or `p > q`.
+    #[inline]
+    pub fn new(p: u64, q: u64) -> Option<Self> {
+        (q != 0 && q <= Self::MAX_DENOMINATOR && p <= q).then_some(Self { p, q })
+    }
+
+    #[inline]
+    pub fn p(&self) -> u64 {
+        self.p
+    }
+
+    #[inline]
+    pub fn q(&self) -> u64 {
+        self.q
+    }
+
+    #[inline]
+    pub fn to_f64(&self) -> f64 {
+        self.p as f64 / self.q as f64
+    }
+}
+
+/// A point of the exact evaluation, rounded once to the nearest int point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExactPoint {
+    pub point: IntPoint,
+    /// The exact point minus `point`, every coordinate is within `[-0.5, 0.5]`.
+    pub residual: Point,
+}
+
+/// The point at `t` of the Bézier curve with the control points `points` (up to 4 of them).
+/// The Bernstein sum is computed over the common denominator `q^n` in `i128`, so the result
+/// does not depend on the platform or on the order of the operations.
+pub(crate) fn bezier_point(points: &[IntPoint], t: RationalT) -> ExactPoint {
+    debug_assert!(!points.is_empty() && points.len() <= 4);
+    let n = points.len() - 1;
+    let p = t.p as i128;
+    let s = (t.q - t.p) as i128;
+
+    let mut x = 0i128;
+    let mut y = 0i128;
+    for (i, point) in points.iter().enumerate() {
+        let weight = BINOMIAL[n][i] * s.pow((n - i) as u32) * p.pow(i as u32);
+        x += weight * point.x as i128;
+        y += weight * point.y as i128;
+    }
+
+    let den = (t.q as i128).pow(n as u32);
+    let (rx, ex) = round_div(x, den);
+    let (ry, ey) = round_div(y, den);
+
+    ExactPoint {
+        point: IntPoint::new(rx as i64, ry as i64),
+        residual: Point::new(ex, ey),
+    }
+}
+
+const BINOMIAL: [[i128; 4]; 4] = [[1, 0, 0, 0], [1, 1, 0, 0], [1, 2, 1, 0], [1, 3, 3, 1]];
+
+/// `num / den` rounded to the nearest, ties away from zero, and the residual of the rounding.
+#[inline]
+fn round_div(num: i128, den: i128) -> (i128, f64) {
+    let half = den >> 1;
+    let value = if num >= 0 {
+        (num + half) / den
+    } else {
+        (num - half) / den
+    };
+    let rest = num - value * den;
+
+    (value, rest as f64 / den as f64)
+}
+
+impl IntSpline {
+    /// The control points from the start to the end.
+    #[inline]
+    pub(crate) fn control_points(&self) -> Vec<IntPoint> {
+        match self {
+            IntSpline::Line(s) => vec![s.a, s.b],
+            IntSpline::Cube(s) => vec![s.a, s.m, s.b],
+            IntSpline::Quad(s) => vec![s.a, s.am, s.bm, s.b],
+        }
+    }
+
+    /// The exact point at `t`, see `bezier_point`.
+    #[inline]
+    pub(crate) fn exact_point_at(&self, t: RationalT) -> ExactPoint {
+        bezier_point(&self.control_points(), t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::bezier::exact::{bezier_point, round_div, RationalT};
+    use crate::int::math::point::IntPoint;
+    use crate::int::math::range::MAX_COORD;
+
+    #[test]
+    fn test_00() {
+        assert_eq!(round_div(7, 2), (4, -0.5));
+        assert_eq!(round_div(-7, 2), (-4, 0.5));
+        assert_eq!(round_div(5, 3), (2, -1.0 / 3.0));
+        assert_eq!(round_div(-5, 3), (-2, 1.0 / 3.0));
+
+        assert!(RationalT::new(1, 0).is_none());
+        assert!(RationalT::new(2, 1).is_none());
+        assert!(RationalT::new(0, RationalT::MAX_DENOMINATOR + 1).is_none());
+    }
+
+    #[test]
+    fn test_01() {
+        // at t = 1/3 the weights are (8, 12, 6, 1) / 27, x = 630 / 27 and y = 1800 / 27
+        let points = [IntPoint::new(0, 0), IntPoint::new(0, 100), IntPoint::new(90, 100), IntPoint::new(90, 0)];
+        let t = RationalT::new(1, 3).unwrap();
+        let e = bezier_point(&points, t);
+        assert_eq!(e.point, IntPoint::new(23, 67));
+        assert!((e.residual.x - 1.0 / 3.0).abs() < 1e-12);
+        assert!((e.residual.y + 1.0 / 3.0).abs() < 1e-12);
+
+        assert_eq!(bezier_point(&points, RationalT::new(0, 7).unwrap()).point, points[0]);
+        assert_eq!(bezier_point(&points, RationalT::new(7, 7).unwrap()).point, points[3]);
+    }
+
+    #[test]
+    fn test_02() {
+        // the widest coordinates and the largest denominator do not overflow
+        let m = MAX_COORD;
+        let points = [IntPoint::new(-m, m), IntPoint::new(m, m), IntPoint::new(m, -m), IntPoint::new(-m, -m)];
+        let q = RationalT::MAX_DENOMINATOR;
+        let t = RationalT::new(q / 2, q).unwrap();
+        let e = bezier_point(&points, t);
+        assert_eq!(e.point, IntPoint::new(m / 2, 0));
+        assert_eq!(e.residual.x, 0.0);
+
+        let t = RationalT::new(q - 1, q).unwrap();
+        let e = bezier_point(&points, t);
+        assert!(e.residual.x.abs() <= 0.5 && e.residual.y.abs() <= 0.5);
+    }
+}
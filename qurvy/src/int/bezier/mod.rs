@@ -4,6 +4,5 @@ pub(crate) mod spline;
 pub(crate) mod spline_line;
 pub(crate) mod spline_cube;
 pub(crate) mod spline_quad;
-pub(crate) mod iter;
-mod approximation;
-pub(crate) mod length;
\ No newline at end of file
+pub mod approximation;
+pub mod exact;
@@ -1,20 +1,38 @@
 use crate::convert::grid::Grid;
 use crate::convert::to_float::ToFloat;
+use crate::error::Error;
 use crate::float::bezier::path::BezierPath;
-use crate::int::bezier::anchor::IntBezierAnchor;
+use crate::float::math::number::FloatNumber;
+use crate::int::bezier::anchor::{AnchorKind, IntBezierAnchor};
+use crate::int::bezier::approximation::IntShort;
+use crate::int::bezier::exact::{bezier_point, ExactPoint, RationalT};
 use crate::int::bezier::spline::IntSpline;
+use crate::int::math::offset::IntOffset;
 use crate::int::math::point::IntPoint;
+use crate::int::math::range::RangeError;
+use crate::int::math::rect::IntRect;
 use serde::{Deserialize, Serialize};
 
+/// The finest sampling `project` refines to, `2^-16` of a spline.
+const MAX_SPLIT_FACTOR: u32 = 16;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IntBezierPath {
     pub anchors: Vec<IntBezierAnchor>,
     pub closed: bool,
 }
 
-impl ToFloat<BezierPath> for IntBezierPath {
+/// A point on the path: the parameter `t` of the spline that starts at anchor `spline_index`.
+#[derive(Debug, Clone, Copy)]
+pub struct PathPosition {
+    pub spline_index: usize,
+    pub t: f64,
+    pub point: IntPoint,
+}
+
+impl<T: FloatNumber> ToFloat<BezierPath<T>> for IntBezierPath {
     #[inline]
-    fn to_float(&self, grid: &Grid) -> BezierPath {
+    fn to_float(&self, grid: &Grid) -> BezierPath<T> {
         BezierPath {
             anchors: self.anchors.iter().map(|a| a.to_float(grid)).collect(),
             closed: self.closed,
@@ -45,9 +63,20 @@ impl IntBezierPath {
         points
     }
 
+    /// The chords of all splines, as `approximate_points` builds them.
+    #[inline]
+    pub fn approximate(&self, min_cos: u32, min_len: u32) -> Vec<IntShort> {
+        let mut shorts = Vec::with_capacity(self.anchors.len() * 16);
+        for spline in self.splines() {
+            shorts.append(&mut spline.approximate(min_cos, min_len));
+        }
+
+        shorts
+    }
+
     #[inline]
-    pub fn avg_length(&self, min_cos: u32, min_len: u32) -> u128 {
-        let mut len = 0u128;
+    pub fn avg_length(&self, min_cos: u32, min_len: u32) -> f64 {
+        let mut len = 0.0;
         for spline in self.splines() {
             len += spline.avg_length(min_cos, min_len);
         }
@@ -55,6 +84,397 @@ impl IntBezierPath {
         len
     }
 
+    /// `approximate_points` that fails with `Error::EmptyPath`, `InvalidParameter` for `min_cos` above 1024
+    /// or a zero `min_len`, `OutOfRange` or `DegenerateSpline` instead of panicking or looping.
+    pub fn try_approximate_points(&self, min_cos: u32, min_len: u32) -> Result<Vec<IntPoint>, Error> {
+        self.check_approximation(min_cos, min_len)?;
+        Ok(self.approximate_points(min_cos, min_len))
+    }
+
+    /// `approximate` with the errors of `try_approximate_points`.
+    pub fn try_approximate(&self, min_cos: u32, min_len: u32) -> Result<Vec<IntShort>, Error> {
+        self.check_approximation(min_cos, min_len)?;
+        Ok(self.approximate(min_cos, min_len))
+    }
+
+    /// `avg_length` with the errors of `try_approximate_points`.
+    pub fn try_avg_length(&self, min_cos: u32, min_len: u32) -> Result<f64, Error> {
+        self.check_approximation(min_cos, min_len)?;
+        Ok(self.avg_length(min_cos, min_len))
+    }
+
+    /// The approximation needs `min_cos` within `[0, 1024]`, a positive `min_len`, coordinates
+    /// in range and a direction for every spline.
+    fn check_approximation(&self, min_cos: u32, min_len: u32) -> Result<(), Error> {
+        if self.anchors.is_empty() {
+            return Err(Error::EmptyPath);
+        }
+        if min_cos > 1024 {
+            return Err(Error::InvalidParameter("min_cos"));
+        }
+        if min_len == 0 {
+            return Err(Error::InvalidParameter("min_len"));
+        }
+        self.validate()?;
+        match self.splines().position(|s| s.is_point()) {
+            Some(spline_index) => Err(Error::DegenerateSpline { spline_index }),
+            None => Ok(()),
+        }
+    }
+
+    /// `insert_anchor` that fails with `Error::EmptyPath`, `InvalidParameter` for a `t` outside of `[0, 1]`
+    /// or a missing spline, or `OutOfRange` instead of inserting a wrapped anchor.
+    pub fn try_insert_anchor(&mut self, spline_index: usize, t: f64) -> Result<usize, Error> {
+        // false for NaN as well
+        if !(0.0..=1.0).contains(&t) {
+            return Err(Error::InvalidParameter("t"));
+        }
+        self.check_spline(spline_index)?;
+        self.insert_anchor(spline_index, t).ok_or(Error::InvalidParameter("spline_index"))
+    }
+
+    /// `insert_anchor_exact` that fails with `Error::EmptyPath`, `InvalidParameter` for a missing spline,
+    /// or `OutOfRange` where the exact products would overflow.
+    pub fn try_insert_anchor_exact(&mut self, spline_index: usize, t: RationalT) -> Result<usize, Error> {
+        self.check_spline(spline_index)?;
+        self.insert_anchor_exact(spline_index, t).ok_or(Error::InvalidParameter("spline_index"))
+    }
+
+    /// `exact_point_at` with the errors of `try_insert_anchor_exact`.
+    pub fn try_exact_point_at(&self, spline_index: usize, t: RationalT) -> Result<ExactPoint, Error> {
+        self.check_spline(spline_index)?;
+        self.exact_point_at(spline_index, t).ok_or(Error::InvalidParameter("spline_index"))
+    }
+
+    /// `nearest_position` that fails with `Error::EmptyPath` for a path without splines, `InvalidParameter`
+    /// for a `split_factor` above 16 or a `point` out of range, or `OutOfRange` for the path.
+    pub fn try_nearest_position(&self, point: IntPoint, split_factor: u32) -> Result<PathPosition, Error> {
+        self.check_projection(point, split_factor)?;
+        self.nearest_position(point, split_factor).ok_or(Error::EmptyPath)
+    }
+
+    /// `project` with the errors of `try_nearest_position`.
+    pub fn try_project(&self, point: IntPoint, split_factor: u32) -> Result<PathPosition, Error> {
+        self.check_projection(point, split_factor)?;
+        self.project(point, split_factor).ok_or(Error::EmptyPath)
+    }
+
+    /// `to_cubic` that fails with `Error::OutOfRange` where the elevation would overflow.
+    pub fn try_to_cubic(&self) -> Result<Self, Error> {
+        self.validate()?;
+        Ok(self.to_cubic())
+    }
+
+    /// The exact split and evaluation need coordinates in range and an existing spline.
+    fn check_spline(&self, spline_index: usize) -> Result<(), Error> {
+        if self.anchors.is_empty() {
+            return Err(Error::EmptyPath);
+        }
+        self.validate()?;
+        if spline_index >= self.splines().count() {
+            return Err(Error::InvalidParameter("spline_index"));
+        }
+        Ok(())
+    }
+
+    /// The sampling needs at most `2^MAX_SPLIT_FACTOR` samples per spline and the squared
+    /// distances need the path and `point` in range.
+    fn check_projection(&self, point: IntPoint, split_factor: u32) -> Result<(), Error> {
+        if self.anchors.is_empty() {
+            return Err(Error::EmptyPath);
+        }
+        if split_factor > MAX_SPLIT_FACTOR {
+            return Err(Error::InvalidParameter("split_factor"));
+        }
+        if !point.is_in_range() {
+            return Err(Error::InvalidParameter("point"));
+        }
+        self.validate()?;
+        Ok(())
+    }
+
+    /// Checks that every anchor and handle point is within `MAX_COORD`, the range
+    /// the int algorithms do not overflow in.
+    pub fn validate(&self) -> Result<(), RangeError> {
+        match self.anchors.iter().position(|a| !a.is_in_range()) {
+            Some(anchor_index) => Err(RangeError { anchor_index }),
+            None => Ok(()),
+        }
+    }
+
+    /// The tight bounding box of the curve as `(min, max)`, the handles outside of it are ignored.
+    /// `None` for a path without anchors.
+    pub fn bounds(&self) -> Option<(IntPoint, IntPoint)> {
+        let first = self.anchors.first()?;
+        let mut rect = IntRect::with_min_max(first.point, first.point);
+        for spline in self.splines() {
+            let boundary = spline.boundary();
+            rect.add_point(&boundary.min);
+            rect.add_point(&boundary.max);
+        }
+
+        Some((rect.min, rect.max))
+    }
+
+    /// Returns the path without zero length handles, and with the neighbour anchors that share
+    /// a point and have no handles towards each other merged into one. The merged anchor keeps
+    /// the incoming handle of the first and the outgoing handle of the second.
+    /// A spline that loops back to its start point is a real curve, its anchors are kept.
+    pub fn canonicalized(&self) -> Self {
+        let mut anchors: Vec<IntBezierAnchor> = Vec::with_capacity(self.anchors.len());
+        for anchor in self.anchors.iter() {
+            let mut anchor = *anchor;
+            anchor.handle_in = anchor.handle_in.filter(|h| !h.is_zero());
+            anchor.handle_out = anchor.handle_out.filter(|h| !h.is_zero());
+            if let Some(last) = anchors.last_mut()
+                && last.merge(&anchor)
+            {
+                continue;
+            }
+            anchors.push(anchor);
+        }
+
+        if self.closed && anchors.len() > 1 {
+            let first = anchors[0];
+            let mut last = anchors[anchors.len() - 1];
+            if last.merge(&first) {
+                anchors[0] = last;
+                anchors.pop();
+            }
+        }
+
+        Self { anchors, closed: self.closed }
+    }
+
+    /// Returns the path where every spline has two control points.
+    /// The elevation is exact up to the rounding of the new control points.
+    pub fn to_cubic(&self) -> Self {
+        let mut path = self.clone();
+        let n = self.anchors.len();
+        for (i, spline) in self.splines().enumerate() {
+            if let IntSpline::Quad(_) = spline {
+                continue;
+            }
+            let quad = spline.elevated();
+
+            let a = &mut path.anchors[i];
+            a.handle_out = Some((quad.am - quad.a).into());
+            a.kind = a.kind.released();
+
+            let b = &mut path.anchors[(i + 1) % n];
+            b.handle_in = Some((quad.bm - quad.b).into());
+            b.kind = b.kind.released();
+        }
+
+        path
+    }
+
+    /// Marks every anchor as `Auto` and computes its handles from the anchor points alone.
+    pub fn auto_smooth(&mut self) {
+        for anchor in self.anchors.iter_mut() {
+            anchor.kind = AnchorKind::Auto;
+        }
+        self.update_auto_handles();
+    }
+
+    /// Recomputes the handles of all `Auto` anchors with Catmull-Rom tangents.
+    /// Must be called after any anchor point of the path is moved.
+    pub fn update_auto_handles(&mut self) {
+        let n = self.anchors.len();
+        if n < 2 {
+            return;
+        }
+
+        for i in 0..n {
+            if self.anchors[i].kind != AnchorKind::Auto {
+                continue;
+            }
+
+            let is_first = i == 0 && !self.closed;
+            let is_last = i + 1 == n && !self.closed;
+
+            let prev = if i > 0 { i - 1 } else if self.closed { n - 1 } else { i };
+            let next = if i + 1 < n { i + 1 } else if self.closed { 0 } else { i };
+
+            let tangent = self.anchors[next].point - self.anchors[prev].point;
+            let handle = IntOffset::new(tangent.x / 6, tangent.y / 6);
+
+            let anchor = &mut self.anchors[i];
+            if handle.is_zero() {
+                anchor.handle_in = None;
+                anchor.handle_out = None;
+            } else {
+                anchor.handle_in = if is_first { None } else { Some(-handle) };
+                anchor.handle_out = if is_last { None } else { Some(handle) };
+            }
+        }
+    }
+
+    /// Finds the closest of `2^split_factor` regular samples per spline to `point`.
+    pub fn nearest_position(&self, point: IntPoint, split_factor: u32) -> Option<PathPosition> {
+        let mut min_dist = i128::MAX;
+        let mut result = None;
+        let count = 1 << split_factor;
+        for (spline_index, spline) in self.splines().enumerate() {
+            let mut samples = spline.regular_points(split_factor);
+            samples.push(spline.end());
+            for (step, &sample) in samples.iter().enumerate() {
+                let dx = (sample.x - point.x) as i128;
+                let dy = (sample.y - point.y) as i128;
+                let dist = dx * dx + dy * dy;
+                if dist < min_dist {
+                    min_dist = dist;
+                    let t = step as f64 / count as f64;
+                    result = Some(PathPosition { spline_index, t, point: sample });
+                }
+            }
+        }
+
+        result
+    }
+
+    /// The closest point of the path to `point`: the best of the `nearest_position` samples
+    /// is refined by halving the step around it until `2^-16` of the spline.
+    pub fn project(&self, point: IntPoint, split_factor: u32) -> Option<PathPosition> {
+        let coarse = self.nearest_position(point, split_factor)?;
+        if split_factor >= MAX_SPLIT_FACTOR {
+            return Some(coarse);
+        }
+
+        let spline = self.splines().nth(coarse.spline_index)?;
+        let sqr_dist = |p: IntPoint| {
+            let dx = (p.x - point.x) as i128;
+            let dy = (p.y - point.y) as i128;
+            dx * dx + dy * dy
+        };
+
+        let mut step = (coarse.t * (1usize << split_factor) as f64) as usize;
+        let mut best = coarse.point;
+        let mut min_dist = sqr_dist(best);
+        for level in split_factor + 1..=MAX_SPLIT_FACTOR {
+            step <<= 1;
+            let center = step;
+            for s in [center.saturating_sub(1), center + 1] {
+                if s > 1 << level {
+                    continue;
+                }
+                let p = spline.split_at(s, level);
+                let dist = sqr_dist(p);
+                if dist < min_dist {
+                    min_dist = dist;
+                    best = p;
+                    step = s;
+                }
+            }
+        }
+
+        let t = step as f64 / (1usize << MAX_SPLIT_FACTOR) as f64;
+        Some(PathPosition { spline_index: coarse.spline_index, t, point: best })
+    }
+
+    /// Splits the spline that starts at anchor `spline_index` at `t` (de Casteljau) and inserts a new anchor there.
+    /// The shape is kept up to the rounding of the new control points. Returns the index of the new anchor,
+    /// `None` if there is no such spline and nothing was inserted.
+    pub fn insert_anchor(&mut self, spline_index: usize, t: f64) -> Option<usize> {
+        self.insert_anchor_with(spline_index, |points| de_casteljau(points, t))
+    }
+
+    /// `insert_anchor` at the rational `t`: every new control point is computed exactly
+    /// and rounded once, so the result is reproducible on any platform.
+    pub fn insert_anchor_exact(&mut self, spline_index: usize, t: RationalT) -> Option<usize> {
+        self.insert_anchor_with(spline_index, |points| bezier_point(points, t).point)
+    }
+
+    /// The exact point at `t` of the spline that starts at anchor `spline_index`, rounded once.
+    /// `None` if there is no such spline. The path must be in range, see `validate`.
+    pub fn exact_point_at(&self, spline_index: usize, t: RationalT) -> Option<ExactPoint> {
+        Some(self.splines().nth(spline_index)?.exact_point_at(t))
+    }
+
+    /// `at` evaluates the curve of the given control points at the split parameter,
+    /// the de Casteljau points are the curves of the consecutive sub-ranges.
+    fn insert_anchor_with(&mut self, spline_index: usize, at: impl Fn(&[IntPoint]) -> IntPoint) -> Option<usize> {
+        let spline = self.splines().nth(spline_index)?;
+        let n = self.anchors.len();
+        let next = (spline_index + 1) % n;
+        let index = spline_index + 1;
+
+        let mut anchor = IntBezierAnchor {
+            point: IntPoint::zero(),
+            handle_in: None,
+            handle_out: None,
+            kind: AnchorKind::Corner,
+        };
+
+        match spline {
+            IntSpline::Line(s) => {
+                anchor.point = at(&[s.a, s.b]);
+            }
+            IntSpline::Cube(s) => {
+                let m0 = at(&[s.a, s.m]);
+                let m1 = at(&[s.m, s.b]);
+                anchor.point = at(&[s.a, s.m, s.b]);
+                if self.anchors[spline_index].handle_out.is_some() {
+                    // the control point stays on the outgoing sides
+                    self.anchors[spline_index].handle_out = Some((m0 - s.a).into());
+                    anchor.handle_out = Some((m1 - anchor.point).into());
+                } else {
+                    anchor.handle_in = Some((m0 - anchor.point).into());
+                    self.anchors[next].handle_in = Some((m1 - s.b).into());
+                }
+            }
+            IntSpline::Quad(s) => {
+                let p0 = at(&[s.a, s.am]);
+                let p2 = at(&[s.bm, s.b]);
+                let m0 = at(&[s.a, s.am, s.bm]);
+                let m1 = at(&[s.am, s.bm, s.b]);
+                anchor.point = at(&[s.a, s.am, s.bm, s.b]);
+                anchor.kind = AnchorKind::Smooth;
+                anchor.handle_in = Some((m0 - anchor.point).into());
+                anchor.handle_out = Some((m1 - anchor.point).into());
+                self.anchors[spline_index].handle_out = Some((p0 - s.a).into());
+                self.anchors[next].handle_in = Some((p2 - s.b).into());
+            }
+        }
+
+        let a = &mut self.anchors[spline_index];
+        a.kind = a.kind.released();
+        let b = &mut self.anchors[next];
+        b.kind = b.kind.released();
+
+        self.anchors.insert(index, anchor);
+
+        Some(index)
+    }
+
+    /// Removes the anchor, its neighbours are joined by one spline.
+    /// A path keeps at least two anchors, returns false if the anchor was not removed.
+    #[inline]
+    pub fn remove_anchor(&mut self, index: usize) -> bool {
+        if index >= self.anchors.len() || self.anchors.len() <= 2 {
+            return false;
+        }
+        self.anchors.remove(index);
+        true
+    }
+
+    /// Removes both handles of the anchor if it has any, otherwise adds smooth handles
+    /// along the Catmull-Rom tangent. This converts the adjacent splines between the line,
+    /// one control point and two control points forms.
+    pub fn toggle_handles(&mut self, index: usize) {
+        let anchor = if let Some(anchor) = self.anchors.get_mut(index) { anchor } else { return; };
+        if anchor.handle_in.is_some() || anchor.handle_out.is_some() {
+            anchor.handle_in = None;
+            anchor.handle_out = None;
+            anchor.kind = AnchorKind::Corner;
+            return;
+        }
+
+        anchor.kind = AnchorKind::Auto;
+        self.update_auto_handles();
+        self.anchors[index].kind = AnchorKind::Smooth;
+    }
+
     #[inline]
     pub(crate) fn splines(&self) -> impl Iterator<Item = IntSpline> + '_ {
         IntSplineIterator::new(self)
@@ -85,8 +505,8 @@ impl<'a> Iterator for IntSplineIterator<'a> {
         if self.i == self.path.anchors.len() {
             self.i += 1;
             return if self.path.closed {
-                let first = self.path.anchors.first().unwrap();
-                let last = self.path.anchors.last().unwrap();
+                let first = self.path.anchors.first()?;
+                let last = self.path.anchors.last()?;
                 Some(IntSpline::new(last, first))
             } else {
                 None
@@ -102,12 +522,32 @@ impl<'a> Iterator for IntSplineIterator<'a> {
     }
 }
 
+#[inline]
+fn lerp(a: IntPoint, b: IntPoint, t: f64) -> IntPoint {
+    let x = a.x as f64 + t * (b.x - a.x) as f64;
+    let y = a.y as f64 + t * (b.y - a.y) as f64;
+    IntPoint::new(x.round() as i64, y.round() as i64)
+}
+
+/// The point at `t` of the curve by repeated `lerp`, every level is rounded.
+fn de_casteljau(points: &[IntPoint], t: f64) -> IntPoint {
+    if let [p] = points {
+        return *p;
+    }
+    let n = points.len();
+    lerp(de_casteljau(&points[..n - 1], t), de_casteljau(&points[1..], t), t)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::int::bezier::anchor::IntBezierAnchor;
+    use crate::error::Error;
+    use crate::int::bezier::anchor::{AnchorKind, IntBezierAnchor};
+    use crate::int::bezier::exact::RationalT;
     use crate::int::bezier::path::IntBezierPath;
+    use crate::int::bezier::spline::IntSpline;
     use crate::int::math::offset::IntOffset;
     use crate::int::math::point::IntPoint;
+    use crate::int::math::range::{RangeError, MAX_COORD};
 
     #[test]
     fn test_00() {
@@ -117,21 +557,25 @@ mod tests {
                     point: IntPoint { x: -1000, y: 0 },
                     handle_in: Some(IntOffset { x: 0, y: -100 }),
                     handle_out: Some(IntOffset { x: 0, y: 100 }),
+                    kind: AnchorKind::Corner,
                 },
                 IntBezierAnchor {
                     point: IntPoint { x: 0, y: 1000 },
                     handle_in: Some(IntOffset { x: -100, y: 0 }),
                     handle_out: Some(IntOffset { x: 100, y: 0 }),
+                    kind: AnchorKind::Corner,
                 },
                 IntBezierAnchor {
                     point: IntPoint { x: 1000, y: 0 },
                     handle_in: Some(IntOffset { x: 0, y: 100 }),
                     handle_out: Some(IntOffset { x: 0, y: -100 }),
+                    kind: AnchorKind::Corner,
                 },
                 IntBezierAnchor {
                     point: IntPoint { x: 0, y: -1000 },
                     handle_in: Some(IntOffset { x: 100, y: 0 }),
                     handle_out: Some(IntOffset { x: -100, y: 0 }),
+                    kind: AnchorKind::Corner,
                 },
             ],
             closed: true,
@@ -141,4 +585,472 @@ mod tests {
 
         assert_eq!(points.len(), 16);
     }
+
+    #[test]
+    fn test_01() {
+        let mut path = IntBezierPath {
+            anchors: [(0, 0), (600, 600), (1200, 0)].iter().map(|&(x, y)| IntBezierAnchor {
+                point: IntPoint::new(x, y),
+                handle_in: None,
+                handle_out: None,
+                kind: AnchorKind::Corner,
+            }).collect(),
+            closed: false,
+        };
+
+        path.auto_smooth();
+
+        assert!(path.anchors[0].handle_in.is_none());
+        assert_eq!(path.anchors[0].handle_out, Some(IntOffset::new(100, 100)));
+        assert_eq!(path.anchors[1].handle_in, Some(IntOffset::new(-200, 0)));
+        assert_eq!(path.anchors[1].handle_out, Some(IntOffset::new(200, 0)));
+        assert_eq!(path.anchors[2].handle_in, Some(IntOffset::new(-100, 100)));
+        assert!(path.anchors[2].handle_out.is_none());
+
+        path.anchors[1].point = IntPoint::new(600, 1200);
+        path.update_auto_handles();
+        assert_eq!(path.anchors[0].handle_out, Some(IntOffset::new(100, 200)));
+    }
+
+    #[test]
+    fn test_02() {
+        let path = IntBezierPath {
+            anchors: vec![
+                IntBezierAnchor {
+                    point: IntPoint::new(0, 0),
+                    handle_in: None,
+                    handle_out: Some(IntOffset::new(0, 300)),
+                    kind: AnchorKind::Corner,
+                },
+                IntBezierAnchor {
+                    point: IntPoint::new(300, 300),
+                    handle_in: None,
+                    handle_out: None,
+                    kind: AnchorKind::Corner,
+                },
+                IntBezierAnchor {
+                    point: IntPoint::new(600, 0),
+                    handle_in: None,
+                    handle_out: None,
+                    kind: AnchorKind::Corner,
+                },
+            ],
+            closed: false,
+        };
+
+        let cubic = path.to_cubic();
+
+        assert!(cubic.splines().all(|s| matches!(s, IntSpline::Quad(_))));
+        assert_eq!(cubic.anchors[0].handle_out, Some(IntOffset::new(0, 200)));
+        assert_eq!(cubic.anchors[1].handle_in, Some(IntOffset::new(-200, 0)));
+        assert_eq!(cubic.anchors[1].handle_out, Some(IntOffset::new(100, -100)));
+        assert_eq!(cubic.anchors[2].handle_in, Some(IntOffset::new(-100, 100)));
+        // evaluation rounding may differ by one unit
+        let a = path.regular_points(3);
+        let b = cubic.regular_points(3);
+        assert_eq!(a.len(), b.len());
+        assert!(a.iter().zip(b.iter()).all(|(p, q)| (p.x - q.x).abs() <= 1 && (p.y - q.y).abs() <= 1));
+    }
+
+    fn is_near(a: &[IntPoint], b: &[IntPoint], max_diff: i64) -> bool {
+        a.len() == b.len() && a.iter().zip(b.iter()).all(|(p, q)| (p.x - q.x).abs() <= max_diff && (p.y - q.y).abs() <= max_diff)
+    }
+
+    #[test]
+    fn test_03() {
+        let path = IntBezierPath {
+            anchors: vec![
+                IntBezierAnchor {
+                    point: IntPoint::new(0, 0),
+                    handle_in: None,
+                    handle_out: Some(IntOffset::new(0, 800)),
+                    kind: AnchorKind::Corner,
+                },
+                IntBezierAnchor {
+                    point: IntPoint::new(1600, 0),
+                    handle_in: Some(IntOffset::new(0, -800)),
+                    handle_out: None,
+                    kind: AnchorKind::Corner,
+                },
+            ],
+            closed: false,
+        };
+
+        let mut split = path.clone();
+        let index = split.insert_anchor(0, 0.5);
+
+        assert_eq!(index, Some(1));
+        assert_eq!(split.anchors.len(), 3);
+        assert_eq!(split.anchors[1].kind, AnchorKind::Smooth);
+        // the halves sampled twice as coarse hit the same points
+        assert!(is_near(&split.regular_points(2), &path.regular_points(3), 2));
+    }
+
+    #[test]
+    fn test_04() {
+        let path = IntBezierPath {
+            anchors: vec![
+                IntBezierAnchor {
+                    point: IntPoint::new(0, 0),
+                    handle_in: None,
+                    handle_out: None,
+                    kind: AnchorKind::Corner,
+                },
+                IntBezierAnchor {
+                    point: IntPoint::new(1600, 0),
+                    handle_in: Some(IntOffset::new(0, 1600)),
+                    handle_out: None,
+                    kind: AnchorKind::Corner,
+                },
+            ],
+            closed: false,
+        };
+
+        let mut split = path.clone();
+        split.insert_anchor(0, 0.5);
+
+        assert_eq!(split.anchors.len(), 3);
+        assert!(split.anchors[0].handle_out.is_none());
+        assert!(split.anchors[1].handle_in.is_some());
+        assert!(is_near(&split.regular_points(2), &path.regular_points(3), 2));
+    }
+
+    #[test]
+    fn test_05() {
+        let mut path = IntBezierPath {
+            anchors: [(0, 0), (1000, 0), (1000, 1000)].iter().map(|&(x, y)| IntBezierAnchor {
+                point: IntPoint::new(x, y),
+                handle_in: None,
+                handle_out: None,
+                kind: AnchorKind::Corner,
+            }).collect(),
+            closed: true,
+        };
+
+        let position = path.nearest_position(IntPoint::new(510, 490), 2).unwrap();
+        assert_eq!(position.spline_index, 2);
+        assert_eq!(position.t, 0.5);
+        assert_eq!(position.point, IntPoint::new(500, 500));
+
+        let index = path.insert_anchor(position.spline_index, position.t).unwrap();
+        assert_eq!(index, 3);
+        assert_eq!(path.anchors[3].point, IntPoint::new(500, 500));
+
+        assert!(path.remove_anchor(index));
+        assert_eq!(path.anchors.len(), 3);
+
+        // the closed path has three splines
+        assert_eq!(path.clone().insert_anchor(3, 0.5), None);
+        assert!(!path.remove_anchor(3));
+
+        path.toggle_handles(1);
+        assert_eq!(path.anchors[1].kind, AnchorKind::Smooth);
+        assert_eq!(path.anchors[1].handle_out, Some(IntOffset::new(166, 166)));
+        assert_eq!(path.anchors[1].handle_in, Some(IntOffset::new(-166, -166)));
+
+        path.toggle_handles(1);
+        assert!(path.anchors[1].handle_in.is_none() && path.anchors[1].handle_out.is_none());
+    }
+
+    #[test]
+    fn test_06() {
+        let path = IntBezierPath {
+            anchors: vec![
+                IntBezierAnchor {
+                    point: IntPoint::new(0, 0),
+                    handle_in: None,
+                    handle_out: None,
+                    kind: AnchorKind::Corner,
+                },
+                IntBezierAnchor {
+                    point: IntPoint::new(1024, 0),
+                    handle_in: None,
+                    handle_out: None,
+                    kind: AnchorKind::Corner,
+                },
+            ],
+            closed: false,
+        };
+
+        let coarse = path.nearest_position(IntPoint::new(300, 50), 2).unwrap();
+        assert_eq!(coarse.point, IntPoint::new(256, 0));
+
+        let position = path.project(IntPoint::new(300, 50), 2).unwrap();
+        assert_eq!(position.spline_index, 0);
+        assert_eq!(position.point, IntPoint::new(300, 0));
+        assert!((position.t - 300.0 / 1024.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_07() {
+        let path = IntBezierPath {
+            anchors: vec![
+                IntBezierAnchor {
+                    point: IntPoint::new(-1000, 0),
+                    handle_in: None,
+                    handle_out: Some(IntOffset::new(0, 1000)),
+                    kind: AnchorKind::Corner,
+                },
+                IntBezierAnchor {
+                    point: IntPoint::new(1000, 0),
+                    handle_in: Some(IntOffset::new(0, 1000)),
+                    handle_out: None,
+                    kind: AnchorKind::Corner,
+                },
+            ],
+            closed: false,
+        };
+
+        // the top of the symmetric arch is at t = 0.5, (0, 750)
+        let position = path.project(IntPoint::new(3, 900), 3).unwrap();
+        assert!((position.t - 0.5).abs() < 0.01);
+        assert!(position.point.x.abs() <= 3 && (position.point.y - 750).abs() <= 1);
+    }
+
+    #[test]
+    fn test_08() {
+        let arch = IntBezierPath {
+            anchors: vec![
+                IntBezierAnchor {
+                    point: IntPoint::new(-1000, 0),
+                    handle_in: None,
+                    handle_out: Some(IntOffset::new(0, 1000)),
+                    kind: AnchorKind::Corner,
+                },
+                IntBezierAnchor {
+                    point: IntPoint::new(1000, 0),
+                    handle_in: Some(IntOffset::new(0, 1000)),
+                    handle_out: None,
+                    kind: AnchorKind::Corner,
+                },
+            ],
+            closed: false,
+        };
+
+        // the handles reach y = 1000, the curve only 750
+        let (min, max) = arch.bounds().unwrap();
+        assert_eq!(min, IntPoint::new(-1000, 0));
+        assert_eq!(max, IntPoint::new(1000, 750));
+
+        let parabola = IntBezierPath {
+            anchors: vec![
+                IntBezierAnchor {
+                    point: IntPoint::new(0, 0),
+                    handle_in: None,
+                    handle_out: Some(IntOffset::new(500, -1000)),
+                    kind: AnchorKind::Corner,
+                },
+                IntBezierAnchor {
+                    point: IntPoint::new(1000, 0),
+                    handle_in: None,
+                    handle_out: None,
+                    kind: AnchorKind::Corner,
+                },
+            ],
+            closed: false,
+        };
+
+        let (min, max) = parabola.bounds().unwrap();
+        assert_eq!(min, IntPoint::new(0, -500));
+        assert_eq!(max, IntPoint::new(1000, 0));
+
+        assert!(IntBezierPath { anchors: vec![], closed: false }.bounds().is_none());
+    }
+
+    #[test]
+    fn test_09() {
+        let anchor = |x: i64, handle: Option<IntOffset>| IntBezierAnchor {
+            point: IntPoint::new(x, 0),
+            handle_in: None,
+            handle_out: handle,
+            kind: AnchorKind::Corner,
+        };
+
+        let mut path = IntBezierPath {
+            anchors: vec![anchor(-MAX_COORD, None), anchor(MAX_COORD, None)],
+            closed: false,
+        };
+        assert_eq!(path.validate(), Ok(()));
+
+        // a handle point out of range, and one that overflows i64 itself
+        path.anchors[1].handle_out = Some(IntOffset::new(1, 0));
+        assert_eq!(path.validate(), Err(RangeError { anchor_index: 1 }));
+        path.anchors[1].handle_out = Some(IntOffset::new(i64::MAX, 0));
+        assert_eq!(path.validate(), Err(RangeError { anchor_index: 1 }));
+
+        path.anchors[0].point.y = MAX_COORD + 1;
+        assert_eq!(path.validate(), Err(RangeError { anchor_index: 0 }));
+    }
+
+    #[test]
+    fn test_10() {
+        let anchor = |x: i64, handle: Option<IntOffset>| IntBezierAnchor {
+            point: IntPoint::new(x, 0),
+            handle_in: None,
+            handle_out: handle,
+            kind: AnchorKind::Corner,
+        };
+
+        let empty = IntBezierPath { anchors: vec![], closed: true };
+        assert_eq!(empty.splines().count(), 0);
+        assert_eq!(empty.try_approximate_points(1000, 4), Err(Error::EmptyPath));
+
+        let mut path = IntBezierPath {
+            anchors: vec![anchor(0, Some(IntOffset::new(50, 100))), anchor(200, None)],
+            closed: false,
+        };
+        let points = path.try_approximate_points(1000, 4).unwrap();
+        assert_eq!(points.first(), Some(&IntPoint::new(0, 0)));
+        assert_eq!(points.last(), Some(&IntPoint::new(200, 0)));
+        assert_eq!(path.try_avg_length(1000, 4), Ok(path.avg_length(1000, 4)));
+
+        assert_eq!(path.try_approximate(1025, 4).unwrap_err(), Error::InvalidParameter("min_cos"));
+        assert_eq!(path.try_approximate(1000, 0).unwrap_err(), Error::InvalidParameter("min_len"));
+
+        // a zero length handle falls back to the direction of the other control points
+        path.anchors[0].handle_out = Some(IntOffset::new(0, 0));
+        assert!(path.try_approximate(1000, 4).is_ok());
+
+        // the closing spline of two coincident anchors
+        path.anchors[0].handle_out = None;
+        path.anchors.push(anchor(0, None));
+        path.closed = true;
+        assert_eq!(path.try_avg_length(1000, 4), Err(Error::DegenerateSpline { spline_index: 2 }));
+
+        path.anchors[1].point.x = MAX_COORD + 1;
+        assert_eq!(path.try_avg_length(1000, 4), Err(Error::OutOfRange(RangeError { anchor_index: 1 })));
+    }
+
+    #[test]
+    fn test_11() {
+        let anchor = |x: i64| IntBezierAnchor {
+            point: IntPoint::new(x, 0),
+            handle_in: None,
+            handle_out: None,
+            kind: AnchorKind::Corner,
+        };
+        let half = RationalT::new(1, 2).unwrap();
+        let origin = IntPoint::zero();
+
+        let mut empty = IntBezierPath { anchors: vec![], closed: false };
+        assert_eq!(empty.try_insert_anchor(0, 0.5), Err(Error::EmptyPath));
+        assert_eq!(empty.try_exact_point_at(0, half).unwrap_err(), Error::EmptyPath);
+        assert_eq!(empty.try_project(origin, 4).unwrap_err(), Error::EmptyPath);
+
+        // a single anchor of an open path has no splines
+        let single = IntBezierPath { anchors: vec![anchor(0)], closed: false };
+        assert_eq!(single.try_nearest_position(origin, 4).unwrap_err(), Error::EmptyPath);
+
+        let mut path = IntBezierPath { anchors: vec![anchor(0), anchor(100)], closed: false };
+        assert_eq!(path.try_insert_anchor(0, f64::NAN), Err(Error::InvalidParameter("t")));
+        assert_eq!(path.try_insert_anchor(0, 1.5), Err(Error::InvalidParameter("t")));
+        assert_eq!(path.try_insert_anchor_exact(1, half), Err(Error::InvalidParameter("spline_index")));
+        assert_eq!(path.try_exact_point_at(1, half).unwrap_err(), Error::InvalidParameter("spline_index"));
+        assert_eq!(path.try_project(origin, 17).unwrap_err(), Error::InvalidParameter("split_factor"));
+        assert_eq!(path.try_project(IntPoint::new(MAX_COORD + 1, 0), 4).unwrap_err(), Error::InvalidParameter("point"));
+        assert_eq!(path.anchors.len(), 2);
+
+        let position = path.try_project(IntPoint::new(30, 10), 4).unwrap();
+        assert_eq!(position.point, IntPoint::new(30, 0));
+        assert_eq!(path.try_exact_point_at(0, half).unwrap().point, IntPoint::new(50, 0));
+        assert_eq!(path.try_insert_anchor(0, 0.5), Ok(1));
+        assert_eq!(path.try_to_cubic().unwrap().anchors.len(), 3);
+
+        path.anchors[2].point.x = MAX_COORD + 1;
+        assert_eq!(path.try_insert_anchor_exact(0, half), Err(Error::OutOfRange(RangeError { anchor_index: 2 })));
+        assert_eq!(path.try_to_cubic().unwrap_err(), Error::OutOfRange(RangeError { anchor_index: 2 }));
+    }
+
+    #[test]
+    fn test_12() {
+        let anchor = |x: i64, handle_in: Option<IntOffset>, handle_out: Option<IntOffset>| IntBezierAnchor {
+            point: IntPoint::new(x, 0),
+            handle_in,
+            handle_out,
+            kind: AnchorKind::Smooth,
+        };
+        let zero = Some(IntOffset::zero());
+        let up = Some(IntOffset::new(0, 50));
+
+        let path = IntBezierPath {
+            anchors: vec![
+                anchor(0, None, zero),
+                anchor(0, None, None),
+                anchor(0, zero, up),
+                anchor(100, up, up),
+                anchor(100, up, None),
+                anchor(0, up, None),
+            ],
+            closed: true,
+        };
+
+        let path = path.canonicalized();
+        assert_eq!(path.anchors.len(), 3);
+        assert!(path.closed);
+
+        // the coincident anchors and the closing one are merged, the handles of both ends are kept
+        assert_eq!(path.anchors[0].point, IntPoint::new(0, 0));
+        assert_eq!(path.anchors[0].handle_in, up);
+        assert_eq!(path.anchors[0].handle_out, up);
+        assert_eq!(path.anchors[0].kind, AnchorKind::Corner);
+
+        // the loop between the two anchors at 100 is kept
+        assert_eq!(path.anchors[1].point, IntPoint::new(100, 0));
+        assert_eq!(path.anchors[2].point, IntPoint::new(100, 0));
+        assert!(path.splines().all(|s| !s.is_point()));
+    }
+
+    #[test]
+    fn test_13() {
+        let path = IntBezierPath {
+            anchors: vec![
+                IntBezierAnchor {
+                    point: IntPoint::new(0, 0),
+                    handle_in: None,
+                    handle_out: Some(IntOffset::new(0, 800)),
+                    kind: AnchorKind::Corner,
+                },
+                IntBezierAnchor {
+                    point: IntPoint::new(1600, 0),
+                    handle_in: Some(IntOffset::new(0, -800)),
+                    handle_out: None,
+                    kind: AnchorKind::Corner,
+                },
+            ],
+            closed: false,
+        };
+
+        // a dyadic t on even coordinates has no rounding, both ways agree
+        let half = RationalT::new(1, 2).unwrap();
+        let mut exact = path.clone();
+        let mut float = path.clone();
+        exact.insert_anchor_exact(0, half);
+        float.insert_anchor(0, 0.5);
+        assert_eq!(exact.anchors.iter().map(|a| a.point).collect::<Vec<_>>(), float.anchors.iter().map(|a| a.point).collect::<Vec<_>>());
+        assert_eq!(exact.anchors[1].handle_in, float.anchors[1].handle_in);
+        assert_eq!(exact.exact_point_at(0, half).unwrap().residual.x, 0.0);
+
+        // the new anchor is the exact point rounded once
+        let third = RationalT::new(1, 3).unwrap();
+        let point = path.exact_point_at(0, third).unwrap();
+        let mut split = path.clone();
+        let index = split.insert_anchor_exact(0, third).unwrap();
+        assert_eq!(split.anchors[index].point, point.point);
+        assert!(point.residual.x.abs() <= 0.5 && point.residual.y.abs() <= 0.5);
+
+        // the halves are the same curve, up to the rounding of their control points
+        let end = RationalT::new(1, 1).unwrap();
+        assert_eq!(split.exact_point_at(0, end).unwrap().point, point.point);
+        let on_half = split.exact_point_at(1, half).unwrap().point;
+        let on_path = path.exact_point_at(0, RationalT::new(2, 3).unwrap()).unwrap().point;
+        assert!((on_half.x - on_path.x).abs() <= 1 && (on_half.y - on_path.y).abs() <= 1);
+
+        assert!(path.exact_point_at(1, half).is_none());
+        assert_eq!(path.clone().insert_anchor_exact(1, half), None);
+
+        // a path keeps its last two anchors
+        assert!(split.remove_anchor(0));
+        assert!(!split.remove_anchor(0));
+        assert_eq!(split.anchors.len(), 2);
+    }
 }
@@ -1,11 +1,14 @@
 use crate::int::bezier::anchor::IntBezierAnchor;
-use crate::int::bezier::approximation::IntApproximation;
-use crate::int::bezier::iter::IntSplinePointsIter;
-use crate::int::bezier::length::IntSplineLength;
+use crate::bezier::approximation::Approximation;
+use crate::bezier::iter::SplinePointsIter;
+use crate::bezier::length::SplineLength;
+use crate::bezier::spline::CADSpline;
+use crate::int::bezier::approximation::IntShort;
 use crate::int::bezier::spline_cube::IntCubeSpline;
 use crate::int::bezier::spline_line::IntLineSpline;
 use crate::int::bezier::spline_quad::IntQuadSpline;
 use crate::int::math::point::IntPoint;
+use crate::int::math::rect::IntRect;
 
 #[derive(Debug, Clone)]
 pub(crate) enum IntSpline {
@@ -16,7 +19,7 @@ pub(crate) enum IntSpline {
 
 impl IntSpline {
     #[inline]
-    pub(super) fn new(a: &IntBezierAnchor, b: &IntBezierAnchor) -> Self {
+    pub(crate) fn new(a: &IntBezierAnchor, b: &IntBezierAnchor) -> Self {
         match (a.handle_out_point(), b.handle_in_point()) {
             (Some(am), Some(bm)) => IntSpline::Quad(IntQuadSpline {
                 a: a.point,
@@ -50,6 +53,15 @@ impl IntSpline {
         }
     }
 
+    #[inline]
+    pub fn approximate(&self, min_cos: u32, min_len: u32) -> Vec<IntShort> {
+        match self {
+            IntSpline::Line(s) => s.approximate(min_cos, min_len),
+            IntSpline::Cube(s) => s.approximate(min_cos, min_len),
+            IntSpline::Quad(s) => s.approximate(min_cos, min_len),
+        }
+    }
+
     #[inline]
     pub fn approximate_points(&self, min_cos: u32, min_len: u32) -> Vec<IntPoint> {
         match self {
@@ -60,7 +72,7 @@ impl IntSpline {
     }
 
     #[inline]
-    pub fn avg_length(&self, min_cos: u32, min_len: u32) -> u128 {
+    pub fn avg_length(&self, min_cos: u32, min_len: u32) -> f64 {
         match self {
             IntSpline::Line(s) => s.avg_length(min_cos, min_len),
             IntSpline::Cube(s) => s.avg_length(min_cos, min_len),
@@ -69,10 +81,54 @@ impl IntSpline {
     }
 }
 
-pub(crate) trait IntCADSpline {
-    fn start(&self) -> IntPoint;
-    fn start_dir(&self) -> IntPoint;
-    fn end_dir(&self) -> IntPoint;
-    fn end(&self) -> IntPoint;
-    fn split_at(&self, step: usize, split_factor: u32) -> IntPoint;
+impl IntSpline {
+    #[inline]
+    pub(crate) fn end(&self) -> IntPoint {
+        match self {
+            IntSpline::Line(s) => s.b,
+            IntSpline::Cube(s) => s.b,
+            IntSpline::Quad(s) => s.b,
+        }
+    }
+
+    /// The point at `step / 2^split_factor` of the spline.
+    #[inline]
+    pub(crate) fn split_at(&self, step: usize, split_factor: u32) -> IntPoint {
+        match self {
+            IntSpline::Line(s) => s.split_at(step, split_factor),
+            IntSpline::Cube(s) => s.split_at(step, split_factor),
+            IntSpline::Quad(s) => s.split_at(step, split_factor),
+        }
+    }
+
+    /// The tight bounding box of the curve.
+    #[inline]
+    pub(crate) fn boundary(&self) -> IntRect {
+        match self {
+            IntSpline::Line(s) => s.boundary(),
+            IntSpline::Cube(s) => s.boundary(),
+            IntSpline::Quad(s) => s.boundary(),
+        }
+    }
+
+    /// True if all control points coincide, such a spline has no direction.
+    #[inline]
+    pub(crate) fn is_point(&self) -> bool {
+        match self {
+            IntSpline::Line(s) => s.a == s.b,
+            IntSpline::Cube(s) => s.a == s.b && s.a == s.m,
+            IntSpline::Quad(s) => s.a == s.b && s.a == s.am && s.a == s.bm,
+        }
+    }
+
+    /// Elevation to the two control points form, exact up to the rounding of the control points.
+    #[inline]
+    pub fn elevated(&self) -> IntQuadSpline {
+        match self {
+            IntSpline::Line(s) => s.elevated(),
+            IntSpline::Cube(s) => s.elevated(),
+            IntSpline::Quad(s) => s.clone(),
+        }
+    }
 }
+
@@ -1,6 +1,8 @@
-use crate::int::bezier::spline::IntCADSpline;
+use crate::bezier::spline::{tangent, CADSpline};
+use crate::int::bezier::spline_quad::IntQuadSpline;
 use crate::int::math::line::IntLine;
 use crate::int::math::point::IntPoint;
+use crate::int::math::rect::IntRect;
 
 #[derive(Debug, Clone)]
 pub(crate) struct IntCubeSpline {
@@ -9,18 +11,18 @@ pub(crate) struct IntCubeSpline {
     pub(crate) b: IntPoint,
 }
 
-impl IntCADSpline for IntCubeSpline {
+impl CADSpline<IntPoint> for IntCubeSpline {
     #[inline]
     fn start(&self) -> IntPoint {
         self.a
     }
     #[inline]
     fn start_dir(&self) -> IntPoint {
-        (self.m - self.a).normalized_10bit()
+        tangent(&[self.m - self.a, self.b - self.a])
     }
     #[inline]
     fn end_dir(&self) -> IntPoint {
-        (self.b - self.m).normalized_10bit()
+        tangent(&[self.b - self.m, self.b - self.a])
     }
     #[inline]
     fn end(&self) -> IntPoint {
@@ -36,3 +38,42 @@ impl IntCADSpline for IntCubeSpline {
         IntLine::new(p10, p11).split_at(step, split_factor)
     }
 }
+
+impl IntCubeSpline {
+    /// Elevation to the two control points form, exact up to the rounding of the control points.
+    #[inline]
+    pub(crate) fn elevated(&self) -> IntQuadSpline {
+        IntQuadSpline {
+            a: self.a,
+            am: IntLine::new(self.a, self.m).third_at(2),
+            bm: IntLine::new(self.b, self.m).third_at(2),
+            b: self.b,
+        }
+    }
+
+    /// The tight bounding box of the curve, not of its control points.
+    pub(crate) fn boundary(&self) -> IntRect {
+        let (min_x, max_x) = Self::axis_range(self.a.x, self.m.x, self.b.x);
+        let (min_y, max_y) = Self::axis_range(self.a.y, self.m.y, self.b.y);
+        IntRect {
+            min: IntPoint::new(min_x, min_y),
+            max: IntPoint::new(max_x, max_y),
+        }
+    }
+
+    /// The derivative is linear, so an axis has at most one extremum at `t = (a - m) / (a - 2m + b)`.
+    fn axis_range(a: i64, m: i64, b: i64) -> (i64, i64) {
+        let (mut min, mut max) = (a.min(b), a.max(b));
+        let den = a - 2 * m + b;
+        if den != 0 {
+            let t = (a - m) as f64 / den as f64;
+            if 0.0 < t && t < 1.0 {
+                let s = 1.0 - t;
+                let v = s * s * a as f64 + 2.0 * s * t * m as f64 + t * t * b as f64;
+                min = min.min(v.floor() as i64);
+                max = max.max(v.ceil() as i64);
+            }
+        }
+        (min, max)
+    }
+}
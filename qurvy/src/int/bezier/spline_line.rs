@@ -1,4 +1,5 @@
-use crate::int::bezier::spline::IntCADSpline;
+use crate::bezier::spline::{tangent, CADSpline};
+use crate::int::bezier::spline_quad::IntQuadSpline;
 use crate::int::math::line::IntLine;
 use crate::int::math::point::IntPoint;
 use crate::int::math::rect::IntRect;
@@ -9,19 +10,19 @@ pub(crate) struct IntLineSpline {
     pub(crate) b: IntPoint,
 }
 
-impl IntCADSpline for IntLineSpline {
+impl CADSpline<IntPoint> for IntLineSpline {
     #[inline]
     fn start(&self) -> IntPoint {
         self.a
     }
     #[inline]
     fn start_dir(&self) -> IntPoint {
-        (self.b - self.a).normalized_10bit()
+        tangent(&[self.b - self.a])
     }
 
     #[inline]
     fn end_dir(&self) -> IntPoint {
-        (self.b - self.a).normalized_10bit()
+        tangent(&[self.b - self.a])
     }
     #[inline]
     fn end(&self) -> IntPoint {
@@ -35,6 +36,18 @@ impl IntCADSpline for IntLineSpline {
 }
 
 impl IntLineSpline {
+    /// Elevation to the two control points form, exact up to the rounding of the control points.
+    #[inline]
+    pub(crate) fn elevated(&self) -> IntQuadSpline {
+        let line = IntLine::new(self.a, self.b);
+        IntQuadSpline {
+            a: self.a,
+            am: line.third_at(1),
+            bm: line.third_at(2),
+            b: self.b,
+        }
+    }
+
     #[inline]
     pub(crate) fn boundary(&self) -> IntRect {
         let (min_x, max_x) = if self.a.x < self.b.x {
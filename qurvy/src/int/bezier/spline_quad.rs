@@ -1,6 +1,7 @@
-use crate::int::bezier::spline::IntCADSpline;
+use crate::bezier::spline::{tangent, CADSpline};
 use crate::int::math::line::IntLine;
 use crate::int::math::point::IntPoint;
+use crate::int::math::rect::IntRect;
 
 #[derive(Debug, Clone)]
 pub(crate) struct IntQuadSpline {
@@ -9,18 +10,18 @@ pub(crate) struct IntQuadSpline {
     pub(crate) bm: IntPoint,
     pub(crate) b: IntPoint,
 }
-impl IntCADSpline for IntQuadSpline {
+impl CADSpline<IntPoint> for IntQuadSpline {
     #[inline]
     fn start(&self) -> IntPoint {
         self.a
     }
     #[inline]
     fn start_dir(&self) -> IntPoint {
-        (self.am - self.a).normalized_10bit()
+        tangent(&[self.am - self.a, self.bm - self.a, self.b - self.a])
     }
     #[inline]
     fn end_dir(&self) -> IntPoint {
-        (self.b - self.bm).normalized_10bit()
+        tangent(&[self.b - self.bm, self.b - self.am, self.b - self.a])
     }
     #[inline]
     fn end(&self) -> IntPoint {
@@ -43,3 +44,53 @@ impl IntCADSpline for IntQuadSpline {
         IntLine::new(p10, p11).split_at(step, split_factor)
     }
 }
+
+impl IntQuadSpline {
+    /// The tight bounding box of the curve, not of its control points.
+    pub(crate) fn boundary(&self) -> IntRect {
+        let (min_x, max_x) = Self::axis_range(self.a.x, self.am.x, self.bm.x, self.b.x);
+        let (min_y, max_y) = Self::axis_range(self.a.y, self.am.y, self.bm.y, self.b.y);
+        IntRect {
+            min: IntPoint::new(min_x, min_y),
+            max: IntPoint::new(max_x, max_y),
+        }
+    }
+
+    /// The extrema of an axis are the roots of the quadratic derivative inside `(0, 1)`.
+    fn axis_range(p0: i64, p1: i64, p2: i64, p3: i64) -> (i64, i64) {
+        let (mut min, mut max) = (p0.min(p3), p0.max(p3));
+
+        let (d0, d1, d2) = ((p1 - p0) as f64, (p2 - p1) as f64, (p3 - p2) as f64);
+        let a = d0 - 2.0 * d1 + d2;
+        let b = 2.0 * (d1 - d0);
+        let c = d0;
+
+        let mut roots = [f64::NAN; 2];
+        if a.abs() < f64::EPSILON {
+            if b.abs() >= f64::EPSILON {
+                roots[0] = -c / b;
+            }
+        } else {
+            let disc = b * b - 4.0 * a * c;
+            if disc >= 0.0 {
+                let sq = disc.sqrt();
+                roots[0] = (-b - sq) / (2.0 * a);
+                roots[1] = (-b + sq) / (2.0 * a);
+            }
+        }
+
+        for t in roots {
+            if 0.0 < t && t < 1.0 {
+                let s = 1.0 - t;
+                let v = s * s * s * p0 as f64
+                    + 3.0 * s * s * t * p1 as f64
+                    + 3.0 * s * t * t * p2 as f64
+                    + t * t * t * p3 as f64;
+                min = min.min(v.floor() as i64);
+                max = max.max(v.ceil() as i64);
+            }
+        }
+
+        (min, max)
+    }
+}
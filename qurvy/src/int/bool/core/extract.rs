@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use crate::int::bool::core::fill::FillEdge;
+use crate::int::math::point::IntPoint;
+
+pub(crate) trait ExtractContours {
+    fn extract_contours(&self) -> Vec<Vec<IntPoint>>;
+}
+
+impl ExtractContours for [FillEdge] {
+    /// Joins directed edges into closed contours. Outer contours are counter-clockwise, holes are clockwise.
+    /// At a vertex shared by several contours the sharpest left turn is taken, so touching regions stay separated.
+    /// A walk that ends before it closes is not a contour and is dropped.
+    fn extract_contours(&self) -> Vec<Vec<IntPoint>> {
+        let mut outgoing: HashMap<IntPoint, Vec<usize>> = HashMap::with_capacity(self.len());
+        for (i, e) in self.iter().enumerate() {
+            outgoing.entry(e.a).or_default().push(i);
+        }
+
+        let mut visited = vec![false; self.len()];
+        let mut contours = Vec::new();
+
+        for first in 0..self.len() {
+            if visited[first] {
+                continue;
+            }
+
+            let start = self[first].a;
+            let mut contour = vec![start];
+            let mut index = first;
+            let is_closed = loop {
+                visited[index] = true;
+                let edge = &self[index];
+                if edge.b == start {
+                    break true;
+                }
+                contour.push(edge.b);
+
+                let back = edge.a - edge.b;
+                let next = outgoing.get(&edge.b).and_then(|candidates| {
+                    candidates
+                        .iter()
+                        .copied()
+                        .filter(|&i| !visited[i])
+                        .reduce(|i, j| {
+                            if is_before_clockwise(back, self[j].b - self[j].a, self[i].b - self[i].a) { j } else { i }
+                        })
+                });
+
+                if let Some(next) = next {
+                    index = next;
+                } else {
+                    break false;
+                }
+            };
+
+            // every vertex of the fill edges is balanced, a dead end means the edges were not split clean
+            if !is_closed {
+                continue;
+            }
+
+            let contour = remove_collinear(contour);
+            if contour.len() > 2 {
+                contours.push(contour);
+            }
+        }
+
+        contours
+    }
+}
+
+/// True if rotating clockwise from `from` reaches `a` before `b`. The `from` direction itself is reached last.
+#[inline]
+fn is_before_clockwise(from: IntPoint, a: IntPoint, b: IntPoint) -> bool {
+    let ha = half(from, a);
+    let hb = half(from, b);
+    if ha != hb {
+        return ha < hb;
+    }
+    a.cross_product_i128(&b) < 0
+}
+
+/// 0 for the first clockwise half turn from `from` (including the opposite direction), 1 for the second one.
+#[inline]
+fn half(from: IntPoint, v: IntPoint) -> u8 {
+    let cross = from.cross_product_i128(&v);
+    if cross < 0 || cross == 0 && from.dot_product_i128(&v) < 0 { 0 } else { 1 }
+}
+
+fn remove_collinear(contour: Vec<IntPoint>) -> Vec<IntPoint> {
+    let n = contour.len();
+    if n < 3 {
+        return contour;
+    }
+
+    let mut result = Vec::with_capacity(n);
+    for i in 0..n {
+        let prev = contour[(i + n - 1) % n];
+        let p = contour[i];
+        let next = contour[(i + 1) % n];
+        if (p - prev).cross_product_i128(&(next - p)) != 0 {
+            result.push(p);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::bool::core::extract::ExtractContours;
+    use crate::int::bool::core::fill::FillEdge;
+    use crate::int::math::point::IntPoint;
+
+    fn edges(points: &[(i64, i64)]) -> Vec<FillEdge> {
+        points.windows(2).map(|w| FillEdge {
+            a: IntPoint::new(w[0].0, w[0].1),
+            b: IntPoint::new(w[1].0, w[1].1),
+        }).collect()
+    }
+
+    #[test]
+    fn test_00() {
+        let contours = edges(&[(0, 0), (10, 0), (10, 10), (0, 10), (0, 0)]).extract_contours();
+        assert_eq!(contours.len(), 1);
+        assert_eq!(contours[0].len(), 4);
+    }
+
+    #[test]
+    fn test_01() {
+        // the walk from (20, 0) ends at (30, 10), it is not returned as a closed contour
+        let mut fill_edges = edges(&[(20, 0), (30, 0), (30, 10)]);
+        fill_edges.extend(edges(&[(0, 0), (10, 0), (10, 10), (0, 10), (0, 0)]));
+
+        let contours = fill_edges.extract_contours();
+        assert_eq!(contours.len(), 1);
+        assert!(contours[0].contains(&IntPoint::new(10, 10)));
+    }
+}
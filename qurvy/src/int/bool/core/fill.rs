@@ -0,0 +1,122 @@
+use crate::int::bool::core::fill_rule::FillRule;
+use crate::int::bool::core::overlay_rule::OverlayRule;
+use crate::int::bool::geom::count::ShapeCountBoolean;
+use crate::int::bool::geom::edge::ShapeEdge;
+use crate::int::math::point::IntPoint;
+
+/// A result edge directed so that the filled region is on its left.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FillEdge {
+    pub(crate) a: IntPoint,
+    pub(crate) b: IntPoint,
+}
+
+pub(crate) trait FillEdges {
+    fn fill_edges(&self, overlay_rule: OverlayRule, fill_rule: FillRule) -> Vec<FillEdge>;
+}
+
+impl FillEdges for [ShapeEdge] {
+    /// Keeps the edges that separate a filled region from an empty one.
+    /// Edges must not cross each other, see `SplitSegments`.
+    fn fill_edges(&self, overlay_rule: OverlayRule, fill_rule: FillRule) -> Vec<FillEdge> {
+        let is_filled = |count: ShapeCountBoolean| {
+            overlay_rule.is_filled(fill_rule.is_filled(count.subj), fill_rule.is_filled(count.clip))
+        };
+
+        let mut result = Vec::new();
+        for (edge, below) in self.iter().zip(self.counts_below()) {
+            if edge.is_vertical() {
+                // `below` is the left side, the right side is reached by crossing the edge
+                let left = is_filled(below);
+                let right = is_filled(below.add(edge.count.invert()));
+                if left != right {
+                    result.push(if left {
+                        FillEdge { a: edge.a, b: edge.b }
+                    } else {
+                        FillEdge { a: edge.b, b: edge.a }
+                    });
+                }
+            } else {
+                let bottom = is_filled(below);
+                let top = is_filled(below.add(edge.count));
+                if bottom != top {
+                    result.push(if top {
+                        FillEdge { a: edge.a, b: edge.b }
+                    } else {
+                        FillEdge { a: edge.b, b: edge.a }
+                    });
+                }
+            }
+        }
+
+        result
+    }
+}
+
+trait CountBelow {
+    fn counts_below(&self) -> Vec<ShapeCountBoolean>;
+}
+
+impl CountBelow for [ShapeEdge] {
+    /// Winding counts right below the middle of every edge, or right to the left of it for a vertical edge.
+    /// A ray is cast down and every non-vertical edge it crosses adds its count.
+    /// The middles are swept from left to right, so a ray only tests the edges that span its x.
+    fn counts_below(&self) -> Vec<ShapeCountBoolean> {
+        // doubled coordinates keep the middle point exact
+        let middle_x = |edge: &ShapeEdge| edge.a.x as i128 + edge.b.x as i128;
+
+        let mut rays: Vec<usize> = (0..self.len()).collect();
+        rays.sort_unstable_by_key(|&i| middle_x(&self[i]));
+
+        let mut sources: Vec<usize> = (0..self.len()).filter(|&i| !self[i].is_vertical()).collect();
+        sources.sort_unstable_by_key(|&i| self[i].a.x);
+
+        let mut counts = vec![ShapeCountBoolean::ZERO; self.len()];
+        let mut active = Vec::new();
+        let mut next = 0;
+
+        for &index in rays.iter() {
+            let edge = &self[index];
+            let mx = middle_x(edge);
+            let my = edge.a.y as i128 + edge.b.y as i128;
+            let vertical = edge.is_vertical();
+
+            while let Some(&j) = sources.get(next).filter(|&&j| 2 * self[j].a.x as i128 <= mx) {
+                active.push(j);
+                next += 1;
+            }
+            // the rays only move right, an edge that ends left of this one is behind all of them
+            active.retain(|&j| 2 * self[j].b.x as i128 >= mx);
+
+            let mut count = ShapeCountBoolean::ZERO;
+            for &j in active.iter() {
+                if j == index {
+                    continue;
+                }
+                let other = &self[j];
+
+                let ax = 2 * other.a.x as i128;
+                let bx = 2 * other.b.x as i128;
+
+                // half-open ranges, a vertical edge is probed slightly to the left
+                let is_inside = if vertical { ax < mx && mx <= bx } else { ax <= mx && mx < bx };
+                if !is_inside {
+                    continue;
+                }
+
+                let dx = (other.b.x - other.a.x) as i128;
+                let dy = (other.b.y - other.a.y) as i128;
+                let px = mx - ax;
+                let py = my - 2 * other.a.y as i128;
+
+                if dx * py - dy * px > 0 {
+                    count.apply(other.count);
+                }
+            }
+
+            counts[index] = count;
+        }
+
+        counts
+    }
+}
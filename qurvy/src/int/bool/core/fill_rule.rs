@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Decides which regions are inside a shape from its winding number.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FillRule {
+    /// Odd winding numbers are inside.
+    #[default]
+    EvenOdd,
+    /// Any non-zero winding number is inside.
+    NonZero,
+    /// Positive winding numbers (counter-clockwise contours) are inside.
+    Positive,
+    /// Negative winding numbers (clockwise contours) are inside.
+    Negative,
+}
+
+impl FillRule {
+    #[inline(always)]
+    pub(crate) fn is_filled(&self, winding: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding & 1 == 1,
+            FillRule::NonZero => winding != 0,
+            FillRule::Positive => winding > 0,
+            FillRule::Negative => winding < 0,
+        }
+    }
+}
@@ -1 +1,5 @@
 pub mod overlay;
+pub mod overlay_rule;
+pub mod fill_rule;
+pub(crate) mod fill;
+pub(crate) mod extract;
@@ -1,4 +1,15 @@
-use crate::int::bool::geom::segment::ShapeSegment;
+use crate::error::Error;
+use crate::int::bezier::path::IntBezierPath;
+use crate::int::bezier::spline::IntSpline;
+use crate::int::bool::core::extract::ExtractContours;
+use crate::int::bool::core::fill::FillEdges;
+use crate::int::bool::core::fill_rule::FillRule;
+use crate::int::bool::core::overlay_rule::OverlayRule;
+use crate::int::bool::geom::edge::ShapeEdge;
+use crate::int::bool::geom::segment::{ShapePart, ShapeSegment};
+use crate::int::bool::split::solver::SplitSegments;
+use crate::int::math::point::IntPoint;
+use crate::int::math::range::RangeError;
 
 #[derive(Debug, Clone, Copy)]
 pub enum ShapeType {
@@ -9,17 +20,280 @@ pub enum ShapeType {
 #[derive(Clone)]
 pub struct Overlay {
     pub(crate) sections: Vec<ShapeSegment>,
+    min_cos: u32,
+    min_len: u32,
 }
 
 impl Overlay {
 
     #[inline]
     pub fn new() -> Self {
-        Self { sections: Vec::new() }
+        Self::with_approximation(1018, 4)
     }
 
-    // #[inline]
-    // pub fn add_bezier_path(&mut self, path: &IntBezierPath, shape_type: ShapeType) {
-    //
-    // }
-}
\ No newline at end of file
+    /// `min_cos` and `min_len` control how splines are approximated, see `IntBezierPath::approximate_points`.
+    #[inline]
+    pub fn with_approximation(min_cos: u32, min_len: u32) -> Self {
+        Self { sections: Vec::new(), min_cos, min_len: min_len.max(2) }
+    }
+
+    /// Adds a path to the subject or clip shape. An open path is treated as closed.
+    /// The path is canonicalized first, see `IntBezierPath::canonicalized`.
+    #[inline]
+    pub fn add_bezier_path(&mut self, path: &IntBezierPath, shape_type: ShapeType) {
+        let path = path.canonicalized();
+        let n = path.anchors.len();
+        for i in 0..n {
+            let a = &path.anchors[i];
+            let b = &path.anchors[(i + 1) % n];
+            self.sections.push(ShapeSegment {
+                shape_type,
+                part: ShapePart::Spline(IntSpline::new(a, b)),
+            });
+        }
+    }
+
+    /// `add_bezier_path` for a path that is not known to be in range, see `IntBezierPath::validate`.
+    /// A path out of range is not added.
+    #[inline]
+    pub fn try_add_bezier_path(&mut self, path: &IntBezierPath, shape_type: ShapeType) -> Result<(), RangeError> {
+        path.validate()?;
+        self.add_bezier_path(path, shape_type);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn add_bezier_paths(&mut self, paths: &[IntBezierPath], shape_type: ShapeType) {
+        for path in paths.iter() {
+            self.add_bezier_path(path, shape_type);
+        }
+    }
+
+    /// Applies the boolean operation to the approximated shapes.
+    /// Returns closed polygons, outer contours are counter-clockwise and holes are clockwise.
+    /// The result is empty if the crossings cannot be resolved, see `try_overlay`.
+    #[inline]
+    pub fn overlay(&self, overlay_rule: OverlayRule, fill_rule: FillRule) -> Vec<Vec<IntPoint>> {
+        self.try_overlay(overlay_rule, fill_rule).unwrap_or_default()
+    }
+
+    /// `overlay` that fails with `Error::UnresolvedCrossings` instead of returning an empty result.
+    pub fn try_overlay(&self, overlay_rule: OverlayRule, fill_rule: FillRule) -> Result<Vec<Vec<IntPoint>>, Error> {
+        let mut edges = Vec::new();
+        for section in self.sections.iter() {
+            let ShapePart::Spline(spline) = &section.part;
+            let points = self.spline_points(spline);
+            for w in points.windows(2) {
+                if let Some(edge) = ShapeEdge::with_points(w[0], w[1], section.shape_type) {
+                    edges.push(edge);
+                }
+            }
+        }
+
+        let edges = edges.split_segments().ok_or(Error::UnresolvedCrossings)?;
+        let fill_edges = edges.fill_edges(overlay_rule, fill_rule);
+
+        Ok(fill_edges.extract_contours())
+    }
+
+    /// The points where the approximated boundaries cross each other or themselves.
+    /// Only the points inside of edges are found, touching at a vertex is not a crossing.
+    /// The points are sorted and unique.
+    /// The result is empty if the crossings cannot be resolved, see `try_crossings`.
+    #[inline]
+    pub fn crossings(&self) -> Vec<IntPoint> {
+        self.try_crossings().unwrap_or_default()
+    }
+
+    /// `crossings` that fails with `Error::UnresolvedCrossings` instead of returning an empty result.
+    pub fn try_crossings(&self) -> Result<Vec<IntPoint>, Error> {
+        let mut vertices = Vec::new();
+        let mut edges = Vec::new();
+        for section in self.sections.iter() {
+            let ShapePart::Spline(spline) = &section.part;
+            let points = self.spline_points(spline);
+            for w in points.windows(2) {
+                if let Some(edge) = ShapeEdge::with_points(w[0], w[1], section.shape_type) {
+                    edges.push(edge);
+                }
+            }
+            vertices.extend(points);
+        }
+        vertices.sort_unstable();
+        vertices.dedup();
+
+        let mut crossings: Vec<IntPoint> = edges.split_segments()
+            .ok_or(Error::UnresolvedCrossings)?
+            .iter()
+            .flat_map(|e| [e.a, e.b])
+            .filter(|p| vertices.binary_search(p).is_err())
+            .collect();
+        crossings.sort_unstable();
+        crossings.dedup();
+
+        Ok(crossings)
+    }
+
+    #[inline]
+    fn spline_points(&self, spline: &IntSpline) -> Vec<IntPoint> {
+        spline.approximate_points(self.min_cos, self.min_len)
+    }
+}
+
+impl Default for Overlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::bezier::anchor::{AnchorKind, IntBezierAnchor};
+    use crate::int::bezier::path::IntBezierPath;
+    use crate::int::bool::core::fill_rule::FillRule;
+    use crate::int::bool::core::overlay::{Overlay, ShapeType};
+    use crate::int::bool::core::overlay_rule::OverlayRule;
+    use crate::int::math::offset::IntOffset;
+    use crate::int::math::point::IntPoint;
+    use crate::int::math::range::{RangeError, MAX_COORD};
+
+    fn square(x: i64, y: i64, size: i64) -> IntBezierPath {
+        let corner = |x: i64, y: i64| IntBezierAnchor {
+            point: IntPoint::new(x, y),
+            handle_in: None,
+            handle_out: None,
+            kind: AnchorKind::Corner,
+        };
+        IntBezierPath {
+            anchors: vec![
+                corner(x, y),
+                corner(x + size, y),
+                corner(x + size, y + size),
+                corner(x, y + size),
+            ],
+            closed: true,
+        }
+    }
+
+    fn area(contour: &[IntPoint]) -> i64 {
+        let n = contour.len();
+        (0..n).map(|i| contour[i].cross_product(&contour[(i + 1) % n])).sum::<i64>() / 2
+    }
+
+    fn overlay(rule: OverlayRule) -> Vec<Vec<IntPoint>> {
+        let mut overlay = Overlay::new();
+        overlay.add_bezier_path(&square(0, 0, 10), ShapeType::Subject);
+        overlay.add_bezier_path(&square(5, 5, 10), ShapeType::Clip);
+        overlay.overlay(rule, FillRule::NonZero)
+    }
+
+    #[test]
+    fn test_00() {
+        let result = overlay(OverlayRule::Union);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 8);
+        assert_eq!(area(&result[0]), 175);
+    }
+
+    #[test]
+    fn test_01() {
+        let result = overlay(OverlayRule::Intersect);
+        assert_eq!(result.len(), 1);
+        assert_eq!(area(&result[0]), 25);
+    }
+
+    #[test]
+    fn test_02() {
+        let result = overlay(OverlayRule::Difference);
+        assert_eq!(result.len(), 1);
+        assert_eq!(area(&result[0]), 75);
+
+        let result = overlay(OverlayRule::Xor);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.iter().map(|c| area(c)).sum::<i64>(), 150);
+    }
+
+    #[test]
+    fn test_03() {
+        // a hole
+        let mut overlay = Overlay::new();
+        overlay.add_bezier_path(&square(0, 0, 30), ShapeType::Subject);
+        overlay.add_bezier_path(&square(10, 10, 10), ShapeType::Clip);
+        let result = overlay.overlay(OverlayRule::Difference, FillRule::EvenOdd);
+
+        assert_eq!(result.len(), 2);
+        let mut areas: Vec<_> = result.iter().map(|c| area(c)).collect();
+        areas.sort();
+        assert_eq!(areas, vec![-100, 900]);
+    }
+
+    #[test]
+    fn test_04() {
+        // squares touching by a corner stay separated
+        let mut overlay = Overlay::new();
+        overlay.add_bezier_path(&square(0, 0, 10), ShapeType::Subject);
+        overlay.add_bezier_path(&square(10, 10, 10), ShapeType::Clip);
+        let result = overlay.overlay(OverlayRule::Union, FillRule::NonZero);
+
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|c| c.len() == 4 && area(c) == 100));
+    }
+
+    #[test]
+    fn test_05() {
+        let mut overlay = Overlay::new();
+        overlay.add_bezier_path(&square(0, 0, 10), ShapeType::Subject);
+        overlay.add_bezier_path(&square(5, 5, 10), ShapeType::Clip);
+
+        assert_eq!(overlay.crossings(), vec![IntPoint::new(5, 10), IntPoint::new(10, 5)]);
+    }
+
+    #[test]
+    fn test_06() {
+        // touching by a corner and sharing an edge does not cross
+        let mut overlay = Overlay::new();
+        overlay.add_bezier_path(&square(0, 0, 10), ShapeType::Subject);
+        overlay.add_bezier_path(&square(10, 10, 10), ShapeType::Clip);
+        overlay.add_bezier_path(&square(10, 0, 10), ShapeType::Clip);
+
+        assert!(overlay.crossings().is_empty());
+    }
+
+    #[test]
+    fn test_07() {
+        // the squares span the whole range
+        let half = MAX_COORD / 2;
+        let mut overlay = Overlay::new();
+        assert!(overlay.try_add_bezier_path(&square(-MAX_COORD, -MAX_COORD, MAX_COORD + half), ShapeType::Subject).is_ok());
+        assert!(overlay.try_add_bezier_path(&square(-half, -half, MAX_COORD + half), ShapeType::Clip).is_ok());
+        assert_eq!(
+            overlay.try_add_bezier_path(&square(0, 0, MAX_COORD + 1), ShapeType::Clip),
+            Err(RangeError { anchor_index: 1 })
+        );
+
+        let result = overlay.overlay(OverlayRule::Intersect, FillRule::NonZero);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 4);
+        assert!(result[0].contains(&IntPoint::new(half, half)));
+        assert!(result[0].contains(&IntPoint::new(-half, -half)));
+    }
+
+    #[test]
+    fn test_08() {
+        // zero length handles and repeated anchors give the same result as the clean square
+        let mut degenerate = square(0, 0, 10);
+        degenerate.anchors[0].handle_out = Some(IntOffset::zero());
+        degenerate.anchors[2].handle_in = Some(IntOffset::zero());
+        degenerate.anchors.insert(1, degenerate.anchors[1]);
+        degenerate.anchors.push(degenerate.anchors[0]);
+
+        let mut overlay = Overlay::new();
+        overlay.add_bezier_path(&degenerate, ShapeType::Subject);
+        overlay.add_bezier_path(&square(5, 5, 10), ShapeType::Clip);
+
+        let result = overlay.overlay(OverlayRule::Union, FillRule::NonZero);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 8);
+        assert_eq!(area(&result[0]), 175);
+    }
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// The boolean operation applied to the subject and clip shapes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverlayRule {
+    #[default]
+    Union,
+    Intersect,
+    Difference,
+    Xor,
+}
+
+impl OverlayRule {
+    #[inline(always)]
+    pub(crate) fn is_filled(&self, subj: bool, clip: bool) -> bool {
+        match self {
+            OverlayRule::Union => subj || clip,
+            OverlayRule::Intersect => subj && clip,
+            OverlayRule::Difference => subj && !clip,
+            OverlayRule::Xor => subj != clip,
+        }
+    }
+}
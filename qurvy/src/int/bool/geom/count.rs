@@ -5,6 +5,7 @@ pub struct ShapeCountBoolean {
 }
 
 impl ShapeCountBoolean {
+    pub(crate) const ZERO: ShapeCountBoolean = ShapeCountBoolean { subj: 0, clip: 0 };
     pub(crate) const SUBJ_DIRECT: ShapeCountBoolean = ShapeCountBoolean { subj: 1, clip: 0 };
     pub(crate) const SUBJ_INVERT: ShapeCountBoolean = ShapeCountBoolean { subj: -1, clip: 0 };
     pub(crate) const CLIP_DIRECT: ShapeCountBoolean = ShapeCountBoolean { subj: 0, clip: 1 };
@@ -12,12 +13,11 @@ impl ShapeCountBoolean {
 }
 
 impl ShapeCountBoolean {
-
     #[inline(always)]
     fn new(subj: i32, clip: i32) -> Self { Self { subj, clip } }
 
     #[inline(always)]
-    fn add(self, count: Self) -> Self {
+    pub(crate) fn add(self, count: Self) -> Self {
         let subj = self.subj + count.subj;
         let clip = self.clip + count.clip;
 
@@ -25,13 +25,18 @@ impl ShapeCountBoolean {
     }
 
     #[inline(always)]
-    fn apply(&mut self, count: Self) {
+    pub(crate) fn apply(&mut self, count: Self) {
         self.subj += count.subj;
         self.clip += count.clip;
     }
 
     #[inline(always)]
-    fn invert(self) -> Self {
+    pub(crate) fn invert(self) -> Self {
         Self { subj: -self.subj, clip: -self.clip }
     }
-}
\ No newline at end of file
+
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.subj == 0 && self.clip == 0
+    }
+}
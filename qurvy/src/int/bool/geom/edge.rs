@@ -0,0 +1,45 @@
+use crate::int::bool::core::overlay::ShapeType;
+use crate::int::bool::geom::count::ShapeCountBoolean;
+use crate::int::math::point::IntPoint;
+
+/// A straight piece of a shape boundary. The end points are ordered (`a < b`),
+/// the direction of the source contour is kept in the sign of `count`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ShapeEdge {
+    pub(crate) a: IntPoint,
+    pub(crate) b: IntPoint,
+    pub(crate) count: ShapeCountBoolean,
+}
+
+impl ShapeEdge {
+    #[inline]
+    pub(crate) fn with_points(a: IntPoint, b: IntPoint, shape_type: ShapeType) -> Option<Self> {
+        let (direct, invert) = match shape_type {
+            ShapeType::Subject => (ShapeCountBoolean::SUBJ_DIRECT, ShapeCountBoolean::SUBJ_INVERT),
+            ShapeType::Clip => (ShapeCountBoolean::CLIP_DIRECT, ShapeCountBoolean::CLIP_INVERT),
+        };
+
+        if a < b {
+            Some(Self { a, b, count: direct })
+        } else if b < a {
+            Some(Self { a: b, b: a, count: invert })
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_vertical(&self) -> bool {
+        self.a.x == self.b.x
+    }
+
+    #[inline(always)]
+    pub(crate) fn min_y(&self) -> i64 {
+        self.a.y.min(self.b.y)
+    }
+
+    #[inline(always)]
+    pub(crate) fn max_y(&self) -> i64 {
+        self.a.y.max(self.b.y)
+    }
+}
@@ -1,2 +1,3 @@
 pub(crate) mod count;
-pub(crate) mod segment;
\ No newline at end of file
+pub(crate) mod edge;
+pub(crate) mod segment;
@@ -1 +1 @@
-mod solver;
\ No newline at end of file
+pub(crate) mod solver;
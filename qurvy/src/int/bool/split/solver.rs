@@ -1,18 +1,260 @@
-use crate::int::bool::geom::segment::ShapeSegment;
+use crate::int::bool::geom::edge::ShapeEdge;
+use crate::int::math::point::IntPoint;
 
 pub(crate) trait SplitSegments {
-    fn split_segments(self) -> Vec<ShapeSegment>;
+    fn split_segments(self) -> Option<Vec<ShapeEdge>>;
 }
 
-impl SplitSegments for Vec<ShapeSegment> {
-    fn split_segments(self) -> Vec<ShapeSegment> {
-        // let mut fragments = Vec::new();
-        // let split_factor = 3;
-        // for (i, s) in self.iter().enumerate() {
-        //     let mut sub_segments = s.to_fragments(i, split_factor);
-        //     fragments.append(&mut sub_segments);
-        // }
+impl SplitSegments for Vec<ShapeEdge> {
+    /// Splits the edges at every crossing and merges the coincident ones.
+    /// A crossing is rounded to the nearest integer point, which can produce new crossings,
+    /// so the split is repeated until nothing changes.
+    /// Returns `None` if the edges still cross after `MAX_ITERATIONS`.
+    fn split_segments(self) -> Option<Vec<ShapeEdge>> {
+        const MAX_ITERATIONS: usize = 16;
 
-        self
+        let mut edges = merge(self);
+        for _ in 0..MAX_ITERATIONS {
+            let mut marks = vec![Vec::new(); edges.len()];
+            let mut any = false;
+            for i in 0..edges.len() {
+                // `merge` sorts the edges by `a`, the next edges start right of the end of edge `i`
+                for j in i + 1..edges.len() {
+                    if edges[j].a.x > edges[i].b.x {
+                        break;
+                    }
+                    any |= cross(&edges, i, j, &mut marks);
+                }
+            }
+
+            if !any {
+                return Some(edges);
+            }
+
+            let mut result = Vec::with_capacity(edges.len() + marks.len());
+            for (edge, mut points) in edges.into_iter().zip(marks) {
+                if points.is_empty() {
+                    result.push(edge);
+                    continue;
+                }
+                // a rounded point can be off the edge line, so the order of the points
+                // along the edge is taken from the projection and not from their own order
+                let dir = edge.b - edge.a;
+                let sqr_len = dir.dot_product_i128(&dir);
+                let position = |p: IntPoint| (p - edge.a).dot_product_i128(&dir);
+                points.retain(|&p| 0 < position(p) && position(p) < sqr_len);
+                points.sort_unstable_by(|&p0, &p1| position(p0).cmp(&position(p1)).then(p0.cmp(&p1)));
+                points.dedup();
+                points.insert(0, edge.a);
+                points.push(edge.b);
+                for w in points.windows(2) {
+                    // a piece can turn against the order of the points, `count` follows it
+                    result.push(if w[0] < w[1] {
+                        ShapeEdge { a: w[0], b: w[1], count: edge.count }
+                    } else {
+                        ShapeEdge { a: w[1], b: w[0], count: edge.count.invert() }
+                    });
+                }
+            }
+
+            edges = merge(result);
+        }
+
+        None
+    }
+}
+
+/// Sums the counts of equal edges and drops the ones that cancel out.
+fn merge(mut edges: Vec<ShapeEdge>) -> Vec<ShapeEdge> {
+    edges.sort_unstable_by(|e0, e1| e0.a.cmp(&e1.a).then(e0.b.cmp(&e1.b)));
+
+    let mut result: Vec<ShapeEdge> = Vec::with_capacity(edges.len());
+    for edge in edges.into_iter() {
+        if let Some(last) = result.last_mut().filter(|last| last.a == edge.a && last.b == edge.b) {
+            last.count.apply(edge.count);
+            continue;
+        }
+        result.push(edge);
+    }
+
+    result.retain(|e| !e.count.is_empty());
+    result
+}
+
+/// Marks the split points of edges `i` and `j`. Returns true if any point was marked.
+fn cross(edges: &[ShapeEdge], i: usize, j: usize, marks: &mut [Vec<IntPoint>]) -> bool {
+    let e0 = &edges[i];
+    let e1 = &edges[j];
+
+    // `a` is the left end point of an edge
+    if e1.a.x > e0.b.x || e0.a.x > e1.b.x || e1.min_y() > e0.max_y() || e0.min_y() > e1.max_y() {
+        return false;
+    }
+
+    // a vertex closer than half a unit to the other edge splits it, the same as a rounded
+    // crossing would, otherwise two almost collinear edges keep crossing after every split.
+    // The pieces meet at that vertex, so their crossing is left to the next iteration.
+    let mut any = false;
+    for p in [e1.a, e1.b] {
+        if is_near_inside(e0, p) {
+            marks[i].push(p);
+            any = true;
+        }
+    }
+    for p in [e0.a, e0.b] {
+        if is_near_inside(e1, p) {
+            marks[j].push(p);
+            any = true;
+        }
+    }
+
+    if any {
+        return true;
+    }
+
+    let r = e0.b - e0.a;
+    let s = e1.b - e1.a;
+    let qp = e1.a - e0.a;
+
+    let denom = r.cross_product_i128(&s);
+    if denom == 0 {
+        // parallel or collinear, the shared parts are split at the vertices above
+        return false;
+    }
+
+    let t = qp.cross_product_i128(&s);
+    let u = qp.cross_product_i128(&r);
+
+    let (t, u, denom) = if denom < 0 { (-t, -u, -denom) } else { (t, u, denom) };
+    if t < 0 || t > denom || u < 0 || u > denom {
+        return false;
+    }
+
+    let x = e0.a.x + div_round(r.x as i128 * t, denom) as i64;
+    let y = e0.a.y + div_round(r.y as i128 * t, denom) as i64;
+    let p = IntPoint::new(x, y);
+
+    let mut any = false;
+    if p != e0.a && p != e0.b {
+        marks[i].push(p);
+        any = true;
+    }
+    if p != e1.a && p != e1.b {
+        marks[j].push(p);
+        any = true;
+    }
+
+    any
+}
+
+/// True if `p` is not an end of the edge, projects inside of it and is closer than half a unit to it.
+fn is_near_inside(edge: &ShapeEdge, p: IntPoint) -> bool {
+    if p == edge.a || p == edge.b {
+        return false;
+    }
+
+    let r = edge.b - edge.a;
+    let ap = p - edge.a;
+    let sqr_len = r.dot_product_i128(&r);
+    let position = ap.dot_product_i128(&r);
+    if position <= 0 || position >= sqr_len {
+        return false;
     }
-}
\ No newline at end of file
+
+    // distance = |cross| / len < 1/2
+    2 * ap.cross_product_i128(&r).unsigned_abs() < (sqr_len as u128).isqrt()
+}
+
+/// Division rounded to the nearest integer, `b` must be positive.
+#[inline(always)]
+fn div_round(a: i128, b: i128) -> i128 {
+    let half = b >> 1;
+    if a >= 0 {
+        (a + half) / b
+    } else {
+        (a - half) / b
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::bool::core::overlay::ShapeType;
+    use crate::int::bool::geom::edge::ShapeEdge;
+    use crate::int::bool::split::solver::{cross, SplitSegments};
+    use crate::int::math::point::IntPoint;
+
+    fn edge(x0: i64, y0: i64, x1: i64, y1: i64, shape_type: ShapeType) -> ShapeEdge {
+        ShapeEdge::with_points(IntPoint::new(x0, y0), IntPoint::new(x1, y1), shape_type).unwrap()
+    }
+
+    #[test]
+    fn test_00() {
+        let edges = vec![
+            edge(0, 0, 10, 10, ShapeType::Subject),
+            edge(0, 10, 10, 0, ShapeType::Clip),
+        ].split_segments().unwrap();
+
+        assert_eq!(edges.len(), 4);
+        assert!(edges.iter().all(|e| e.a == IntPoint::new(5, 5) || e.b == IntPoint::new(5, 5)));
+    }
+
+    #[test]
+    fn test_01() {
+        let edges = vec![
+            edge(0, 0, 10, 0, ShapeType::Subject),
+            edge(5, 0, 15, 0, ShapeType::Subject),
+            edge(10, 0, 5, 0, ShapeType::Clip),
+        ].split_segments().unwrap();
+
+        assert_eq!(edges.len(), 3);
+        let middle = edges.iter().find(|e| e.a == IntPoint::new(5, 0)).unwrap();
+        assert_eq!(middle.count.subj, 2);
+        assert_eq!(middle.count.clip, -1);
+    }
+
+    #[test]
+    fn test_02() {
+        let edges = vec![
+            edge(0, 0, 10, 0, ShapeType::Subject),
+            edge(10, 0, 0, 0, ShapeType::Subject),
+        ].split_segments().unwrap();
+
+        assert!(edges.is_empty());
+    }
+
+    #[test]
+    fn test_03() {
+        // the crossing is rounded to the start of the first edge, off the line of the second one
+        // and left of its start, the second edge is still split into two pieces
+        let edges = vec![
+            edge(-9427, -233, -9215, -239, ShapeType::Clip),
+            edge(-9427, -229, -9400, -496, ShapeType::Subject),
+        ].split_segments().unwrap();
+
+        assert_eq!(edges.len(), 3);
+        let a = IntPoint::new(-9427, -233);
+        let vertical = edges.iter().find(|e| e.a == a && e.b == IntPoint::new(-9427, -229)).unwrap();
+        assert_eq!(vertical.count.subj, -1);
+        let tail = edges.iter().find(|e| e.a == a && e.b == IntPoint::new(-9400, -496)).unwrap();
+        assert_eq!(tail.count.subj, 1);
+    }
+
+    #[test]
+    fn test_04() {
+        // two almost collinear edges start one next to the other, every rounded crossing
+        // used to start a new pair of them a few units further
+        let edges = vec![
+            edge(0, 0, 9, 0, ShapeType::Clip),
+            edge(0, 0, 13673, -29, ShapeType::Clip),
+            edge(9, 0, 4249, -21, ShapeType::Clip),
+            edge(13673, -29, 9, 0, ShapeType::Clip),
+        ].split_segments().unwrap();
+
+        let mut marks = vec![Vec::new(); edges.len()];
+        for i in 0..edges.len() {
+            for j in i + 1..edges.len() {
+                assert!(!cross(&edges, i, j, &mut marks));
+            }
+        }
+    }
+}
@@ -19,6 +19,14 @@ impl IntLine {
         IntPoint::new(x, y)
     }
 
+    /// The point at `k / 3` of the line, used for degree elevation.
+    #[inline]
+    pub(crate) fn third_at(&self, k: i64) -> IntPoint {
+        let x = self.a.x + ((self.b.x - self.a.x) as i128 * k as i128 / 3) as i64;
+        let y = self.a.y + ((self.b.y - self.a.y) as i128 * k as i128 / 3) as i64;
+        IntPoint::new(x, y)
+    }
+
     #[inline]
     fn split_one_dimension_at(a: i64, b: i64, step: usize, split_factor: u32) -> i64 {
         let ab = b.wrapping_sub(a) as i128;
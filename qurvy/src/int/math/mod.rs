@@ -1,4 +1,5 @@
 pub mod point;
 pub mod offset;
+pub mod range;
 pub(crate) mod line;
 pub(crate) mod rect;
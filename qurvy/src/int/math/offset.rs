@@ -1,6 +1,8 @@
+use std::ops;
 use serde::{Deserialize, Serialize};
 use crate::convert::grid::Grid;
 use crate::convert::to_float::ToFloat;
+use crate::float::math::number::FloatNumber;
 use crate::float::math::offset::Offset;
 use crate::int::math::point::IntPoint;
 
@@ -19,6 +21,28 @@ impl IntOffset {
     pub fn new(x: i64, y: i64) -> Self {
         Self { x, y }
     }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.x == 0 && self.y == 0
+    }
+
+    #[inline]
+    pub fn length(&self) -> f64 {
+        (self.x as f64).hypot(self.y as f64)
+    }
+}
+
+impl ops::Neg for IntOffset {
+    type Output = IntOffset;
+
+    #[inline(always)]
+    fn neg(self) -> IntOffset {
+        IntOffset {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
 }
 
 impl From<IntPoint> for IntOffset {
@@ -28,11 +52,11 @@ impl From<IntPoint> for IntOffset {
     }
 }
 
-impl ToFloat<Offset> for IntOffset {
+impl<T: FloatNumber> ToFloat<Offset<T>> for IntOffset {
     #[inline]
-    fn to_float(&self, grid: &Grid) -> Offset {
-        let x = grid.int_to_float(self.x);
-        let y = grid.int_to_float(self.y);
+    fn to_float(&self, grid: &Grid) -> Offset<T> {
+        let x = T::from_f64(grid.int_to_float(self.x));
+        let y = T::from_f64(grid.int_to_float(self.y));
 
         Offset::new(x, y)
     }
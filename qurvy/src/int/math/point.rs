@@ -1,11 +1,13 @@
 use crate::convert::grid::Grid;
 use crate::convert::to_float::ToFloat;
+use crate::error::Error;
+use crate::float::math::number::FloatNumber;
 use crate::float::math::point::Point;
 use crate::int::math::offset::IntOffset;
 use serde::{Deserialize, Serialize};
 use std::ops;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct IntPoint {
     pub x: i64,
     pub y: i64,
@@ -22,6 +24,9 @@ impl IntPoint {
         Self { x, y }
     }
 
+    /// The vector scaled to the length of 1024, every coordinate stays within `[-1024, 1024]`.
+    /// The length is rounded down before the division, so a short vector comes out a bit longer.
+    /// Panics on a zero vector, `try_normalized_10bit` reports it instead.
     #[inline]
     pub fn normalized_10bit(&self) -> IntPoint {
         let dx = (self.x as i128).unsigned_abs().pow(2);
@@ -46,15 +51,36 @@ impl IntPoint {
         }
     }
 
+    /// `normalized_10bit` that fails with `Error::ZeroVector` instead of panicking.
+    #[inline]
+    pub fn try_normalized_10bit(&self) -> Result<IntPoint, Error> {
+        if self.x == 0 && self.y == 0 {
+            return Err(Error::ZeroVector);
+        }
+        Ok(self.normalized_10bit())
+    }
+
+    /// Exact for coordinates below 2^31, `dot_product_i128` covers the whole `MAX_COORD` range.
     #[inline]
     pub fn dot_product(&self, other: &Self) -> i64 {
         self.x * other.x + self.y * other.y
     }
 
+    /// Exact for coordinates below 2^31, `cross_product_i128` covers the whole `MAX_COORD` range.
     #[inline]
     pub fn cross_product(&self, other: &Self) -> i64 {
         self.x * other.y - self.y * other.x
     }
+
+    #[inline]
+    pub fn dot_product_i128(&self, other: &Self) -> i128 {
+        self.x as i128 * other.x as i128 + self.y as i128 * other.y as i128
+    }
+
+    #[inline]
+    pub fn cross_product_i128(&self, other: &Self) -> i128 {
+        self.x as i128 * other.y as i128 - self.y as i128 * other.x as i128
+    }
 }
 
 impl ops::Add for IntPoint {
@@ -112,11 +138,11 @@ impl From<IntOffset> for IntPoint {
     }
 }
 
-impl ToFloat<Point> for IntPoint {
+impl<T: FloatNumber> ToFloat<Point<T>> for IntPoint {
     #[inline]
-    fn to_float(&self, grid: &Grid) -> Point {
-        let x = grid.int_to_float(self.x);
-        let y = grid.int_to_float(self.y);
+    fn to_float(&self, grid: &Grid) -> Point<T> {
+        let x = T::from_f64(grid.int_to_float(self.x));
+        let y = T::from_f64(grid.int_to_float(self.y));
 
         Point::new(x, y)
     }
@@ -125,6 +151,7 @@ impl ToFloat<Point> for IntPoint {
 #[cfg(test)]
 mod tests {
     use super::IntPoint;
+    use crate::error::Error;
     use rand::Rng;
 
     #[test]
@@ -134,6 +161,12 @@ mod tests {
         assert_eq!(IntPoint::new(3, 4).normalized_10bit(), IntPoint::new(614, 819));
     }
 
+    #[test]
+    fn test_zero_vector_normalization() {
+        assert_eq!(IntPoint::new(0, 0).try_normalized_10bit(), Err(Error::ZeroVector));
+        assert_eq!(IntPoint::new(0, -7).try_normalized_10bit(), Ok(IntPoint::new(0, -1024)));
+    }
+
     #[test]
     fn test_big_numbers() {
         let x: i64 = 507758875930;
@@ -170,4 +203,17 @@ mod tests {
             assert!(error < 1024 * 5);
         }
     }
+
+    #[test]
+    fn test_products_at_max_coord() {
+        use crate::int::math::range::MAX_COORD;
+
+        let a = IntPoint::new(MAX_COORD, -MAX_COORD);
+        let b = IntPoint::new(MAX_COORD, MAX_COORD);
+        let sqr = (MAX_COORD as i128).pow(2);
+        assert_eq!(a.dot_product_i128(&b), 0);
+        assert_eq!(a.cross_product_i128(&b), 2 * sqr);
+        assert_eq!(a.dot_product_i128(&a), 2 * sqr);
+        assert_eq!(IntPoint::new(3, 4).dot_product_i128(&IntPoint::new(4, -3)), 0);
+    }
 }
@@ -0,0 +1,34 @@
+use std::fmt;
+use crate::int::math::point::IntPoint;
+
+/// The largest coordinate magnitude the int algorithms accept, for anchor points and for
+/// handle points (anchor point plus handle offset) alike.
+///
+/// The boolean split solver multiplies an edge vector (41 bits for two coordinates within
+/// the range) by a cross product of two edge vectors (83 bits), that has to fit `i128`.
+/// Everything else has a wider margin: the approximation splits a vector of 41 bits
+/// by at most 2^32 steps, and `IntPoint::normalized_10bit` shifts only vectors below 2^53.
+pub const MAX_COORD: i64 = 1 << 40;
+
+/// A coordinate of a path is outside of `[-MAX_COORD, MAX_COORD]`, or a float coordinate is not finite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeError {
+    /// The index of the first anchor that is out of range.
+    pub anchor_index: usize,
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "anchor {} is out of the int coordinate range ±2^40", self.anchor_index)
+    }
+}
+
+impl std::error::Error for RangeError {}
+
+impl IntPoint {
+    /// True if both coordinates are within `[-MAX_COORD, MAX_COORD]`.
+    #[inline]
+    pub fn is_in_range(&self) -> bool {
+        self.x.unsigned_abs() <= MAX_COORD as u64 && self.y.unsigned_abs() <= MAX_COORD as u64
+    }
+}
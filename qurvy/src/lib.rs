@@ -1,8 +1,12 @@
+pub mod bezier;
 pub mod float;
 pub mod int;
 pub mod convert;
+pub mod error;
 mod data;
 
+pub use error::Error;
+
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
 }
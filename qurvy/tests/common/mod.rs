@@ -0,0 +1,72 @@
+//! The seeded random input shared by the randomized suites.
+//!
+//! A failure prints the seed, `QURVY_SEED=<seed> cargo test --test <suite>` reproduces it.
+
+use qurvy::float::bezier::path::BezierPath;
+use qurvy::int::bezier::anchor::{AnchorKind, IntBezierAnchor};
+use qurvy::int::bezier::path::IntBezierPath;
+use qurvy::int::math::offset::IntOffset;
+use qurvy::int::math::point::IntPoint;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+pub const CASES: usize = 64;
+const DEFAULT_SEED: u64 = 0x5175_7276;
+
+pub fn seeded_rng() -> (StdRng, u64) {
+    let seed = std::env::var("QURVY_SEED")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_SEED);
+    (StdRng::seed_from_u64(seed), seed)
+}
+
+/// A path of lines, one and two control point splines, every handle is present with 70% chance.
+pub fn random_int_path(rng: &mut StdRng, closed: bool) -> IntBezierPath {
+    let n = rng.random_range(2..=6);
+    let offset = |rng: &mut StdRng| if rng.random_bool(0.7) {
+        Some(IntOffset::new(rng.random_range(-5_000..=5_000), rng.random_range(-5_000..=5_000)))
+    } else {
+        None
+    };
+    let anchors = (0..n).map(|_| IntBezierAnchor {
+        point: IntPoint::new(rng.random_range(-20_000..=20_000), rng.random_range(-20_000..=20_000)),
+        handle_in: offset(rng),
+        handle_out: offset(rng),
+        kind: AnchorKind::Corner,
+    }).collect();
+
+    IntBezierPath { anchors, closed }
+}
+
+/// The path sampled with `2^split_factor` points per spline, including its last point.
+pub fn int_polyline(path: &IntBezierPath, split_factor: u32) -> Vec<(f64, f64)> {
+    let mut points: Vec<_> = path.regular_points(split_factor).iter().map(|p| (p.x as f64, p.y as f64)).collect();
+    let last = if path.closed { path.anchors.first() } else { path.anchors.last() };
+    if let Some(a) = last {
+        points.push((a.point.x as f64, a.point.y as f64));
+    }
+    points
+}
+
+pub fn float_polyline(path: &BezierPath, split_factor: u32) -> Vec<(f64, f64)> {
+    let mut points: Vec<_> = path.regular_points(split_factor).iter().map(|p| (p.x, p.y)).collect();
+    let last = if path.closed { path.anchors.first() } else { path.anchors.last() };
+    if let Some(a) = last {
+        points.push((a.point.x, a.point.y));
+    }
+    points
+}
+
+fn segment_distance(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (abx, aby) = (b.0 - a.0, b.1 - a.1);
+    let (apx, apy) = (p.0 - a.0, p.1 - a.1);
+    let sqr_len = abx * abx + aby * aby;
+    let t = if sqr_len > 0.0 { ((apx * abx + apy * aby) / sqr_len).clamp(0.0, 1.0) } else { 0.0 };
+    let (dx, dy) = (apx - t * abx, apy - t * aby);
+    (dx * dx + dy * dy).sqrt()
+}
+
+pub fn polyline_distance(p: (f64, f64), polyline: &[(f64, f64)]) -> f64 {
+    polyline.windows(2).map(|w| segment_distance(p, w[0], w[1])).fold(f64::MAX, f64::min)
+}
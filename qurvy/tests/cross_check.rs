@@ -0,0 +1,114 @@
+//! Randomized cross-checks of the float and int stacks: the same curves, converted exactly
+//! between them, must give the same points and lengths up to the int rounding.
+//!
+//! `QURVY_SEED=<seed> cargo test --test cross_check` reproduces a failure.
+
+mod common;
+
+use qurvy::convert::grid::Grid;
+use qurvy::convert::to_float::ToFloat;
+use qurvy::float::bezier::path::BezierPath;
+use qurvy::int::bezier::path::IntBezierPath;
+use rand::Rng;
+use common::{float_polyline, int_polyline, polyline_distance, random_int_path, seeded_rng, CASES};
+
+// the same tolerances in the units of each stack
+const FLOAT_MIN_COS: f64 = 0.99;
+const FLOAT_MIN_LEN: f64 = 0.01;
+const INT_MIN_COS: u32 = 1014;
+const INT_MIN_LEN: u32 = 41;
+
+// 2^12 int units per float unit, the int coordinates convert to float exactly
+fn grid() -> Grid {
+    Grid::new(12, 0)
+}
+
+/// The int polyline in float units.
+fn int_polyline_float(path: &IntBezierPath, split_factor: u32) -> Vec<(f64, f64)> {
+    let grid = grid();
+    int_polyline(path, split_factor).iter().map(|&(x, y)| (grid.int_to_float(x as i64), grid.int_to_float(y as i64))).collect()
+}
+
+fn length(polyline: &[(f64, f64)]) -> f64 {
+    polyline.windows(2).map(|w| ((w[1].0 - w[0].0).powi(2) + (w[1].1 - w[0].1).powi(2)).sqrt()).sum()
+}
+
+/// The regular points, the approximation and the lengths of the same path agree in both stacks.
+/// The approximations stop at different chords where a chord is close to the limits, so they are
+/// checked against the curve of the other stack rather than against each other.
+fn check_path(float: &BezierPath, int: &IntBezierPath, seed: u64, case: usize) {
+    let unit = grid().int_to_float(1);
+
+    // each of the three de Casteljau levels of the int stack truncates a coordinate by less than a unit
+    let fs = float_polyline(float, 8);
+    let is = int_polyline_float(int, 8);
+    assert_eq!(fs.len(), is.len(), "seed {}, case {}", seed, case);
+    for (f, i) in fs.iter().zip(is.iter()) {
+        assert!((f.0 - i.0).abs() <= 3.0 * unit && (f.1 - i.1).abs() <= 3.0 * unit, "seed {}, case {}: {:?} {:?}", seed, case, f, i);
+    }
+    // the rounding of dense samples adds up along the curve, the lengths are compared on long chords,
+    // a few units of rounding remain on short curves
+    let (float_len, int_len) = (length(&float_polyline(float, 4)), length(&int_polyline_float(int, 4)));
+    assert!((float_len - int_len).abs() <= 1e-3 * float_len + 8.0 * unit, "seed {}, case {}: {} {}", seed, case, float_len, int_len);
+
+    let float_curve = float_polyline(float, 10);
+    let int_curve = int_polyline_float(int, 10);
+    let fs: Vec<_> = float.approximate_points(FLOAT_MIN_COS, FLOAT_MIN_LEN).iter().map(|p| (p.x, p.y)).collect();
+    let grid = grid();
+    let is: Vec<_> = int.approximate_points(INT_MIN_COS, INT_MIN_LEN).iter()
+        .map(|p| (grid.int_to_float(p.x), grid.int_to_float(p.y)))
+        .collect();
+    assert_eq!(fs.first(), is.first(), "seed {}, case {}", seed, case);
+    assert_eq!(fs.last(), is.last(), "seed {}, case {}", seed, case);
+    for &p in fs.iter() {
+        let d = polyline_distance(p, &int_curve);
+        assert!(d <= 8.0 * unit, "seed {}, case {}: float point {:?} is {} away from the int curve", seed, case, p, d);
+    }
+    for &p in is.iter() {
+        let d = polyline_distance(p, &float_curve);
+        assert!(d <= 8.0 * unit, "seed {}, case {}: int point {:?} is {} away from the float curve", seed, case, p, d);
+    }
+
+    // both approximations are inscribed in the same curve
+    let curve_len = length(&float_curve);
+    let float_len = float.avg_length(FLOAT_MIN_COS, FLOAT_MIN_LEN);
+    let int_len = int.avg_length(INT_MIN_COS, INT_MIN_LEN) * unit;
+    assert!(float_len <= curve_len * (1.0 + 1e-3), "seed {}, case {}: {} {}", seed, case, float_len, curve_len);
+    assert!(int_len <= curve_len * (1.0 + 1e-3), "seed {}, case {}: {} {}", seed, case, int_len, curve_len);
+}
+
+#[test]
+fn splines_of_every_kind() {
+    let (mut rng, seed) = seeded_rng();
+    // lines, one and two control point splines
+    let mut kinds = [0; 3];
+    for case in 0..CASES {
+        let closed = rng.random_bool(0.5);
+        let path = random_int_path(&mut rng, closed);
+        let n = path.anchors.len();
+        let count = if path.closed { n } else { n - 1 };
+        for i in 0..count {
+            let (a, b) = (path.anchors[i], path.anchors[(i + 1) % n]);
+            kinds[a.handle_out.is_some() as usize + b.handle_in.is_some() as usize] += 1;
+
+            let spline = IntBezierPath { anchors: vec![a, b], closed: false };
+            check_path(&spline.to_float(&grid()), &spline, seed, case);
+        }
+    }
+    assert!(kinds.iter().all(|&k| k > 0), "seed {}: {:?}", seed, kinds);
+}
+
+#[test]
+fn open_and_closed_paths() {
+    let (mut rng, seed) = seeded_rng();
+    for case in 0..CASES {
+        let closed = rng.random_bool(0.5);
+        let path = random_int_path(&mut rng, closed);
+        check_path(&path.to_float(&grid()), &path, seed, case);
+
+        if path.closed {
+            let points = path.approximate_points(INT_MIN_COS, INT_MIN_LEN);
+            assert_eq!(points.first(), points.last(), "seed {}, case {}", seed, case);
+        }
+    }
+}
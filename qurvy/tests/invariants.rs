@@ -0,0 +1,252 @@
+//! Randomized checks of the geometric invariants of the float and int paths.
+//!
+//! Every test runs `CASES` random cases from one seed. A failure prints the seed,
+//! `QURVY_SEED=<seed> cargo test --test invariants` reproduces it.
+
+mod common;
+
+use std::f64::consts::PI;
+use qurvy::float::bezier::anchor::BezierAnchor;
+use qurvy::float::bezier::path::BezierPath;
+use qurvy::float::math::offset::Offset;
+use qurvy::float::math::point::Point;
+use qurvy::int::bezier::anchor::{AnchorKind, IntBezierAnchor};
+use qurvy::int::bezier::path::IntBezierPath;
+use qurvy::int::bool::core::fill_rule::FillRule;
+use qurvy::int::bool::core::overlay::{Overlay, ShapeType};
+use qurvy::int::bool::core::overlay_rule::OverlayRule;
+use qurvy::int::math::point::IntPoint;
+use rand::rngs::StdRng;
+use rand::Rng;
+use common::{float_polyline, int_polyline, polyline_distance, random_int_path, seeded_rng, CASES};
+
+/// A smooth closed path through points around `center` at growing angles.
+fn random_star_path(rng: &mut StdRng, center: IntPoint) -> IntBezierPath {
+    let n = rng.random_range(3..=7);
+    let anchors = (0..n).map(|i| {
+        let angle = 2.0 * PI * (i as f64 + rng.random_range(0.0..0.5)) / n as f64;
+        let radius = rng.random_range(5_000.0..10_000.0);
+        IntBezierAnchor {
+            point: IntPoint::new(
+                center.x + (radius * angle.cos()) as i64,
+                center.y + (radius * angle.sin()) as i64,
+            ),
+            handle_in: None,
+            handle_out: None,
+            kind: AnchorKind::Corner,
+        }
+    }).collect();
+
+    let mut path = IntBezierPath { anchors, closed: true };
+    path.auto_smooth();
+    path
+}
+
+fn random_float_path(rng: &mut StdRng) -> BezierPath {
+    let n = rng.random_range(2..=5);
+    let anchors = (0..n).map(|_| BezierAnchor {
+        point: Point::new(rng.random_range(-100.0..100.0), rng.random_range(-100.0..100.0)),
+        handle_in: Some(Offset::new(rng.random_range(-30.0..30.0), rng.random_range(-30.0..30.0))),
+        handle_out: Some(Offset::new(rng.random_range(-30.0..30.0), rng.random_range(-30.0..30.0))),
+        kind: AnchorKind::Corner,
+    }).collect();
+
+    BezierPath { anchors, closed: false }
+}
+
+fn area(contours: &[Vec<IntPoint>]) -> i64 {
+    contours.iter().map(|c| {
+        let n = c.len();
+        (0..n).map(|i| c[i].cross_product(&c[(i + 1) % n])).sum::<i64>() / 2
+    }).sum()
+}
+
+fn perimeter(polyline: &[IntPoint]) -> f64 {
+    let n = polyline.len();
+    (0..n).map(|i| {
+        let (a, b) = (polyline[i], polyline[(i + 1) % n]);
+        (((b.x - a.x) as f64).powi(2) + ((b.y - a.y) as f64).powi(2)).sqrt()
+    }).sum()
+}
+
+/// Winding number of the closed contours around `p`.
+fn winding(contours: &[Vec<IntPoint>], p: (f64, f64)) -> i32 {
+    let mut winding = 0;
+    for c in contours {
+        let n = c.len();
+        for i in 0..n {
+            let (a, b) = (c[i], c[(i + 1) % n]);
+            let (ay, by) = (a.y as f64, b.y as f64);
+            let x = a.x as f64 + (p.1 - ay) / (by - ay) * (b.x - a.x) as f64;
+            if ay <= p.1 && p.1 < by && x > p.0 {
+                winding += 1;
+            } else if by <= p.1 && p.1 < ay && x > p.0 {
+                winding -= 1;
+            }
+        }
+    }
+    winding
+}
+
+#[test]
+fn split_and_rejoin() {
+    let (mut rng, seed) = seeded_rng();
+    for case in 0..CASES {
+        let closed = rng.random_bool(0.5);
+        let path = random_int_path(&mut rng, closed);
+        let spline_count = if path.closed { path.anchors.len() } else { path.anchors.len() - 1 };
+        let spline_index = rng.random_range(0..spline_count);
+        let t = rng.random_range(0.1..0.9);
+
+        let mut split = path.clone();
+        split.insert_anchor(spline_index, t);
+
+        // both halves together cover the source curve and nothing else
+        let source = int_polyline(&path, 8);
+        let joined = int_polyline(&split, 8);
+        for &p in source.iter() {
+            let d = polyline_distance(p, &joined);
+            assert!(d < 4.0, "seed {}, case {}: source point {:?} is {} away from the split path", seed, case, p, d);
+        }
+        for &p in joined.iter() {
+            let d = polyline_distance(p, &source);
+            assert!(d < 4.0, "seed {}, case {}: split point {:?} is {} away from the source path", seed, case, p, d);
+        }
+    }
+}
+
+#[test]
+fn approximation_points_on_curve() {
+    let (mut rng, seed) = seeded_rng();
+    for case in 0..CASES {
+        let closed = rng.random_bool(0.5);
+        let path = random_int_path(&mut rng, closed);
+        let min_cos = rng.random_range(900..=1020);
+        let curve = int_polyline(&path, 10);
+        for p in path.approximate_points(min_cos, 4) {
+            let p = (p.x as f64, p.y as f64);
+            let d = polyline_distance(p, &curve);
+            assert!(d < 8.0, "seed {}, case {}: approximation point {:?} is {} away from the curve", seed, case, p, d);
+        }
+    }
+}
+
+#[test]
+fn union_and_intersection_areas() {
+    let (mut rng, seed) = seeded_rng();
+    for case in 0..CASES {
+        let a = random_star_path(&mut rng, IntPoint::zero());
+        let center = IntPoint::new(rng.random_range(-12_000..=12_000), rng.random_range(-12_000..=12_000));
+        let b = random_star_path(&mut rng, center);
+
+        let shape_area = |path: &IntBezierPath| {
+            let mut overlay = Overlay::new();
+            overlay.add_bezier_path(path, ShapeType::Subject);
+            area(&overlay.overlay(OverlayRule::Union, FillRule::NonZero))
+        };
+
+        let mut overlay = Overlay::new();
+        overlay.add_bezier_path(&a, ShapeType::Subject);
+        overlay.add_bezier_path(&b, ShapeType::Clip);
+        let union = area(&overlay.overlay(OverlayRule::Union, FillRule::NonZero));
+        let intersection = area(&overlay.overlay(OverlayRule::Intersect, FillRule::NonZero));
+
+        // the crossings are rounded to the int grid, each one moves the boundary by less than a unit
+        let tolerance = perimeter(&a.approximate_points(1018, 4)) + perimeter(&b.approximate_points(1018, 4));
+        let diff = (union + intersection - shape_area(&a) - shape_area(&b)).abs() as f64;
+        assert!(diff <= tolerance, "seed {}, case {}: area difference {} exceeds {}", seed, case, diff, tolerance);
+    }
+}
+
+#[test]
+fn point_in_path_parity() {
+    let (mut rng, seed) = seeded_rng();
+    for case in 0..CASES {
+        let path = random_star_path(&mut rng, IntPoint::zero());
+        let mut overlay = Overlay::new();
+        overlay.add_bezier_path(&path, ShapeType::Subject);
+        let result = overlay.overlay(OverlayRule::Union, FillRule::EvenOdd);
+
+        // the boundary the overlay works with
+        let boundary = path.approximate_points(1018, 4);
+        let mut closed: Vec<_> = boundary.iter().map(|p| (p.x as f64, p.y as f64)).collect();
+        closed.push(closed[0]);
+
+        for _ in 0..32 {
+            let p = (rng.random_range(-11_000.0..11_000.0), rng.random_range(-11_000.0..11_000.0));
+            if polyline_distance(p, &closed) < 4.0 {
+                continue;
+            }
+            let parity = winding(std::slice::from_ref(&boundary), p) % 2 != 0;
+            let is_inside = winding(&result, p) != 0;
+            assert_eq!(parity, is_inside, "seed {}, case {}: point {:?}", seed, case, p);
+        }
+    }
+}
+
+#[test]
+fn normalized_10bit_bound() {
+    let (mut rng, seed) = seeded_rng();
+    for case in 0..CASES * 16 {
+        let bits = rng.random_range(1..=40);
+        let max = 1i64 << bits;
+        let v = IntPoint::new(rng.random_range(-max..=max), rng.random_range(-max..=max));
+        if v == IntPoint::zero() {
+            continue;
+        }
+
+        let n = v.normalized_10bit();
+        let len = ((n.x as f64).powi(2) + (n.y as f64).powi(2)).sqrt();
+        assert!(n.x.abs() <= 1024 && n.y.abs() <= 1024, "seed {}, case {}: {:?} -> {:?}", seed, case, v, n);
+        // the length is rounded down before the division, the result is never shorter
+        assert!(len >= 1021.0, "seed {}, case {}: {:?} -> {:?} has length {}", seed, case, v, n, len);
+        // the direction is kept
+        assert!(v.x.signum() * n.x.signum() >= 0 && v.y.signum() * n.y.signum() >= 0, "seed {}, case {}: {:?} -> {:?}", seed, case, v, n);
+    }
+}
+
+#[test]
+fn float_to_cubic_keeps_shape() {
+    let (mut rng, seed) = seeded_rng();
+    for case in 0..CASES {
+        let mut path = random_float_path(&mut rng);
+        for anchor in path.anchors.iter_mut() {
+            // mix the line and one control point forms in
+            if rng.random_bool(0.3) {
+                anchor.handle_in = None;
+            }
+            if rng.random_bool(0.3) {
+                anchor.handle_out = None;
+            }
+        }
+
+        let source = path.regular_points(4);
+        let cubic = path.to_cubic().regular_points(4);
+        assert_eq!(source.len(), cubic.len());
+        for (a, b) in source.iter().zip(cubic.iter()) {
+            assert!(a.distance(*b) < 1e-9, "seed {}, case {}: {:?} != {:?}", seed, case, a, b);
+        }
+    }
+}
+
+#[test]
+fn float_fit_within_tolerance() {
+    let (mut rng, seed) = seeded_rng();
+    for case in 0..CASES {
+        let source = random_float_path(&mut rng);
+        let tolerance = rng.random_range(0.2..2.0);
+        let points: Vec<Point> = source.regular_points(5).iter()
+            .map(|p| Point::new(
+                p.x + rng.random_range(-0.25..0.25) * tolerance,
+                p.y + rng.random_range(-0.25..0.25) * tolerance,
+            ))
+            .collect();
+
+        let fitted = BezierPath::fit(&points, tolerance, PI / 4.0);
+        let curve = float_polyline(&fitted, 8);
+        for p in points.iter() {
+            let d = polyline_distance((p.x, p.y), &curve);
+            assert!(d <= 1.01 * tolerance, "seed {}, case {}: point {:?} is {} away, tolerance {}", seed, case, p, d, tolerance);
+        }
+    }
+}
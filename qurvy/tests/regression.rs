@@ -0,0 +1,138 @@
+//! Runs the editor fixtures of `tests/intersect` and compares the results with the
+//! `test_N.expected.json` files next to them.
+//!
+//! `QURVY_BLESS=1 cargo test --test regression` rewrites the expected files.
+
+use std::path::{Path, PathBuf};
+use qurvy::convert::grid::Grid;
+use qurvy::convert::to_int::ToInt;
+use qurvy::float::bezier::path::BezierPath;
+use qurvy::int::bezier::path::IntBezierPath;
+use qurvy::int::bool::core::fill_rule::FillRule;
+use qurvy::int::bool::core::overlay::{Overlay, ShapeType};
+use qurvy::int::bool::core::overlay_rule::OverlayRule;
+use qurvy::int::math::point::IntPoint;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+struct CurveTest {
+    curves: Vec<BezierPath>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Expected {
+    crossings: Vec<IntPoint>,
+    boolean: Vec<BooleanResult>,
+}
+
+/// The first curve is the subject, the others are the clip.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct BooleanResult {
+    rule: OverlayRule,
+    contours: Vec<Contour>,
+}
+
+/// The topology of a result contour: a positive area is an outer contour, a negative one a hole.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Contour {
+    points: usize,
+    area: i64,
+}
+
+const RULES: [OverlayRule; 4] = [
+    OverlayRule::Union,
+    OverlayRule::Intersect,
+    OverlayRule::Difference,
+    OverlayRule::Xor,
+];
+
+fn fixtures_folder() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/intersect")
+}
+
+/// Indices of the `test_N.json` files, the expected files are skipped.
+fn test_indices(folder: &Path) -> Vec<usize> {
+    let mut indices: Vec<usize> = std::fs::read_dir(folder)
+        .expect("fixtures folder")
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_prefix("test_")?.strip_suffix(".json")?.parse().ok()
+        })
+        .collect();
+    indices.sort_unstable();
+    indices
+}
+
+fn area(contour: &[IntPoint]) -> i64 {
+    let n = contour.len();
+    (0..n).map(|i| contour[i].cross_product(&contour[(i + 1) % n])).sum::<i64>() / 2
+}
+
+fn solve(curves: &[IntBezierPath]) -> Expected {
+    let mut overlay = Overlay::new();
+    for (index, curve) in curves.iter().enumerate() {
+        let shape_type = if index == 0 { ShapeType::Subject } else { ShapeType::Clip };
+        overlay.add_bezier_path(curve, shape_type);
+    }
+
+    let boolean = RULES.iter().map(|&rule| {
+        let mut contours: Vec<Contour> = overlay.overlay(rule, FillRule::NonZero)
+            .iter()
+            .map(|c| Contour { points: c.len(), area: area(c) })
+            .collect();
+        // the order of the contours is not part of the result
+        contours.sort_unstable_by_key(|c| (c.area, c.points));
+        BooleanResult { rule, contours }
+    }).collect();
+
+    Expected { crossings: overlay.crossings(), boolean }
+}
+
+fn write_expected(path: &Path, expected: &Expected) {
+    let mut data = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut data, formatter);
+    expected.serialize(&mut serializer).unwrap();
+    data.push(b'\n');
+    std::fs::write(path, data).unwrap();
+}
+
+#[test]
+fn intersect_fixtures() {
+    let is_bless = std::env::var("QURVY_BLESS").is_ok_and(|v| v == "1");
+    let folder = fixtures_folder();
+    let indices = test_indices(&folder);
+    assert!(!indices.is_empty(), "no fixtures in {:?}", folder);
+
+    let grid = Grid::debug();
+    let mut failed = Vec::new();
+    for index in indices {
+        let data = std::fs::read_to_string(folder.join(format!("test_{}.json", index))).unwrap();
+        let test: CurveTest = serde_json::from_str(&data).unwrap();
+        let curves: Vec<IntBezierPath> = test.curves.iter().map(|c| c.to_int(&grid)).collect();
+        let result = solve(&curves);
+
+        let expected_path = folder.join(format!("test_{}.expected.json", index));
+        if is_bless {
+            write_expected(&expected_path, &result);
+            continue;
+        }
+
+        let expected: Option<Expected> = std::fs::read_to_string(&expected_path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok());
+        match expected {
+            Some(expected) if expected == result => {}
+            Some(expected) => {
+                eprintln!("test_{}: expected {:?}\n  got {:?}", index, expected, result);
+                failed.push(index);
+            }
+            None => {
+                eprintln!("test_{}: missing or unreadable {:?}", index, expected_path);
+                failed.push(index);
+            }
+        }
+    }
+
+    assert!(failed.is_empty(), "failed fixtures: {:?}, run with QURVY_BLESS=1 to accept the new results", failed);
+}